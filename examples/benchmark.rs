@@ -0,0 +1,55 @@
+//! This example shows how to use Perf UI to run a benchmark, for example in CI.
+//!
+//! The app runs headless for a few seconds and then exits, with an error
+//! code if the performance was not within budget.
+
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy::app::ScheduleRunnerPlugin;
+use bevy::log::LogPlugin;
+use iyes_perf_ui::prelude::*;
+
+fn main() -> AppExit {
+    App::new()
+        .add_plugins(MinimalPlugins.set(
+            // try to run at 60 FPS
+            ScheduleRunnerPlugin::run_loop(Duration::from_secs_f64(1.0 / 60.0))
+        ))
+        .add_plugins(LogPlugin::default())
+
+        // we want Bevy to measure these values for us:
+        .add_plugins(bevy::diagnostic::FrameTimeDiagnosticsPlugin::default())
+
+        .add_plugins(PerfUiPlugin)
+        .add_systems(Startup, setup)
+
+        .run()
+}
+
+fn setup(mut commands: Commands) {
+    commands.spawn((
+        PerfUiBenchmark {
+            // Let things settle. Wait longer than Bevy's diagnostics history
+            // (120 frames), so that startup hitches are not counted.
+            warmup: PerfUiBenchmarkLength::Seconds(3.0),
+            length: PerfUiBenchmarkLength::Frames(300),
+            // allow up to 1% of frames to be over budget
+            max_violation_fraction: 0.01,
+            ..default()
+        },
+        // The highlight thresholds are the budget
+        PerfUiEntryFPSAverage {
+            threshold_highlight: Some(50.0),
+            ..default()
+        },
+        PerfUiEntryFPSPctLow {
+            threshold_highlight: Some(30.0),
+            ..default()
+        },
+        PerfUiEntryFrameTimeWorst {
+            threshold_highlight: Some(1000.0 / 30.0),
+            ..default()
+        },
+    ));
+}
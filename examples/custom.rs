@@ -159,6 +159,15 @@ impl PerfUiEntry for PerfUiTimeSinceLastClick {
             .map(|t| (*value as f32) > t)
             .unwrap_or(false)
     }
+
+    // (optional) Provide the threshold, so that the `clear_margin`
+    // of the Perf UI's `highlight_debounce` settings can be applied
+    fn highlight_threshold(
+        &self,
+        _value: &Self::Value,
+    ) -> Option<f64> {
+        self.threshold_highlight.map(|t| t as f64)
+    }
 }
 
 fn handle_click(
@@ -0,0 +1,53 @@
+//! This example shows how to use Perf UI in a headless app (no UI or rendering),
+//! such as a dedicated server, by reporting the values as text.
+
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy::app::ScheduleRunnerPlugin;
+use bevy::log::LogPlugin;
+use iyes_perf_ui::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins(MinimalPlugins.set(
+            // run at 60 FPS
+            ScheduleRunnerPlugin::run_loop(Duration::from_secs_f64(1.0 / 60.0))
+        ))
+        .add_plugins(LogPlugin::default())
+
+        // we want Bevy to measure these values for us:
+        .add_plugins(bevy::diagnostic::FrameTimeDiagnosticsPlugin::default())
+        .add_plugins(bevy::diagnostic::EntityCountDiagnosticsPlugin)
+
+        .add_plugins(PerfUiPlugin)
+        .add_systems(Startup, setup)
+
+        .run();
+}
+
+fn setup(mut commands: Commands) {
+    // Log the values every 2 seconds
+    commands.spawn((
+        PerfUiTextOutput {
+            interval: PerfUiUpdateInterval::Seconds(2.0),
+            ..default()
+        },
+        PerfUiEntryFPS::default(),
+        PerfUiEntryFPSWorst::default(),
+        PerfUiEntryFrameTime::default(),
+        PerfUiEntryEntityCount::default(),
+        PerfUiEntryRunningTime::default(),
+    ));
+
+    // Or send them anywhere you like, using a custom function
+    commands.spawn((
+        PerfUiTextOutput {
+            interval: PerfUiUpdateInterval::Frames(600),
+            ..default()
+        }.with_callback(|_e_root, text| {
+            println!("{}", text);
+        }),
+        PerfUiEntryFrameCount::default(),
+    ));
+}
@@ -0,0 +1,35 @@
+//! This example shows how to load a Perf UI from a layout asset file.
+//!
+//! Run it with Bevy's `file_watcher` feature, and try editing
+//! `assets/layout.perfui.ron` while the example is running:
+//!
+//! ```sh
+//! cargo run --example layout --features asset,bevy/file_watcher
+//! ```
+
+use bevy::prelude::*;
+use iyes_perf_ui::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+
+        // we want Bevy to measure these values for us:
+        .add_plugins(bevy::diagnostic::FrameTimeDiagnosticsPlugin::default())
+        .add_plugins(bevy::diagnostic::EntityCountDiagnosticsPlugin)
+        .add_plugins(bevy::diagnostic::SystemInformationDiagnosticsPlugin)
+
+        .add_plugins(PerfUiPlugin)
+        .add_systems(Startup, setup)
+
+        .run();
+}
+
+fn setup(mut commands: Commands, ass: Res<AssetServer>) {
+    // spawn a camera to be able to see anything
+    commands.spawn(Camera2d);
+
+    // The root settings and all the entries come from the file.
+    // The Perf UI is (re)built whenever the file is loaded.
+    commands.spawn(PerfUiLayoutHandle(ass.load("layout.perfui.ron")));
+}
@@ -0,0 +1,48 @@
+//! This example shows how to record the values of a Perf UI to a file,
+//! to analyze them after a play session.
+
+use bevy::prelude::*;
+use iyes_perf_ui::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+
+        // we want Bevy to measure these values for us:
+        .add_plugins(bevy::diagnostic::FrameTimeDiagnosticsPlugin::default())
+        .add_plugins(bevy::diagnostic::EntityCountDiagnosticsPlugin)
+
+        .add_plugins(PerfUiPlugin)
+        .add_systems(Startup, setup)
+
+        .run();
+}
+
+fn setup(mut commands: Commands) {
+    // spawn a camera to be able to see anything
+    commands.spawn(Camera2d);
+
+    commands.spawn((
+        // Write the values of all entries to a CSV file, every frame,
+        // while also displaying them on-screen as usual.
+        PerfUiRecorder::new("perf_ui_trace.csv"),
+        PerfUiEntryFPS::default(),
+        PerfUiEntryFrameTime::default(),
+        PerfUiEntryEntityCount::default(),
+        PerfUiEntryRunningTime::default(),
+    ));
+
+    // Record less often, in JSON Lines format, without displaying anything.
+    commands.spawn((
+        PerfUiRoot {
+            display_labels: false,
+            ..default()
+        },
+        Visibility::Hidden,
+        PerfUiRecorder::new("perf_ui_trace.jsonl")
+            .with_format(PerfUiRecordFormat::JsonLines)
+            .with_interval(PerfUiUpdateInterval::Seconds(0.5)),
+        PerfUiEntryFPSWorst::default(),
+        PerfUiEntryFrameTimeWorst::default(),
+    ));
+}
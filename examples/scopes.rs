@@ -0,0 +1,60 @@
+//! This example shows how to measure the time spent in parts of your own code,
+//! and display it in the Perf UI, without writing a custom entry.
+
+use bevy::prelude::*;
+use iyes_perf_ui::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+
+        .add_plugins(bevy::diagnostic::FrameTimeDiagnosticsPlugin::default())
+
+        .add_plugins(PerfUiPlugin)
+        .add_systems(Startup, setup)
+        .add_systems(Update, (pathfinding, ai))
+
+        .run();
+}
+
+fn setup(mut commands: Commands) {
+    // spawn a camera to be able to see anything
+    commands.spawn(Camera2d);
+
+    commands.spawn((
+        PerfUiRoot {
+            // the min/avg/max are easier to read if they do not change every frame
+            update_interval: PerfUiUpdateInterval::Seconds(0.25),
+            ..default()
+        },
+        PerfUiEntryFPS::default(),
+        PerfUiEntryFrameTime::default(),
+        // every scope will appear here automatically, as a row
+        // with the min/avg/max time per frame
+        PerfUiScopes::default(),
+    ));
+}
+
+fn pathfinding(time: Res<Time>) {
+    // measure everything until the end of this function
+    perf_ui_scope!("pathfinding");
+    // between 3 and 5 ms, so it is easy to tell apart from "ai" (1 ms)
+    busy_wait(3.0 + 2.0 * time.elapsed_secs().sin().abs() as f64);
+}
+
+fn ai() {
+    for _ in 0..4 {
+        // the same scope can run several times per frame;
+        // the times will be added up
+        perf_ui_scope!("ai");
+        busy_wait(0.25);
+    }
+}
+
+/// Pretend to do some work, for the given number of milliseconds.
+fn busy_wait(ms: f64) {
+    let start = std::time::Instant::now();
+    while start.elapsed().as_secs_f64() * 1000.0 < ms {
+        std::hint::spin_loop();
+    }
+}
@@ -41,6 +41,13 @@ fn setup(mut commands: Commands, ass: Res<AssetServer>) {
         inner_margin: 2.0,
         inner_padding: 4.0,
         values_col_width: 128.0,
+        // avoid flickering highlights: values must stay past the threshold
+        // for 0.25 seconds, and cross back by 5% to clear
+        highlight_debounce: PerfUiHighlightDebounce {
+            seconds: 0.25,
+            clear_margin: 0.05,
+            ..default()
+        },
         font_label: ass.load("Ubuntu-B.ttf"),
         font_value: ass.load("Ubuntu-R.ttf"),
         font_highlight: ass.load("Ubuntu-RI.ttf"),
@@ -98,6 +105,38 @@ fn setup(mut commands: Commands, ass: Res<AssetServer>) {
             precision: 4,
             ..default()
         },
+        // Display the recent history of frame times as a graph
+        PerfUiWidgetGraph {
+            text_position: BarTextPosition::NoText,
+            samples: 120,
+            graph_height_px: Some(48.0),
+            // Each sample is colored individually
+            graph_color: ColorGradient::new().with_stops([
+                (1.0, Color::srgb(0.15, 0.8, 0.9)),
+                (8.0, Color::srgb(0.7, 0.15, 0.9))
+            ]),
+            ..PerfUiWidgetGraph::new(PerfUiEntryFrameTime {
+                label: "Frame Duration (history)".into(),
+                max_value_hint: Some(20.0),
+                ..default()
+            })
+        },
+        // Display the distribution of recent frame times as a histogram
+        PerfUiWidgetHistogram {
+            // 0.5 ms per bin
+            bin_width: 0.5,
+            histogram_height_px: Some(48.0),
+            ..PerfUiWidgetHistogram::new(PerfUiEntryFrameTime {
+                label: "Frame Duration (distribution)".into(),
+                // bins are colored using the entry's gradient
+                color_gradient: ColorGradient::new().with_stops([
+                    (1.0, Color::srgb(0.15, 0.8, 0.9)),
+                    (8.0, Color::srgb(0.7, 0.15, 0.9))
+                ]),
+                max_value_hint: Some(20.0),
+                ..default()
+            })
+        },
     ));
 
     // Perf UI #2: ECS stats + System CPU/RAM usage
@@ -107,6 +146,8 @@ fn setup(mut commands: Commands, ass: Res<AssetServer>) {
             position: PerfUiPosition::BottomLeft,
             // always display this Perf UI below the other one
             z_index: GlobalZIndex(i32::MAX - 1),
+            // these values don't need to be updated every frame
+            update_interval: PerfUiUpdateInterval::Seconds(0.25),
             ..root_config.clone()
         },
         PerfUiWidgetBar {
@@ -150,18 +191,18 @@ fn setup(mut commands: Commands, ass: Res<AssetServer>) {
         PerfUiWidgetBar {
             text_position: BarTextPosition::OutsideEnd,
             bar_background: Color::srgba(0.0, 0.0, 0.0, 0.5),
-            bar_color: ColorGradient::new().with_stops([
-                (0.0, Color::srgb(0.0, 0.0, 1.0)),
-                (100.0, Color::srgb(1.0, 0.0, 0.0)),
-            ]),
+            // There are also colorblind-friendly presets. In "stepped" mode,
+            // colors are not interpolated, so the levels are easy to tell apart.
+            bar_color: ColorGradient::new_preset_cividis(0.0, 50.0, 100.0)
+                .unwrap()
+                .with_stepped(true),
             bar_border_color: Color::WHITE,
             bar_border_px: 2.0,
             ..PerfUiWidgetBar::new(PerfUiEntryMemUsage {
                 label: "System RAM Utilization".into(),
-                color_gradient: ColorGradient::new().with_stops([
-                    (0.0, Color::srgb(0.0, 0.0, 1.0)),
-                    (100.0, Color::srgb(1.0, 0.0, 0.0)),
-                ]),
+                color_gradient: ColorGradient::new_preset_cividis(0.0, 50.0, 100.0)
+                    .unwrap()
+                    .with_stepped(true),
                 threshold_highlight: None,
                 precision: 1,
                 ..default()
@@ -13,6 +13,10 @@ fn main() {
         .add_plugins(bevy::diagnostic::EntityCountDiagnosticsPlugin)
         .add_plugins(bevy::diagnostic::SystemInformationDiagnosticsPlugin)
         .add_plugins(bevy::render::diagnostic::RenderDiagnosticsPlugin)
+        // and this one measures the time spent in each schedule:
+        .add_plugins(PerfUiScheduleTimingPlugin)
+        // and this one counts archetypes, tables, components, resources:
+        .add_plugins(PerfUiWorldStatsPlugin::default())
 
         .add_plugins(PerfUiPlugin)
         .add_systems(Startup, setup)
@@ -33,18 +37,38 @@ fn setup(mut commands: Commands) {
             PerfUiWidgetBar::new(PerfUiEntryFPSWorst::default()),
             PerfUiWidgetBar::new(PerfUiEntryFrameTime::default()),
             PerfUiWidgetBar::new(PerfUiEntryFrameTimeWorst::default()),
+            PerfUiWidgetBar::new(PerfUiEntryFrameTimeJitter::default()),
             PerfUiWidgetBar::new(PerfUiEntryRenderCpuTime::default()),
             PerfUiWidgetBar::new(PerfUiEntryRenderGpuTime::default()),
             PerfUiWidgetBar::new(PerfUiEntryEntityCount::default()),
             PerfUiWidgetBar::new(PerfUiEntryCpuUsage::default()),
             PerfUiWidgetBar::new(PerfUiEntryMemUsage::default()),
             PerfUiEntryFrameCount::default(),
+            PerfUiEntryFPSPctLow::default(),
+            PerfUiEntryHitches::default(),
+            // the 5 most expensive render passes, one row each
+            PerfUiWidgetTable::new(PerfUiEntryRenderPasses {
+                top_n: Some(5),
+                ..default()
+            }),
         ),
         (
             PerfUiEntryFixedTimeStep::default(),
             PerfUiWidgetBar::new(PerfUiEntryFixedOverstep::default()),
             PerfUiEntryRunningTime::default(),
             PerfUiEntryClock::default(),
+            // which phase of the frame is expensive?
+            PerfUiWidgetStackedBar::new(PerfUiEntryScheduleBreakdown::default()),
+            // any diagnostic can be displayed, by its path
+            PerfUiEntryDiagnostic {
+                label: "Frame Time (seconds)".into(),
+                unit: "s".into(),
+                // the diagnostic is measured in milliseconds
+                scale: 0.001,
+                digits: 1,
+                precision: 4,
+                ..PerfUiEntryDiagnostic::new(bevy::diagnostic::FrameTimeDiagnosticsPlugin::FRAME_TIME)
+            },
         ),
         (
             PerfUiEntryCursorPosition::default(),
@@ -53,5 +77,36 @@ fn setup(mut commands: Commands) {
             PerfUiEntryWindowMode::default(),
             PerfUiEntryWindowPresentMode::default(),
         ),
+        (
+            // if these keep climbing, something is creating
+            // new combinations of components all the time
+            PerfUiWidgetBar::new(PerfUiEntryArchetypeCount::default()),
+            PerfUiEntryTableCount::default(),
+            PerfUiEntryComponentCount::default(),
+            PerfUiEntryResourceCount::default(),
+            PerfUiEntryComponentInstanceCount::default(),
+        ),
+        // To have more than one entry of the same type,
+        // spawn the extra ones as child entities.
+        children![
+            PerfUiEntryFPSPctLow {
+                label: "FPS (1% low)".into(),
+                filter_fraction: 0.01,
+                ..default()
+            },
+            PerfUiEntryFrameTimePercentile {
+                percentile: 50.0,
+                ..default()
+            },
+            PerfUiEntryFrameTimePercentile {
+                percentile: 99.0,
+                ..default()
+            },
+            // the same render passes, as segments of one bar
+            PerfUiWidgetStackedBar::new(PerfUiEntryRenderPasses {
+                top_n: Some(5),
+                ..default()
+            }),
+        ],
     ));
 }
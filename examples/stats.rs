@@ -0,0 +1,86 @@
+//! This example shows how to display statistics of entries' values over time,
+//! such as the worst value in the last few seconds, or the peak this session.
+//!
+//! Press R to reset the session statistics.
+
+use bevy::prelude::*;
+use iyes_perf_ui::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+
+        // we want Bevy to measure these values for us:
+        .add_plugins(bevy::diagnostic::FrameTimeDiagnosticsPlugin::default())
+        .add_plugins(bevy::diagnostic::SystemInformationDiagnosticsPlugin)
+        .add_plugins(bevy::render::diagnostic::RenderDiagnosticsPlugin)
+
+        .add_plugins(PerfUiPlugin)
+        .add_systems(Startup, setup)
+        .add_systems(Update, reset_stats)
+
+        .run();
+}
+
+fn setup(mut commands: Commands) {
+    // spawn a camera to be able to see anything
+    commands.spawn(Camera2d);
+
+    commands.spawn((
+        PerfUiRoot {
+            // stats are easier to read if they do not change every frame
+            update_interval: PerfUiUpdateInterval::Seconds(0.25),
+            ..default()
+        },
+        // the current values, for comparison
+        PerfUiEntryFPS::default(),
+        PerfUiEntryRenderGpuTime::default(),
+        PerfUiEntryMemUsage::default(),
+        // the lowest FPS in the last 5 seconds
+        PerfUiEntryStats::new(PerfUiEntryFPS {
+            // use the raw values, to see individual slow frames
+            smoothed: false,
+            ..default()
+        })
+            .with_stat(PerfUiStat::Min)
+            .with_window(PerfUiStatsWindow::Seconds(5.0)),
+        // the worst Render GPU Time in the last 5 seconds
+        PerfUiEntryStats::new(PerfUiEntryRenderGpuTime::default())
+            .with_stat(PerfUiStat::Max)
+            .with_window(PerfUiStatsWindow::Seconds(5.0)),
+        // the peak RAM usage this session
+        PerfUiEntryStats::new(PerfUiEntryMemUsage::default())
+            .with_stat(PerfUiStat::Max)
+            .with_window(PerfUiStatsWindow::Session),
+        // To have more than one stats entry for the same entry type,
+        // spawn the extra ones as child entities.
+        children![
+            PerfUiEntryStats::new(PerfUiEntryFrameTime::default())
+                .with_stat(PerfUiStat::Percentile(50.0))
+                .with_window(PerfUiStatsWindow::Seconds(10.0)),
+            PerfUiEntryStats::new(PerfUiEntryFrameTime::default())
+                .with_stat(PerfUiStat::Percentile(99.0))
+                .with_window(PerfUiStatsWindow::Seconds(10.0)),
+            PerfUiEntryStats::new(PerfUiEntryFrameTime::default())
+                .with_stat(PerfUiStat::Mean)
+                .with_window(PerfUiStatsWindow::Session)
+                .with_label("Frame Time (session avg)"),
+        ],
+    ));
+}
+
+fn reset_stats(
+    kbd: Res<ButtonInput<KeyCode>>,
+    mut q_mem: Query<&mut PerfUiEntryStats<PerfUiEntryMemUsage>>,
+    mut q_frametime: Query<&mut PerfUiEntryStats<PerfUiEntryFrameTime>>,
+) {
+    if !kbd.just_pressed(KeyCode::KeyR) {
+        return;
+    }
+    for mut stats in &mut q_mem {
+        stats.reset();
+    }
+    for mut stats in &mut q_frametime {
+        stats.reset();
+    }
+}
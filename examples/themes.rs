@@ -0,0 +1,84 @@
+//! This example shows how to style all your Perf UIs using a theme.
+//!
+//! Press T to switch between the preset themes.
+
+use bevy::prelude::*;
+use iyes_perf_ui::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+
+        // we want Bevy to measure these values for us:
+        .add_plugins(bevy::diagnostic::FrameTimeDiagnosticsPlugin::default())
+        .add_plugins(bevy::diagnostic::EntityCountDiagnosticsPlugin)
+        .add_plugins(bevy::diagnostic::SystemInformationDiagnosticsPlugin)
+
+        .add_plugins(PerfUiPlugin)
+        // the theme applies to all Perf UIs
+        .insert_resource(PerfUiTheme::dark())
+        .add_systems(Startup, setup)
+        .add_systems(Update, switch_theme)
+
+        .run();
+}
+
+fn setup(mut commands: Commands) {
+    // spawn a camera to be able to see anything
+    commands.spawn(Camera2d);
+
+    // This Perf UI uses defaults for everything, so it follows the theme.
+    commands.spawn((
+        PerfUiRoot {
+            position: PerfUiPosition::TopLeft,
+            ..default()
+        },
+        PerfUiEntryFPS::default(),
+        PerfUiEntryFPSWorst::default(),
+        PerfUiEntryFrameTime::default(),
+        PerfUiEntryFrameTimeWorst::default(),
+        PerfUiEntryEntityCount::default(),
+    ));
+
+    // This Perf UI sets its label color explicitly, and marks it as
+    // overridden, so it will be kept. Everything else follows the theme.
+    commands.spawn((
+        PerfUiRoot {
+            position: PerfUiPosition::TopRight,
+            label_color: Color::srgb(0.5, 0.75, 1.0),
+            ..default()
+        },
+        PerfUiThemeOverrides {
+            label_color: true,
+            ..default()
+        },
+        PerfUiEntryCpuUsage::default(),
+        PerfUiEntryMemUsage::default(),
+        PerfUiEntryFPS {
+            // a custom gradient, not affected by the theme
+            color_gradient: ColorGradient::new()
+                .with_stop(30.0, Color::srgb(0.5, 0.0, 1.0))
+                .with_stop(60.0, Color::srgb(1.0, 0.5, 1.0)),
+            ..default()
+        },
+    ));
+}
+
+fn switch_theme(
+    mut theme: ResMut<PerfUiTheme>,
+    mut current: Local<usize>,
+    kbd: Res<ButtonInput<KeyCode>>,
+) {
+    if kbd.just_pressed(KeyCode::KeyT) {
+        let presets = [
+            ("dark", PerfUiTheme::dark()),
+            ("light", PerfUiTheme::light()),
+            ("high contrast", PerfUiTheme::high_contrast()),
+            ("colorblind-safe", PerfUiTheme::colorblind_safe()),
+        ];
+        *current = (*current + 1) % presets.len();
+        let (name, preset) = presets[*current].clone();
+        info!("Switching to the {} theme.", name);
+        *theme = preset;
+    }
+}
@@ -0,0 +1,76 @@
+//! This example shows how to react when the values of entries cross
+//! their highlight thresholds.
+//!
+//! Try resizing the window or doing something else that causes
+//! your framerate to drop.
+
+use bevy::prelude::*;
+use iyes_perf_ui::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+
+        // we want Bevy to measure these values for us:
+        .add_plugins(bevy::diagnostic::FrameTimeDiagnosticsPlugin::default())
+
+        .add_plugins(PerfUiPlugin)
+        .init_resource::<QualityLevel>()
+        .add_systems(Startup, setup)
+        .add_systems(Update, adjust_quality)
+
+        .run();
+}
+
+/// Pretend that our game has some graphics quality settings.
+#[derive(Resource)]
+struct QualityLevel(u32);
+
+impl Default for QualityLevel {
+    fn default() -> Self {
+        QualityLevel(3)
+    }
+}
+
+fn setup(mut commands: Commands) {
+    // spawn a camera to be able to see anything
+    commands.spawn(Camera2d);
+
+    commands.spawn((
+        // enable the events for this Perf UI
+        PerfUiThresholdEvents,
+        PerfUiEntryFPS {
+            threshold_highlight: Some(55.0),
+            ..default()
+        },
+        PerfUiEntryFrameTimeWorst {
+            threshold_highlight: Some(33.3),
+            ..default()
+        },
+    ))
+    // use an observer to log warnings
+    .observe(|trigger: Trigger<PerfUiThresholdCrossed>| {
+        let ev = trigger.event();
+        let value = ev.value.as_ref()
+            .map(|v| v.formatted.as_str())
+            .unwrap_or("N/A");
+        if ev.highlighted {
+            warn!("{} is over budget: {}", ev.label, value);
+        } else {
+            info!("{} is back within budget after {:.2?}: {}", ev.label, ev.duration, value);
+        }
+    });
+}
+
+/// Lower the quality when the framerate drops.
+fn adjust_quality(
+    mut evr_crossed: EventReader<PerfUiThresholdCrossed>,
+    mut quality: ResMut<QualityLevel>,
+) {
+    for ev in evr_crossed.read() {
+        if ev.highlighted && ev.label == "FPS" && quality.0 > 0 {
+            quality.0 -= 1;
+            info!("Lowering graphics quality to level {}.", quality.0);
+        }
+    }
+}
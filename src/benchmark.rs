@@ -0,0 +1,324 @@
+//! Benchmark mode: measure performance and check it against a budget.
+//!
+//! Add the [`PerfUiBenchmark`] component to your Perf UI entity, to run a
+//! benchmark using its entries. After an optional warm-up period, the values
+//! of all the entries are measured for the configured length of time. At the
+//! end, a summary report is produced and (optionally) the app exits.
+//!
+//! The budget is defined by the entries' own highlight thresholds (such as
+//! `threshold_highlight`): every measured value past the threshold counts as
+//! a violation (the `PerfUiHighlightDebounce` settings are not applied). If
+//! the fraction of violations for any entry exceeds `max_violation_fraction`,
+//! the benchmark fails and the app exits with an error code. This makes it
+//! easy to gate CI runs on performance. Frames where an entry has no value
+//! are not counted, but reported separately.
+//!
+//! When the warm-up is over, all entries discard the data they have collected
+//! so far (see `PerfUiEntry::clear_samples`), so that entries such as
+//! `PerfUiEntryFPSWorst` do not report values from the warm-up.
+//!
+//! ```rust
+//! commands.spawn((
+//!     PerfUiBenchmark {
+//!         warmup: PerfUiBenchmarkLength::Seconds(2.0),
+//!         length: PerfUiBenchmarkLength::Frames(1000),
+//!         ..default()
+//!     },
+//!     PerfUiEntryFPSAverage::default(),
+//!     PerfUiEntryFPSPctLow::default(),
+//!     PerfUiEntryFrameTimeWorst {
+//!         threshold_highlight: Some(33.3),
+//!         ..default()
+//!     },
+//! ));
+//! ```
+
+use std::num::NonZeroU8;
+use std::path::PathBuf;
+
+use bevy::prelude::*;
+
+use crate::collect::{unique_labels, PerfUiCollector};
+use crate::entry::PerfUiClearSamples;
+use crate::ui::root::PerfUiRoot;
+
+/// How long to run (a phase of) a benchmark for.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PerfUiBenchmarkLength {
+    /// A number of frames.
+    Frames(u32),
+    /// An amount of (real) time, in seconds.
+    Seconds(f32),
+}
+
+/// Component to run a benchmark using the entries of a Perf UI.
+///
+/// Add this to the entity with the `PerfUiRoot` component.
+///
+/// See the [module-level documentation](self) for more info.
+#[derive(Component, Debug, Clone)]
+#[require(PerfUiRoot, PerfUiCollector, PerfUiBenchmarkState)]
+pub struct PerfUiBenchmark {
+    /// How long to wait before starting to measure.
+    ///
+    /// Useful to skip any loading or startup hitches.
+    ///
+    /// Default: 1 second
+    pub warmup: PerfUiBenchmarkLength,
+    /// How long to measure for.
+    ///
+    /// Default: 10 seconds
+    pub length: PerfUiBenchmarkLength,
+    /// The maximum fraction (0.0-1.0) of measured values of an entry
+    /// that are allowed to violate the budget (be past the highlight threshold).
+    ///
+    /// Default: `0.0` (no violations allowed)
+    pub max_violation_fraction: f32,
+    /// Also write the report to this file.
+    ///
+    /// Regardless, the report is always logged using `bevy_log`.
+    ///
+    /// Default: `None`
+    pub report_path: Option<PathBuf>,
+    /// Exit the app when the benchmark is finished?
+    ///
+    /// If the benchmark failed, the app exits with an error code.
+    ///
+    /// Default: `true`
+    pub exit_on_finish: bool,
+}
+
+impl Default for PerfUiBenchmark {
+    fn default() -> Self {
+        PerfUiBenchmark {
+            warmup: PerfUiBenchmarkLength::Seconds(1.0),
+            length: PerfUiBenchmarkLength::Seconds(10.0),
+            max_violation_fraction: 0.0,
+            report_path: None,
+            exit_on_finish: true,
+        }
+    }
+}
+
+/// Event sent when a benchmark is finished.
+#[derive(Event, Debug, Clone)]
+pub struct PerfUiBenchmarkFinished {
+    /// The Perf UI entity with the benchmark.
+    pub entity: Entity,
+    /// Did all the entries stay within budget?
+    pub passed: bool,
+    /// The text of the summary report.
+    pub report: String,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+enum BenchmarkPhase {
+    #[default]
+    Warmup,
+    Measure,
+    Finished,
+}
+
+#[doc(hidden)]
+#[derive(Component, Default)]
+pub struct PerfUiBenchmarkState {
+    phase: BenchmarkPhase,
+    phase_start_frame: u32,
+    phase_start_time: f32,
+    frames: u32,
+    stats: Vec<BenchmarkStats>,
+}
+
+#[derive(Default)]
+struct BenchmarkStats {
+    label: String,
+    samples: u32,
+    missing: u32,
+    violations: u32,
+    numeric_count: u32,
+    sum: f64,
+    min: f64,
+    max: f64,
+}
+
+impl BenchmarkStats {
+    fn violation_fraction(&self) -> f32 {
+        if self.samples == 0 {
+            0.0
+        } else {
+            self.violations as f32 / self.samples as f32
+        }
+    }
+}
+
+impl PerfUiBenchmarkLength {
+    fn is_reached(self, frames: u32, secs: f32) -> bool {
+        match self {
+            PerfUiBenchmarkLength::Frames(n) => frames >= n,
+            PerfUiBenchmarkLength::Seconds(s) => secs >= s,
+        }
+    }
+}
+
+pub(crate) fn tick_perf_ui_benchmark(
+    time: Res<Time<Real>>,
+    mut q_benchmark: Query<(Entity, &PerfUiBenchmark, &mut PerfUiBenchmarkState, &mut PerfUiCollector)>,
+    mut evw_clear: EventWriter<PerfUiClearSamples>,
+) {
+    let now = time.elapsed_secs();
+    for (e_root, benchmark, mut state, mut collector) in &mut q_benchmark {
+        if state.frames == 0 {
+            state.phase_start_time = now;
+        }
+        state.frames += 1;
+        match state.phase {
+            BenchmarkPhase::Warmup => {
+                let frames = state.frames - state.phase_start_frame;
+                if benchmark.warmup.is_reached(frames, now - state.phase_start_time) {
+                    // The entries discard their warm-up data at the start of
+                    // the next frame, so that is when we start measuring.
+                    evw_clear.write(PerfUiClearSamples { entity: e_root });
+                    state.phase = BenchmarkPhase::Measure;
+                    state.phase_start_frame = state.frames + 1;
+                }
+            }
+            BenchmarkPhase::Measure => {
+                if state.frames == state.phase_start_frame {
+                    state.phase_start_time = now;
+                }
+                collector.due = true;
+            }
+            BenchmarkPhase::Finished => {}
+        }
+    }
+}
+
+pub(crate) fn output_perf_ui_benchmark(
+    time: Res<Time<Real>>,
+    mut q_benchmark: Query<(Entity, &PerfUiBenchmark, &mut PerfUiBenchmarkState, &PerfUiCollector)>,
+    mut evw_finished: EventWriter<PerfUiBenchmarkFinished>,
+    mut evw_exit: EventWriter<AppExit>,
+) {
+    let now = time.elapsed_secs();
+    for (e_root, benchmark, mut state, collector) in &mut q_benchmark {
+        if state.phase != BenchmarkPhase::Measure || state.frames < state.phase_start_frame {
+            continue;
+        }
+
+        let labels = unique_labels(&collector.rows, &[]);
+        for (label, row) in labels.into_iter().zip(&collector.rows) {
+            let i = match state.stats.iter().position(|s| s.label == label) {
+                Some(i) => i,
+                None => {
+                    state.stats.push(BenchmarkStats {
+                        label,
+                        min: f64::INFINITY,
+                        max: f64::NEG_INFINITY,
+                        ..default()
+                    });
+                    state.stats.len() - 1
+                }
+            };
+            let stats = &mut state.stats[i];
+            let Some(value) = &row.value else {
+                stats.missing += 1;
+                continue;
+            };
+            stats.samples += 1;
+            if value.past_threshold {
+                stats.violations += 1;
+            }
+            if let Some(x) = value.numeric.filter(|x| x.is_finite()) {
+                stats.numeric_count += 1;
+                stats.sum += x;
+                stats.min = stats.min.min(x);
+                stats.max = stats.max.max(x);
+            }
+        }
+
+        let frames = state.frames - state.phase_start_frame + 1;
+        let secs = now - state.phase_start_time;
+        if !benchmark.length.is_reached(frames, secs) {
+            continue;
+        }
+
+        state.phase = BenchmarkPhase::Finished;
+        let passed = state.stats.iter()
+            .all(|s| s.violation_fraction() <= benchmark.max_violation_fraction);
+        let report = format_report(&state.stats, benchmark, frames, secs, passed);
+        if passed {
+            info!("{}", report);
+        } else {
+            error!("{}", report);
+        }
+        if let Some(path) = &benchmark.report_path {
+            if let Err(e) = std::fs::write(path, &report) {
+                error!("Perf UI Benchmark: cannot write report to {:?}: {}", path, e);
+            }
+        }
+        evw_finished.write(PerfUiBenchmarkFinished {
+            entity: e_root,
+            passed,
+            report,
+        });
+        if benchmark.exit_on_finish {
+            evw_exit.write(if passed {
+                AppExit::Success
+            } else {
+                AppExit::Error(NonZeroU8::MIN)
+            });
+        }
+    }
+}
+
+fn format_report(
+    stats: &[BenchmarkStats],
+    benchmark: &PerfUiBenchmark,
+    frames: u32,
+    secs: f32,
+    passed: bool,
+) -> String {
+    use std::fmt::Write;
+
+    let header = ["Entry", "Min", "Mean", "Max", "Missing", "Over Budget", "Result"];
+    let mut table: Vec<[String; 7]> = vec![header.map(String::from)];
+    for s in stats {
+        let ok = s.violation_fraction() <= benchmark.max_violation_fraction;
+        let (min, mean, max) = if s.numeric_count > 0 {
+            (
+                format!("{:.3}", s.min),
+                format!("{:.3}", s.sum / s.numeric_count as f64),
+                format!("{:.3}", s.max),
+            )
+        } else {
+            ("-".into(), "-".into(), "-".into())
+        };
+        table.push([
+            s.label.clone(),
+            min, mean, max,
+            format!("{}", s.missing),
+            format!("{:.1}%", s.violation_fraction() * 100.0),
+            if ok { "PASS" } else { "FAIL" }.into(),
+        ]);
+    }
+    let mut widths = [0; 7];
+    for row in &table {
+        for (w, cell) in widths.iter_mut().zip(row) {
+            *w = (*w).max(cell.chars().count());
+        }
+    }
+
+    let mut text = format!(
+        "Perf UI Benchmark: {} ({} frames, {:.2} s, max {:.1}% over budget allowed)",
+        if passed { "PASSED" } else { "FAILED" },
+        frames, secs,
+        benchmark.max_violation_fraction * 100.0,
+    );
+    for row in &table {
+        let _ = write!(text, "\n  {:<w$}", row[0], w = widths[0]);
+        for (cell, w) in row[1..].iter().zip(&widths[1..]) {
+            let _ = write!(text, "  {:>w$}", cell, w = *w);
+        }
+    }
+    text
+}
@@ -0,0 +1,217 @@
+//! Collecting the values of Perf UI entries, for outputs other than Bevy UI.
+//!
+//! Outputs such as [`PerfUiTextOutput`](crate::text::PerfUiTextOutput) and
+//! [`PerfUiRecorder`](crate::recorder::PerfUiRecorder) need the current values
+//! of all the entries of a Perf UI. Every frame when any of them wants new
+//! values, the values of all entries on that Perf UI are collected in one place,
+//! by the same systems that update the Perf UI (so every value is only
+//! computed once per frame, and is highlighted the same way everywhere).
+//!
+//! If you want to access the values from your own code, use the
+//! [`PerfUiSnapshot`] resource.
+
+use bevy::prelude::*;
+
+use crate::entry::PerfUiEntry;
+use crate::ui::root::PerfUiRoot;
+
+/// Internal ordering of the systems that collect values.
+///
+/// All of them are in `PerfUiSet::Update`.
+#[derive(SystemSet, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum PerfUiCollectSet {
+    /// Outputs decide if they want new values this frame.
+    Prepare,
+    /// The Perf UIs are updated, and the values of all entries are collected.
+    Collect,
+    /// Outputs use the collected values.
+    Output,
+}
+
+#[doc(hidden)]
+#[derive(Component, Default)]
+pub struct PerfUiCollector {
+    /// Should values be collected this frame?
+    pub(crate) due: bool,
+    /// The collected values, sorted by sort key.
+    pub(crate) rows: Vec<PerfUiSnapshotEntry>,
+}
+
+impl PerfUiCollector {
+    /// Add the value of an entry, as obtained when updating the Perf UI.
+    pub(crate) fn push<E: PerfUiEntry>(
+        &mut self,
+        entry: &E,
+        sort_key: i32,
+        value: Option<&E::Value>,
+        highlight: bool,
+        root: &PerfUiRoot,
+    ) {
+        let value = value
+            .map(|value| PerfUiSnapshotValue {
+                formatted: entry.format_value(value),
+                raw: format!("{:?}", value),
+                numeric: entry.value_as_f64(value),
+                highlight,
+                past_threshold: entry.value_highlight(value),
+                color: entry.value_color(value)
+                    .unwrap_or(root.default_value_color),
+            });
+        let i = self.rows.partition_point(|row| row.sort_key <= sort_key);
+        self.rows.insert(i, PerfUiSnapshotEntry {
+            sort_key,
+            label: entry.label().to_owned(),
+            value,
+        });
+    }
+}
+
+/// Resource with the current values of all entries of all Perf UIs.
+///
+/// This allows you to access the values programmatically, for example
+/// for bug reports, telemetry, or tests.
+///
+/// It is not present by default, because collecting all the values has
+/// some overhead. To enable it, add the resource to your app:
+///
+/// ```rust
+/// app.init_resource::<PerfUiSnapshot>();
+/// ```
+///
+/// It will then be updated every frame, in `PerfUiSet::Update`.
+#[derive(Resource, Debug, Default, Clone)]
+pub struct PerfUiSnapshot {
+    /// The Perf UIs, in no particular order.
+    pub roots: Vec<PerfUiSnapshotRoot>,
+}
+
+/// The current values of all entries of a Perf UI.
+#[derive(Debug, Clone)]
+pub struct PerfUiSnapshotRoot {
+    /// The Perf UI entity (with the `PerfUiRoot` component).
+    pub entity: Entity,
+    /// The entries, sorted in the order they are displayed.
+    pub entries: Vec<PerfUiSnapshotEntry>,
+}
+
+impl PerfUiSnapshot {
+    /// Get the values of a specific Perf UI.
+    pub fn get(&self, e_root: Entity) -> Option<&PerfUiSnapshotRoot> {
+        self.roots.iter().find(|root| root.entity == e_root)
+    }
+
+    /// Iterate over the entries of all Perf UIs.
+    pub fn entries(&self) -> impl Iterator<Item = &PerfUiSnapshotEntry> {
+        self.roots.iter().flat_map(|root| root.entries.iter())
+    }
+
+    /// Find the first entry with the given label, in any Perf UI.
+    pub fn find(&self, label: &str) -> Option<&PerfUiSnapshotEntry> {
+        self.entries().find(|entry| entry.label == label)
+    }
+}
+
+impl PerfUiSnapshotRoot {
+    /// Find the first entry with the given label.
+    pub fn find(&self, label: &str) -> Option<&PerfUiSnapshotEntry> {
+        self.entries.iter().find(|entry| entry.label == label)
+    }
+}
+
+/// The current value of a Perf UI entry.
+#[derive(Debug, Clone)]
+pub struct PerfUiSnapshotEntry {
+    /// The sort key of the entry (controls its position in the Perf UI).
+    pub sort_key: i32,
+    /// The label of the entry.
+    pub label: String,
+    /// The value of the entry, or `None` if it is unavailable.
+    pub value: Option<PerfUiSnapshotValue>,
+}
+
+/// A value of a Perf UI entry, in all the different forms it can be presented.
+#[derive(Debug, Clone)]
+pub struct PerfUiSnapshotValue {
+    /// The value formatted for display (as per `PerfUiEntry::format_value`).
+    pub formatted: String,
+    /// The `Debug` representation of the raw value.
+    pub raw: String,
+    /// The raw value as a number, if the entry provides one (as per `PerfUiEntry::value_as_f64`).
+    pub numeric: Option<f64>,
+    /// Should the value be highlighted?
+    ///
+    /// This takes into account the `PerfUiHighlightDebounce` settings.
+    pub highlight: bool,
+    /// Is the value past the entry's highlight threshold?
+    ///
+    /// Unlike `highlight`, this is only based on the current value
+    /// (as per `PerfUiEntry::value_highlight`), without any debouncing.
+    pub past_threshold: bool,
+    /// The color of the value (the entry's custom color, or the Perf UI's default).
+    pub color: Color,
+}
+
+impl PerfUiSnapshotEntry {
+    /// The formatted value, or the given error text if there is no value.
+    pub fn formatted_or<'a>(&'a self, text_err: &'a str) -> &'a str {
+        self.value.as_ref()
+            .map(|v| v.formatted.as_str())
+            .unwrap_or(text_err)
+    }
+}
+
+/// Get unique names for the rows, based on the entries' labels.
+///
+/// If multiple entries have the same label, a number is appended.
+/// The names will also not collide with any of the `reserved` names.
+pub(crate) fn unique_labels(rows: &[PerfUiSnapshotEntry], reserved: &[&str]) -> Vec<String> {
+    let mut names: Vec<String> = Vec::with_capacity(rows.len());
+    for row in rows {
+        let mut name = row.label.clone();
+        let mut n = 1;
+        while reserved.contains(&name.as_str()) || names.contains(&name) {
+            n += 1;
+            name = format!("{} ({})", row.label, n);
+        }
+        names.push(name);
+    }
+    names
+}
+
+pub(crate) fn reset_perf_ui_collectors(
+    mut q_collector: Query<&mut PerfUiCollector>,
+) {
+    for mut collector in &mut q_collector {
+        collector.due = false;
+        collector.rows.clear();
+    }
+}
+
+pub(crate) fn prepare_perf_ui_snapshot(
+    mut commands: Commands,
+    q_root: Query<(Entity, Has<PerfUiCollector>), With<PerfUiRoot>>,
+    mut q_collector: Query<&mut PerfUiCollector, With<PerfUiRoot>>,
+) {
+    for (e_root, has_collector) in &q_root {
+        if !has_collector {
+            // we will start collecting next frame
+            commands.entity(e_root).insert(PerfUiCollector::default());
+        }
+    }
+    for mut collector in &mut q_collector {
+        collector.due = true;
+    }
+}
+
+pub(crate) fn update_perf_ui_snapshot(
+    mut snapshot: ResMut<PerfUiSnapshot>,
+    q_collector: Query<(Entity, &PerfUiCollector), With<PerfUiRoot>>,
+) {
+    snapshot.roots.clear();
+    for (e_root, collector) in &q_collector {
+        snapshot.roots.push(PerfUiSnapshotRoot {
+            entity: e_root,
+            entries: collector.rows.clone(),
+        });
+    }
+}
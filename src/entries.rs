@@ -14,8 +14,14 @@ pub mod prelude {
         PerfUiEntryFPSAverage,
         PerfUiEntryFrameTime,
         PerfUiEntryFrameTimeWorst,
+        PerfUiEntryFrameTimePercentile,
+        PerfUiEntryFrameTimeJitter,
+        PerfUiJitterMethod,
+        PerfUiEntryHitches,
+        PerfUiHitchThreshold,
         PerfUiEntryFrameCount,
         PerfUiEntryEntityCount,
+        PerfUiEntryDiagnostic,
     };
 
     #[cfg(feature = "sysinfo")]
@@ -29,6 +35,24 @@ pub mod prelude {
     pub use super::render::{
         PerfUiEntryRenderCpuTime,
         PerfUiEntryRenderGpuTime,
+        PerfUiEntryRenderPasses,
+        PerfUiRenderTimeKind,
+    };
+
+    pub use super::schedule::{
+        PerfUiScheduleTimingPlugin,
+        PerfUiSchedule,
+        PerfUiEntryScheduleTime,
+        PerfUiEntryScheduleBreakdown,
+    };
+
+    pub use super::world::{
+        PerfUiWorldStatsPlugin,
+        PerfUiEntryArchetypeCount,
+        PerfUiEntryTableCount,
+        PerfUiEntryComponentCount,
+        PerfUiEntryResourceCount,
+        PerfUiEntryComponentInstanceCount,
     };
 
     pub use super::time::{
@@ -50,7 +74,9 @@ pub mod prelude {
 
 pub mod diagnostics;
 pub mod render;
+pub mod schedule;
 pub mod time;
+pub mod world;
 
 #[cfg(feature = "window")]
 pub mod window;
@@ -62,8 +88,12 @@ pub(crate) fn predefined_entries_plugin(app: &mut App) {
     app.add_perf_ui_simple_entry::<PerfUiEntryFPSAverage>();
     app.add_perf_ui_simple_entry::<PerfUiEntryFrameTime>();
     app.add_perf_ui_simple_entry::<PerfUiEntryFrameTimeWorst>();
+    app.add_perf_ui_simple_entry::<PerfUiEntryFrameTimePercentile>();
+    app.add_perf_ui_simple_entry::<PerfUiEntryFrameTimeJitter>();
+    app.add_perf_ui_simple_entry::<PerfUiEntryHitches>();
     app.add_perf_ui_simple_entry::<PerfUiEntryFrameCount>();
     app.add_perf_ui_simple_entry::<PerfUiEntryEntityCount>();
+    app.add_perf_ui_simple_entry::<PerfUiEntryDiagnostic>();
 
     #[cfg(feature = "sysinfo")]
     app.add_perf_ui_simple_entry::<PerfUiEntryCpuUsage>();
@@ -76,6 +106,16 @@ pub(crate) fn predefined_entries_plugin(app: &mut App) {
 
     app.add_perf_ui_simple_entry::<PerfUiEntryRenderCpuTime>();
     app.add_perf_ui_simple_entry::<PerfUiEntryRenderGpuTime>();
+    app.add_perf_ui_simple_entry::<PerfUiEntryRenderPasses>();
+
+    app.add_perf_ui_simple_entry::<PerfUiEntryScheduleTime>();
+    app.add_perf_ui_simple_entry::<PerfUiEntryScheduleBreakdown>();
+
+    app.add_perf_ui_simple_entry::<PerfUiEntryArchetypeCount>();
+    app.add_perf_ui_simple_entry::<PerfUiEntryTableCount>();
+    app.add_perf_ui_simple_entry::<PerfUiEntryComponentCount>();
+    app.add_perf_ui_simple_entry::<PerfUiEntryResourceCount>();
+    app.add_perf_ui_simple_entry::<PerfUiEntryComponentInstanceCount>();
 
     app.add_perf_ui_simple_entry::<PerfUiEntryClock>();
     app.add_perf_ui_simple_entry::<PerfUiEntryRunningTime>();
@@ -90,6 +130,197 @@ pub(crate) fn predefined_entries_plugin(app: &mut App) {
         app.add_perf_ui_simple_entry::<PerfUiEntryWindowPresentMode>();
         app.add_perf_ui_simple_entry::<PerfUiEntryCursorPosition>();
     }
+
+    app.register_type::<PerfUiEntryFPS>();
+    app.register_type::<PerfUiEntryFPSPctLow>();
+    app.register_type::<PerfUiEntryFPSWorst>();
+    app.register_type::<PerfUiEntryFPSAverage>();
+    app.register_type::<PerfUiEntryFrameTime>();
+    app.register_type::<PerfUiEntryFrameTimeWorst>();
+    app.register_type::<PerfUiEntryFrameTimePercentile>();
+    app.register_type::<PerfUiEntryFrameTimeJitter>();
+    app.register_type::<PerfUiJitterMethod>();
+    app.register_type::<PerfUiEntryHitches>();
+    app.register_type::<PerfUiHitchThreshold>();
+    app.register_type::<PerfUiEntryFrameCount>();
+    app.register_type::<PerfUiEntryEntityCount>();
+    app.register_type::<PerfUiEntryDiagnostic>();
+    #[cfg(feature = "sysinfo")]
+    {
+        app.register_type::<PerfUiEntryCpuUsage>();
+        app.register_type::<PerfUiEntryMemUsage>();
+        app.register_type::<PerfUiEntrySystemCpuUsage>();
+        app.register_type::<PerfUiEntrySystemMemUsage>();
+    }
+    app.register_type::<PerfUiEntryRenderCpuTime>();
+    app.register_type::<PerfUiEntryRenderGpuTime>();
+    app.register_type::<PerfUiEntryRenderPasses>();
+    app.register_type::<PerfUiRenderTimeKind>();
+    app.register_type::<PerfUiEntryScheduleTime>();
+    app.register_type::<PerfUiEntryScheduleBreakdown>();
+    app.register_type::<PerfUiSchedule>();
+    app.register_type::<PerfUiEntryArchetypeCount>();
+    app.register_type::<PerfUiEntryTableCount>();
+    app.register_type::<PerfUiEntryComponentCount>();
+    app.register_type::<PerfUiEntryResourceCount>();
+    app.register_type::<PerfUiEntryComponentInstanceCount>();
+    app.register_type::<PerfUiEntryClock>();
+    app.register_type::<PerfUiEntryRunningTime>();
+    app.register_type::<PerfUiEntryFixedTimeStep>();
+    app.register_type::<PerfUiEntryFixedOverstep>();
+    #[cfg(feature = "window")]
+    {
+        app.register_type::<PerfUiEntryWindowResolution>();
+        app.register_type::<PerfUiEntryWindowScaleFactor>();
+        app.register_type::<PerfUiEntryWindowMode>();
+        app.register_type::<PerfUiEntryWindowPresentMode>();
+        app.register_type::<PerfUiEntryCursorPosition>();
+    }
+
+    #[cfg(feature = "asset")]
+    {
+        app.add_perf_ui_layout_entry::<PerfUiEntryFPS>("FPS");
+        app.add_perf_ui_layout_entry::<PerfUiEntryFPSPctLow>("FPSPctLow");
+        app.add_perf_ui_layout_entry::<PerfUiEntryFPSWorst>("FPSWorst");
+        app.add_perf_ui_layout_entry::<PerfUiEntryFPSAverage>("FPSAverage");
+        app.add_perf_ui_layout_entry::<PerfUiEntryFrameTime>("FrameTime");
+        app.add_perf_ui_layout_entry::<PerfUiEntryFrameTimeWorst>("FrameTimeWorst");
+        app.add_perf_ui_layout_entry::<PerfUiEntryFrameTimePercentile>("FrameTimePercentile");
+        app.add_perf_ui_layout_entry::<PerfUiEntryFrameTimeJitter>("FrameTimeJitter");
+        app.add_perf_ui_layout_entry::<PerfUiEntryHitches>("Hitches");
+        app.add_perf_ui_layout_entry::<PerfUiEntryFrameCount>("FrameCount");
+        app.add_perf_ui_layout_entry::<PerfUiEntryEntityCount>("EntityCount");
+        app.add_perf_ui_layout_entry::<PerfUiEntryDiagnostic>("Diagnostic");
+        #[cfg(feature = "sysinfo")]
+        {
+            app.add_perf_ui_layout_entry::<PerfUiEntryCpuUsage>("CpuUsage");
+            app.add_perf_ui_layout_entry::<PerfUiEntryMemUsage>("MemUsage");
+            app.add_perf_ui_layout_entry::<PerfUiEntrySystemCpuUsage>("SystemCpuUsage");
+            app.add_perf_ui_layout_entry::<PerfUiEntrySystemMemUsage>("SystemMemUsage");
+        }
+        app.add_perf_ui_layout_entry::<PerfUiEntryRenderCpuTime>("RenderCpuTime");
+        app.add_perf_ui_layout_entry::<PerfUiEntryRenderGpuTime>("RenderGpuTime");
+        app.add_perf_ui_layout_entry::<PerfUiEntryRenderPasses>("RenderPasses");
+        app.add_perf_ui_layout_entry::<PerfUiEntryScheduleTime>("ScheduleTime");
+        app.add_perf_ui_layout_entry::<PerfUiEntryScheduleBreakdown>("ScheduleBreakdown");
+        app.add_perf_ui_layout_entry::<PerfUiEntryArchetypeCount>("ArchetypeCount");
+        app.add_perf_ui_layout_entry::<PerfUiEntryTableCount>("TableCount");
+        app.add_perf_ui_layout_entry::<PerfUiEntryComponentCount>("ComponentCount");
+        app.add_perf_ui_layout_entry::<PerfUiEntryResourceCount>("ResourceCount");
+        app.add_perf_ui_layout_entry::<PerfUiEntryComponentInstanceCount>("ComponentInstanceCount");
+        app.add_perf_ui_layout_entry::<PerfUiEntryClock>("Clock");
+        app.add_perf_ui_layout_entry::<PerfUiEntryRunningTime>("RunningTime");
+        app.add_perf_ui_layout_entry::<PerfUiEntryFixedTimeStep>("FixedTimeStep");
+        app.add_perf_ui_layout_entry::<PerfUiEntryFixedOverstep>("FixedOverstep");
+        #[cfg(feature = "window")]
+        {
+            app.add_perf_ui_layout_entry::<PerfUiEntryWindowResolution>("WindowResolution");
+            app.add_perf_ui_layout_entry::<PerfUiEntryWindowScaleFactor>("WindowScaleFactor");
+            app.add_perf_ui_layout_entry::<PerfUiEntryWindowMode>("WindowMode");
+            app.add_perf_ui_layout_entry::<PerfUiEntryWindowPresentMode>("WindowPresentMode");
+            app.add_perf_ui_layout_entry::<PerfUiEntryCursorPosition>("CursorPosition");
+        }
+    }
+
+    app.add_perf_ui_history::<PerfUiEntryFPS>();
+    app.add_perf_ui_history::<PerfUiEntryFrameTime>();
+    app.add_perf_ui_history::<PerfUiEntryEntityCount>();
+    app.add_perf_ui_history::<PerfUiEntryDiagnostic>();
+    #[cfg(feature = "sysinfo")]
+    app.add_perf_ui_history::<PerfUiEntryCpuUsage>();
+    #[cfg(feature = "sysinfo")]
+    app.add_perf_ui_history::<PerfUiEntryMemUsage>();
+    #[cfg(feature = "sysinfo")]
+    app.add_perf_ui_history::<PerfUiEntrySystemCpuUsage>();
+    #[cfg(feature = "sysinfo")]
+    app.add_perf_ui_history::<PerfUiEntrySystemMemUsage>();
+    app.add_perf_ui_history::<PerfUiEntryRenderCpuTime>();
+    app.add_perf_ui_history::<PerfUiEntryRenderGpuTime>();
+    app.add_perf_ui_history::<PerfUiEntryScheduleTime>();
+    app.add_perf_ui_history::<PerfUiEntryArchetypeCount>();
+    app.add_perf_ui_history::<PerfUiEntryTableCount>();
+    app.add_perf_ui_history::<PerfUiEntryComponentCount>();
+    app.add_perf_ui_history::<PerfUiEntryResourceCount>();
+    app.add_perf_ui_history::<PerfUiEntryComponentInstanceCount>();
+    app.add_perf_ui_history::<PerfUiEntryFixedOverstep>();
+
+    add_stats::<PerfUiEntryFPS>(app);
+    add_stats::<PerfUiEntryFPSPctLow>(app);
+    add_stats::<PerfUiEntryFPSWorst>(app);
+    add_stats::<PerfUiEntryFPSAverage>(app);
+    add_stats::<PerfUiEntryFrameTime>(app);
+    add_stats::<PerfUiEntryFrameTimeWorst>(app);
+    add_stats::<PerfUiEntryFrameTimePercentile>(app);
+    add_stats::<PerfUiEntryFrameTimeJitter>(app);
+    add_stats::<PerfUiEntryHitches>(app);
+    add_stats::<PerfUiEntryEntityCount>(app);
+    add_stats::<PerfUiEntryDiagnostic>(app);
+    #[cfg(feature = "sysinfo")]
+    {
+        add_stats::<PerfUiEntryCpuUsage>(app);
+        add_stats::<PerfUiEntryMemUsage>(app);
+        add_stats::<PerfUiEntrySystemCpuUsage>(app);
+        add_stats::<PerfUiEntrySystemMemUsage>(app);
+    }
+    add_stats::<PerfUiEntryRenderCpuTime>(app);
+    add_stats::<PerfUiEntryRenderGpuTime>(app);
+    add_stats::<PerfUiEntryScheduleTime>(app);
+    add_stats::<PerfUiEntryArchetypeCount>(app);
+    add_stats::<PerfUiEntryTableCount>(app);
+    add_stats::<PerfUiEntryComponentCount>(app);
+    add_stats::<PerfUiEntryResourceCount>(app);
+    add_stats::<PerfUiEntryComponentInstanceCount>(app);
+    add_stats::<PerfUiEntryFixedOverstep>(app);
+
+    #[cfg(feature = "asset")]
+    {
+        // (stats of `PerfUiEntryDiagnostic` are not supported, as it has no default path)
+        add_stats_layout_entry::<PerfUiEntryFPS>(app, "FPS");
+        add_stats_layout_entry::<PerfUiEntryFPSPctLow>(app, "FPSPctLow");
+        add_stats_layout_entry::<PerfUiEntryFPSWorst>(app, "FPSWorst");
+        add_stats_layout_entry::<PerfUiEntryFPSAverage>(app, "FPSAverage");
+        add_stats_layout_entry::<PerfUiEntryFrameTime>(app, "FrameTime");
+        add_stats_layout_entry::<PerfUiEntryFrameTimeWorst>(app, "FrameTimeWorst");
+        add_stats_layout_entry::<PerfUiEntryFrameTimePercentile>(app, "FrameTimePercentile");
+        add_stats_layout_entry::<PerfUiEntryFrameTimeJitter>(app, "FrameTimeJitter");
+        add_stats_layout_entry::<PerfUiEntryHitches>(app, "Hitches");
+        add_stats_layout_entry::<PerfUiEntryEntityCount>(app, "EntityCount");
+        #[cfg(feature = "sysinfo")]
+        {
+            add_stats_layout_entry::<PerfUiEntryCpuUsage>(app, "CpuUsage");
+            add_stats_layout_entry::<PerfUiEntryMemUsage>(app, "MemUsage");
+            add_stats_layout_entry::<PerfUiEntrySystemCpuUsage>(app, "SystemCpuUsage");
+            add_stats_layout_entry::<PerfUiEntrySystemMemUsage>(app, "SystemMemUsage");
+        }
+        add_stats_layout_entry::<PerfUiEntryRenderCpuTime>(app, "RenderCpuTime");
+        add_stats_layout_entry::<PerfUiEntryRenderGpuTime>(app, "RenderGpuTime");
+        add_stats_layout_entry::<PerfUiEntryScheduleTime>(app, "ScheduleTime");
+        add_stats_layout_entry::<PerfUiEntryArchetypeCount>(app, "ArchetypeCount");
+        add_stats_layout_entry::<PerfUiEntryTableCount>(app, "TableCount");
+        add_stats_layout_entry::<PerfUiEntryComponentCount>(app, "ComponentCount");
+        add_stats_layout_entry::<PerfUiEntryResourceCount>(app, "ResourceCount");
+        add_stats_layout_entry::<PerfUiEntryComponentInstanceCount>(app, "ComponentInstanceCount");
+        add_stats_layout_entry::<PerfUiEntryFixedOverstep>(app, "FixedOverstep");
+    }
+}
+
+fn add_stats<E>(app: &mut App)
+where
+    E: crate::entry::PerfUiEntry,
+    E::Value: num_traits::NumCast,
+    E: bevy::reflect::FromReflect + bevy::reflect::Reflectable,
+{
+    app.register_type::<crate::stats::PerfUiEntryStats<E>>();
+    app.add_perf_ui_stats::<E>();
+}
+
+#[cfg(feature = "asset")]
+fn add_stats_layout_entry<E>(app: &mut App, name: &str)
+where
+    E: crate::entry::PerfUiEntry + Clone + Default + serde::de::DeserializeOwned,
+    E::Value: num_traits::NumCast,
+{
+    app.add_perf_ui_layout_entry::<crate::stats::PerfUiEntryStats<E>>(format!("Stats<{}>", name));
 }
 
 /// Bundle for a Perf UI with all entry types provided by `iyes_perf_ui`.
@@ -124,6 +355,9 @@ pub struct PerfUiAllEntries {
     pub fps_worst: PerfUiEntryFPSWorst,
     pub frametime: PerfUiEntryFrameTime,
     pub frametime_worst: PerfUiEntryFrameTimeWorst,
+    pub frametime_pct: PerfUiEntryFrameTimePercentile,
+    pub frametime_jitter: PerfUiEntryFrameTimeJitter,
+    pub hitches: PerfUiEntryHitches,
     pub frame_count: PerfUiEntryFrameCount,
     pub entity_count: PerfUiEntryEntityCount,
     #[cfg(feature = "sysinfo")]
@@ -256,6 +490,28 @@ pub struct PerfUiFixedTimeEntries {
     pub fixed_overstep: PerfUiEntryFixedOverstep,
 }
 
+/// All entries related to the ECS World.
+///
+/// Requires [`PerfUiWorldStatsPlugin`](world::PerfUiWorldStatsPlugin)
+/// (and Bevy's `EntityCountDiagnosticsPlugin`, for the entity count).
+///
+/// ```rust
+/// commands.spawn((
+///     PerfUiWorldEntries::default(),
+///     // ...
+/// ));
+/// ```
+#[allow(missing_docs)]
+#[derive(Bundle, Default)]
+pub struct PerfUiWorldEntries {
+    pub entity_count: PerfUiEntryEntityCount,
+    pub component_instance_count: PerfUiEntryComponentInstanceCount,
+    pub archetype_count: PerfUiEntryArchetypeCount,
+    pub table_count: PerfUiEntryTableCount,
+    pub component_count: PerfUiEntryComponentCount,
+    pub resource_count: PerfUiEntryResourceCount,
+}
+
 /// All entries related to windowing.
 ///
 /// ```rust
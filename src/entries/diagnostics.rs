@@ -1,11 +1,14 @@
 //! Perf UI Entries based on Bevy Diagnostics
 
 use bevy::prelude::*;
-use bevy::diagnostic::{DiagnosticsStore, EntityCountDiagnosticsPlugin, FrameTimeDiagnosticsPlugin};
+use bevy::diagnostic::{DiagnosticPath, DiagnosticsStore, EntityCountDiagnosticsPlugin, FrameTimeDiagnosticsPlugin};
 use bevy::ecs::system::lifetimeless::SRes;
 use bevy::ecs::system::SystemParam;
 use bevy::math::FloatOrd;
 
+use std::collections::VecDeque;
+use std::time::Duration;
+
 #[cfg(feature = "sysinfo")]
 use bevy::diagnostic::SystemInformationDiagnosticsPlugin;
 
@@ -14,8 +17,11 @@ use crate::entry::*;
 use crate::utils::*;
 
 /// Perf UI Entry to display Bevy's built-in FPS measurement diagnostic.
-#[derive(Component, Debug, Clone)]
+#[derive(Component, Debug, Clone, Reflect)]
 #[require(PerfUiRoot)]
+#[reflect(Component, Default, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct PerfUiEntryFPS {
     /// Custom label. If empty (default), the default label will be used.
     pub label: String,
@@ -23,7 +29,7 @@ pub struct PerfUiEntryFPS {
     ///
     /// To disable (always use default color), set to empty `ColorGradient::default()`.
     ///
-    /// Default: Red-Yellow-Green gradient between 30-60-120 FPS.
+    /// Default: Red-Yellow-Green gradient (or the default `ColorGradientPreset`) between 30-60-120 FPS.
     pub color_gradient: ColorGradient,
     /// Highlight the value if FPS is below this threshold.
     ///
@@ -49,6 +55,14 @@ pub struct PerfUiEntryFPS {
     ///
     /// Default: `0`
     pub precision: u8,
+    /// History of recent values, for widgets that can display it.
+    ///
+    /// Default: last 300 values, sampled every frame.
+    pub history: PerfUiHistory<f64>,
+    /// Update the displayed value at this interval, instead of the Perf UI's.
+    ///
+    /// Default: `None` (use the `update_interval` of the `PerfUiRoot`)
+    pub update_interval: Option<PerfUiUpdateInterval>,
     /// Sort Key (control where the entry will appear in the Perf UI).
     pub sort_key: i32,
 }
@@ -57,12 +71,14 @@ impl Default for PerfUiEntryFPS {
     fn default() -> Self {
         PerfUiEntryFPS {
             label: String::new(),
-            color_gradient: ColorGradient::new_preset_ryg(30.0, 60.0, 120.0).unwrap(),
+            color_gradient: ColorGradient::new_preset_default(30.0, 60.0, 120.0).unwrap(),
             threshold_highlight: Some(20.0),
             max_value_hint: None,
             smoothed: true,
             digits: 4,
             precision: 0,
+            history: PerfUiHistory::new(300),
+            update_interval: None,
             sort_key: next_sort_key(),
         }
     }
@@ -71,8 +87,14 @@ impl Default for PerfUiEntryFPS {
 /// Perf UI Entry to display Bevy's built-in FPS measurement diagnostic.
 ///
 /// Displays the worst (lowest) value in recent history.
-#[derive(Component, Debug, Clone)]
+///
+/// The entry keeps its own history of FPS values (see the `history` field),
+/// so it can look further back than Bevy's diagnostic history (120 values).
+#[derive(Component, Debug, Clone, Reflect)]
 #[require(PerfUiRoot)]
+#[reflect(Component, Default, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct PerfUiEntryFPSWorst {
     /// Custom label. If empty (default), the default label will be used.
     pub label: String,
@@ -80,7 +102,7 @@ pub struct PerfUiEntryFPSWorst {
     ///
     /// To disable (always use default color), set to empty `ColorGradient::default()`.
     ///
-    /// Default: Red-Yellow-Green gradient between 30-60-120 FPS.
+    /// Default: Red-Yellow-Green gradient (or the default `ColorGradientPreset`) between 30-60-120 FPS.
     pub color_gradient: ColorGradient,
     /// Highlight the value if FPS is below this threshold.
     ///
@@ -102,6 +124,16 @@ pub struct PerfUiEntryFPSWorst {
     ///
     /// Default: `0`
     pub precision: u8,
+    /// Recent FPS values, to find the worst one in.
+    ///
+    /// Set `max_len` and `sample_interval` to choose how far back to look.
+    ///
+    /// Default: last 300 values, sampled every frame.
+    pub history: PerfUiHistory<f32>,
+    /// Update the displayed value at this interval, instead of the Perf UI's.
+    ///
+    /// Default: `None` (use the `update_interval` of the `PerfUiRoot`)
+    pub update_interval: Option<PerfUiUpdateInterval>,
     /// Sort Key (control where the entry will appear in the Perf UI).
     pub sort_key: i32,
 }
@@ -110,11 +142,13 @@ impl Default for PerfUiEntryFPSWorst {
     fn default() -> Self {
         PerfUiEntryFPSWorst {
             label: String::new(),
-            color_gradient: ColorGradient::new_preset_ryg(30.0, 60.0, 120.0).unwrap(),
+            color_gradient: ColorGradient::new_preset_default(30.0, 60.0, 120.0).unwrap(),
             threshold_highlight: Some(20.0),
             max_value_hint: None,
             digits: 4,
             precision: 0,
+            history: PerfUiHistory::new(300),
+            update_interval: None,
             sort_key: next_sort_key(),
         }
     }
@@ -123,8 +157,11 @@ impl Default for PerfUiEntryFPSWorst {
 /// Perf UI Entry to display Bevy's built-in FPS measurement diagnostic.
 ///
 /// Displays the average of the values Bevy keeps in its history buffer.
-#[derive(Component, Debug, Clone)]
+#[derive(Component, Debug, Clone, Reflect)]
 #[require(PerfUiRoot)]
+#[reflect(Component, Default, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct PerfUiEntryFPSAverage {
     /// Custom label. If empty (default), the default label will be used.
     pub label: String,
@@ -132,7 +169,7 @@ pub struct PerfUiEntryFPSAverage {
     ///
     /// To disable (always use default color), set to empty `ColorGradient::default()`.
     ///
-    /// Default: Red-Yellow-Green gradient between 30-60-120 FPS.
+    /// Default: Red-Yellow-Green gradient (or the default `ColorGradientPreset`) between 30-60-120 FPS.
     pub color_gradient: ColorGradient,
     /// Highlight the value if FPS is below this threshold.
     ///
@@ -154,6 +191,10 @@ pub struct PerfUiEntryFPSAverage {
     ///
     /// Default: `0`
     pub precision: u8,
+    /// Update the displayed value at this interval, instead of the Perf UI's.
+    ///
+    /// Default: `None` (use the `update_interval` of the `PerfUiRoot`)
+    pub update_interval: Option<PerfUiUpdateInterval>,
     /// Sort Key (control where the entry will appear in the Perf UI).
     pub sort_key: i32,
 }
@@ -162,11 +203,12 @@ impl Default for PerfUiEntryFPSAverage {
     fn default() -> Self {
         PerfUiEntryFPSAverage {
             label: String::new(),
-            color_gradient: ColorGradient::new_preset_ryg(30.0, 60.0, 120.0).unwrap(),
+            color_gradient: ColorGradient::new_preset_default(30.0, 60.0, 120.0).unwrap(),
             threshold_highlight: Some(20.0),
             max_value_hint: None,
             digits: 4,
             precision: 0,
+            update_interval: None,
             sort_key: next_sort_key(),
         }
     }
@@ -185,12 +227,21 @@ impl Default for PerfUiEntryFPSAverage {
 /// The percentage of values to select is customizable, defaulting to 10%.
 /// See the `filter_fraction` field.
 ///
-/// The reason for using 10% instead of 1% as the default is that Bevy only
-/// keeps a history buffer of 120 values. Using 1% would only leave 2 values
-/// (rounded up). 10% is 12 values, which arguably gives a better indication
-/// of framerate stability.
-#[derive(Component, Debug, Clone)]
+/// The entry keeps its own history of FPS values (see the `history` field),
+/// so it can look further back than Bevy's diagnostic history (120 values).
+///
+/// The reason for using 10% instead of 1% as the default is that, with the
+/// default 300 values, 1% would only leave 3 values. 10% is 30 values,
+/// which arguably gives a better indication of framerate stability. For a
+/// meaningful 1% low, increase the length of `history` (such as to 1000).
+///
+/// For percentiles over a longer time window, see
+/// [`PerfUiEntryFrameTimePercentile`].
+#[derive(Component, Debug, Clone, Reflect)]
 #[require(PerfUiRoot)]
+#[reflect(Component, Default, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct PerfUiEntryFPSPctLow {
     /// Custom label. If empty (default), the default label will be used.
     pub label: String,
@@ -198,7 +249,7 @@ pub struct PerfUiEntryFPSPctLow {
     ///
     /// To disable (always use default color), set to empty `ColorGradient::default()`.
     ///
-    /// Default: Red-Yellow-Green gradient between 30-60-120 FPS.
+    /// Default: Red-Yellow-Green gradient (or the default `ColorGradientPreset`) between 30-60-120 FPS.
     pub color_gradient: ColorGradient,
     /// Highlight the value if FPS is below this threshold.
     ///
@@ -224,6 +275,16 @@ pub struct PerfUiEntryFPSPctLow {
     ///
     /// Default: `0.1` (i.e "10% low")
     pub filter_fraction: f32,
+    /// Recent FPS values, to select the lowest ones from.
+    ///
+    /// Set `max_len` and `sample_interval` to choose how far back to look.
+    ///
+    /// Default: last 300 values, sampled every frame.
+    pub history: PerfUiHistory<f32>,
+    /// Update the displayed value at this interval, instead of the Perf UI's.
+    ///
+    /// Default: `None` (use the `update_interval` of the `PerfUiRoot`)
+    pub update_interval: Option<PerfUiUpdateInterval>,
     /// Sort Key (control where the entry will appear in the Perf UI).
     pub sort_key: i32,
 }
@@ -232,12 +293,14 @@ impl Default for PerfUiEntryFPSPctLow {
     fn default() -> Self {
         PerfUiEntryFPSPctLow {
             label: String::new(),
-            color_gradient: ColorGradient::new_preset_ryg(30.0, 60.0, 120.0).unwrap(),
+            color_gradient: ColorGradient::new_preset_default(30.0, 60.0, 120.0).unwrap(),
             threshold_highlight: Some(20.0),
             max_value_hint: None,
             digits: 4,
             precision: 0,
             filter_fraction: 0.1,
+            history: PerfUiHistory::new(300),
+            update_interval: None,
             sort_key: next_sort_key(),
         }
     }
@@ -246,8 +309,11 @@ impl Default for PerfUiEntryFPSPctLow {
 /// Perf UI Entry to display Bevy's built-in frame time measurement diagnostic.
 ///
 /// Displays the frame time in *milliseconds*.
-#[derive(Component, Debug, Clone)]
+#[derive(Component, Debug, Clone, Reflect)]
 #[require(PerfUiRoot)]
+#[reflect(Component, Default, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct PerfUiEntryFrameTime {
     /// Custom label. If empty (default), the default label will be used.
     pub label: String,
@@ -259,7 +325,7 @@ pub struct PerfUiEntryFrameTime {
     ///
     /// To disable (always use default color), set to empty `ColorGradient::default()`.
     ///
-    /// Default: Green-Yellow-Red gradient between the frametimes equivalent to 120-60-30 FPS.
+    /// Default: Green-Yellow-Red gradient (or the default `ColorGradientPreset`, reversed) between the frametimes equivalent to 120-60-30 FPS.
     pub color_gradient: ColorGradient,
     /// Highlight the value if frame time is above this threshold.
     ///
@@ -285,6 +351,14 @@ pub struct PerfUiEntryFrameTime {
     ///
     /// Default: `3`
     pub precision: u8,
+    /// History of recent values, for widgets that can display it.
+    ///
+    /// Default: last 300 values, sampled every frame.
+    pub history: PerfUiHistory<f64>,
+    /// Update the displayed value at this interval, instead of the Perf UI's.
+    ///
+    /// Default: `None` (use the `update_interval` of the `PerfUiRoot`)
+    pub update_interval: Option<PerfUiUpdateInterval>,
     /// Sort Key (control where the entry will appear in the Perf UI).
     pub sort_key: i32,
 }
@@ -294,7 +368,7 @@ impl Default for PerfUiEntryFrameTime {
         PerfUiEntryFrameTime {
             label: String::new(),
             display_units: true,
-            color_gradient: ColorGradient::new_preset_gyr(
+            color_gradient: ColorGradient::new_preset_default_reversed(
                 1000.0 / 120.0,
                 1000.0 / 60.0,
                 1000.0 / 30.0,
@@ -304,6 +378,8 @@ impl Default for PerfUiEntryFrameTime {
             smoothed: false,
             digits: 2,
             precision: 3,
+            history: PerfUiHistory::new(300),
+            update_interval: None,
             sort_key: next_sort_key(),
         }
     }
@@ -313,9 +389,15 @@ impl Default for PerfUiEntryFrameTime {
 ///
 /// Displays the worst (highest) value in recent history.
 ///
+/// The entry keeps its own history of frame times (see the `history` field),
+/// so it can look further back than Bevy's diagnostic history (120 values).
+///
 /// Displays the frame time in *milliseconds*.
-#[derive(Component, Debug, Clone)]
+#[derive(Component, Debug, Clone, Reflect)]
 #[require(PerfUiRoot)]
+#[reflect(Component, Default, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct PerfUiEntryFrameTimeWorst {
     /// Custom label. If empty (default), the default label will be used.
     pub label: String,
@@ -327,7 +409,7 @@ pub struct PerfUiEntryFrameTimeWorst {
     ///
     /// To disable (always use default color), set to empty `ColorGradient::default()`.
     ///
-    /// Default: Green-Yellow-Red gradient between the frametimes equivalent to 120-60-30 FPS.
+    /// Default: Green-Yellow-Red gradient (or the default `ColorGradientPreset`, reversed) between the frametimes equivalent to 120-60-30 FPS.
     pub color_gradient: ColorGradient,
     /// Highlight the value if frame time is above this threshold.
     ///
@@ -349,6 +431,16 @@ pub struct PerfUiEntryFrameTimeWorst {
     ///
     /// Default: `3`
     pub precision: u8,
+    /// Recent frame times, to find the worst one in.
+    ///
+    /// Set `max_len` and `sample_interval` to choose how far back to look.
+    ///
+    /// Default: last 300 values, sampled every frame.
+    pub history: PerfUiHistory<f32>,
+    /// Update the displayed value at this interval, instead of the Perf UI's.
+    ///
+    /// Default: `None` (use the `update_interval` of the `PerfUiRoot`)
+    pub update_interval: Option<PerfUiUpdateInterval>,
     /// Sort Key (control where the entry will appear in the Perf UI).
     pub sort_key: i32,
 }
@@ -358,7 +450,107 @@ impl Default for PerfUiEntryFrameTimeWorst {
         PerfUiEntryFrameTimeWorst {
             label: String::new(),
             display_units: true,
-            color_gradient: ColorGradient::new_preset_gyr(
+            color_gradient: ColorGradient::new_preset_default_reversed(
+                1000.0 / 120.0,
+                1000.0 / 60.0,
+                1000.0 / 30.0,
+            ).unwrap(),
+            threshold_highlight: Some(1000.0 / 20.0),
+            max_value_hint: None,
+            digits: 2,
+            precision: 3,
+            history: PerfUiHistory::new(300),
+            update_interval: None,
+            sort_key: next_sort_key(),
+        }
+    }
+}
+
+/// Perf UI Entry to display a percentile of frame times.
+///
+/// For example, the p99 frame time is the frame time that 99% of frames
+/// were faster than (or as fast as), which tells you how bad the occasional
+/// slow frames are.
+///
+/// Unlike the other frame time entries, this entry keeps its own buffer of
+/// frame times, covering a configurable time window (or the whole session).
+/// Bevy's diagnostic only keeps the last 120 values, which is not enough
+/// for high percentiles to be meaningful. Frame times are recorded every
+/// frame (even if the Perf UI is hidden), measured using `Time<Real>`,
+/// so `FrameTimeDiagnosticsPlugin` is not required.
+///
+/// To display multiple percentiles, put the extra entries on
+/// child entities of the Perf UI entity.
+///
+/// Displays the frame time in *milliseconds*.
+#[derive(Component, Debug, Clone, Reflect)]
+#[require(PerfUiRoot)]
+#[reflect(Component, Default, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct PerfUiEntryFrameTimePercentile {
+    /// Custom label. If empty (default), the default label will be used.
+    pub label: String,
+    /// Which percentile to display (`0.0`-`100.0`).
+    ///
+    /// Default: `99.0`
+    pub percentile: f32,
+    /// Which frames to compute the percentile over.
+    ///
+    /// Default: the last 10 seconds
+    pub window: PerfUiStatsWindow,
+    /// Display the unit ("ms") alongside the number.
+    ///
+    /// Default: `true`
+    pub display_units: bool,
+    /// Enable color based on value.
+    ///
+    /// To disable (always use default color), set to empty `ColorGradient::default()`.
+    ///
+    /// Default: Green-Yellow-Red gradient (or the default `ColorGradientPreset`, reversed) between the frametimes equivalent to 120-60-30 FPS.
+    pub color_gradient: ColorGradient,
+    /// Highlight the value if frame time is above this threshold.
+    ///
+    /// Default: frametime equivalent to 20 FPS
+    pub threshold_highlight: Option<f32>,
+    /// If displayed using a Bar (or other similar) widget that can
+    /// show the value within a range, what should its max value be?
+    ///
+    /// If `None`, the value will be computed from the maximum of the
+    /// color gradient and the highlight threshold.
+    ///
+    /// Default: `None`
+    pub max_value_hint: Option<f32>,
+    /// Number of digits to display for the integer (whole number) part.
+    ///
+    /// Default: `2`
+    pub digits: u8,
+    /// Number of digits to display for the fractional (after the decimal point) part.
+    ///
+    /// Default: `3`
+    pub precision: u8,
+    /// Update the displayed value at this interval, instead of the Perf UI's.
+    ///
+    /// Default: `None` (use the `update_interval` of the `PerfUiRoot`)
+    pub update_interval: Option<PerfUiUpdateInterval>,
+    /// The frame times collected so far.
+    ///
+    /// Default: empty
+    #[reflect(ignore)]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub samples: PerfUiStatsSamples,
+    /// Sort Key (control where the entry will appear in the Perf UI).
+    pub sort_key: i32,
+}
+
+impl Default for PerfUiEntryFrameTimePercentile {
+    fn default() -> Self {
+        PerfUiEntryFrameTimePercentile {
+            label: String::new(),
+            percentile: 99.0,
+            window: PerfUiStatsWindow::Seconds(10.0),
+            display_units: true,
+            color_gradient: ColorGradient::new_preset_default_reversed(
                 1000.0 / 120.0,
                 1000.0 / 60.0,
                 1000.0 / 30.0,
@@ -367,14 +559,288 @@ impl Default for PerfUiEntryFrameTimeWorst {
             max_value_hint: None,
             digits: 2,
             precision: 3,
+            update_interval: None,
+            samples: default(),
+            sort_key: next_sort_key(),
+        }
+    }
+}
+
+/// How should [`PerfUiEntryFrameTimeJitter`] measure the variation of frame times?
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Debug, Default, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PerfUiJitterMethod {
+    /// The average difference between the frame times of consecutive frames.
+    ///
+    /// This is sensitive to frame pacing problems (frame times alternating
+    /// between fast and slow), but not to gradual changes in framerate.
+    #[default]
+    MeanAbsDelta,
+    /// The standard deviation of frame times.
+    StdDev,
+}
+
+/// Perf UI Entry to display the jitter (variation) of frame times.
+///
+/// This is a single number that indicates the quality of frame pacing:
+/// a stable framerate has low jitter, even if the framerate itself is low,
+/// while a framerate that swings up and down has high jitter.
+///
+/// Computed over the recent history of Bevy's frame time diagnostic.
+///
+/// Displays the jitter in *milliseconds*.
+#[derive(Component, Debug, Clone, Reflect)]
+#[require(PerfUiRoot)]
+#[reflect(Component, Default, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct PerfUiEntryFrameTimeJitter {
+    /// Custom label. If empty (default), the default label will be used.
+    pub label: String,
+    /// How to measure the variation of frame times.
+    ///
+    /// Default: `MeanAbsDelta`
+    pub method: PerfUiJitterMethod,
+    /// Display the unit ("ms") alongside the number.
+    ///
+    /// Default: `true`
+    pub display_units: bool,
+    /// Enable color based on value.
+    ///
+    /// To disable (always use default color), set to empty `ColorGradient::default()`.
+    ///
+    /// Default: Green-Yellow-Red gradient (or the default `ColorGradientPreset`, reversed) between 0.5-2-5 ms.
+    pub color_gradient: ColorGradient,
+    /// Highlight the value if the jitter is above this threshold.
+    ///
+    /// Default: `8.0` ms
+    pub threshold_highlight: Option<f32>,
+    /// If displayed using a Bar (or other similar) widget that can
+    /// show the value within a range, what should its max value be?
+    ///
+    /// If `None`, the value will be computed from the maximum of the
+    /// color gradient and the highlight threshold.
+    ///
+    /// Default: `None`
+    pub max_value_hint: Option<f32>,
+    /// Number of digits to display for the integer (whole number) part.
+    ///
+    /// Default: `2`
+    pub digits: u8,
+    /// Number of digits to display for the fractional (after the decimal point) part.
+    ///
+    /// Default: `3`
+    pub precision: u8,
+    /// Update the displayed value at this interval, instead of the Perf UI's.
+    ///
+    /// Default: `None` (use the `update_interval` of the `PerfUiRoot`)
+    pub update_interval: Option<PerfUiUpdateInterval>,
+    /// Sort Key (control where the entry will appear in the Perf UI).
+    pub sort_key: i32,
+}
+
+impl Default for PerfUiEntryFrameTimeJitter {
+    fn default() -> Self {
+        PerfUiEntryFrameTimeJitter {
+            label: String::new(),
+            method: default(),
+            display_units: true,
+            color_gradient: ColorGradient::new_preset_default_reversed(0.5, 2.0, 5.0).unwrap(),
+            threshold_highlight: Some(8.0),
+            max_value_hint: None,
+            digits: 2,
+            precision: 3,
+            update_interval: None,
+            sort_key: next_sort_key(),
+        }
+    }
+}
+
+/// How slow must a frame be, to count as a hitch?
+#[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Debug, Default, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PerfUiHitchThreshold {
+    /// Frames slower than this many milliseconds.
+    Millis(f32),
+    /// Frames slower than this multiple of the median frame time.
+    ///
+    /// The median is computed over the `median_window` of the entry.
+    MedianMultiple(f32),
+}
+
+impl Default for PerfUiHitchThreshold {
+    fn default() -> Self {
+        PerfUiHitchThreshold::MedianMultiple(2.0)
+    }
+}
+
+/// Perf UI Entry to count hitches (stutters): frames slower than a threshold.
+///
+/// Counts all hitches since app startup (or since `reset`), so that short
+/// hitches do not go unnoticed, even if they happen when nobody is looking
+/// at the other frame time entries. Also displays the number of hitches in
+/// the last minute, and how long ago the last hitch happened.
+///
+/// Frame times are recorded every frame (even if the Perf UI is hidden),
+/// measured using `Time<Real>`, so `FrameTimeDiagnosticsPlugin` is not required.
+///
+/// The value of the entry is the number of hitches in the last minute.
+/// The color, highlight, and the numeric value (`PerfUiEntry::value_as_f64`,
+/// used by recorders, snapshots and benchmarks) are all based on it, as
+/// is the fill of a Bar widget, or the statistics of `PerfUiEntryStats`.
+/// The total count and the time since the last hitch are available from
+/// the `state`.
+#[derive(Component, Debug, Clone, Reflect)]
+#[require(PerfUiRoot)]
+#[reflect(Component, Default, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct PerfUiEntryHitches {
+    /// Custom label. If empty (default), the default label will be used.
+    pub label: String,
+    /// How slow must a frame be, to count as a hitch?
+    ///
+    /// Note: at very high framerates, small absolute variations in
+    /// frame time can exceed a multiple of the median. Consider using
+    /// `Millis` if that is a problem.
+    ///
+    /// Default: 2x the median frame time
+    pub threshold: PerfUiHitchThreshold,
+    /// Which frames to compute the median frame time over,
+    /// if the threshold is `MedianMultiple`.
+    ///
+    /// Default: the last 5 seconds
+    pub median_window: PerfUiStatsWindow,
+    /// Display the number of hitches in the last minute.
+    ///
+    /// Default: `true`
+    pub display_per_minute: bool,
+    /// Display how long ago the last hitch happened.
+    ///
+    /// Default: `true`
+    pub display_since_last: bool,
+    /// Enable color based on the number of hitches in the last minute.
+    ///
+    /// To disable (always use default color), set to empty `ColorGradient::default()`.
+    ///
+    /// Default: Green-Yellow-Red gradient (or the default `ColorGradientPreset`, reversed) between 0-2-6 hitches per minute.
+    pub color_gradient: ColorGradient,
+    /// Highlight the value if the number of hitches in the last minute is above this threshold.
+    ///
+    /// Default: `10.0`
+    pub threshold_highlight: Option<f32>,
+    /// If displayed using a Bar (or other similar) widget that can
+    /// show the value within a range, what should its max value be?
+    ///
+    /// If `None`, the value will be computed from the maximum of the
+    /// color gradient and the highlight threshold.
+    ///
+    /// Default: `None`
+    pub max_value_hint: Option<u32>,
+    /// Update the displayed value at this interval, instead of the Perf UI's.
+    ///
+    /// Default: `None` (use the `update_interval` of the `PerfUiRoot`)
+    pub update_interval: Option<PerfUiUpdateInterval>,
+    /// The hitches and frame times recorded so far.
+    ///
+    /// Default: empty
+    #[reflect(ignore)]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub state: PerfUiHitchState,
+    /// Sort Key (control where the entry will appear in the Perf UI).
+    pub sort_key: i32,
+}
+
+impl Default for PerfUiEntryHitches {
+    fn default() -> Self {
+        PerfUiEntryHitches {
+            label: String::new(),
+            threshold: default(),
+            median_window: PerfUiStatsWindow::Seconds(5.0),
+            display_per_minute: true,
+            display_since_last: true,
+            color_gradient: ColorGradient::new_preset_default_reversed(0.0, 2.0, 6.0).unwrap(),
+            threshold_highlight: Some(10.0),
+            max_value_hint: None,
+            update_interval: None,
+            state: default(),
             sort_key: next_sort_key(),
         }
     }
 }
 
+impl PerfUiEntryHitches {
+    /// Forget all hitches recorded so far, and start counting again.
+    pub fn reset(&mut self) {
+        self.state = default();
+    }
+}
+
+/// The hitches recorded by a [`PerfUiEntryHitches`].
+///
+/// The framework records new frame times every frame. This is only meant
+/// to be stored in the entry, it is not serialized/reflected.
+#[derive(Debug, Default, Clone)]
+pub struct PerfUiHitchState {
+    /// Recent frame times, to compute the median.
+    frame_times: PerfUiStatsSamples,
+    /// Total number of hitches.
+    count: u32,
+    /// When the hitches in the last minute happened.
+    recent: VecDeque<Duration>,
+    /// When the last hitch happened.
+    last: Option<Duration>,
+    /// When the last frame time was recorded.
+    now: Duration,
+}
+
+impl PerfUiHitchState {
+    /// Total number of hitches, since startup (or since the entry was reset).
+    pub fn count(&self) -> u32 {
+        self.count
+    }
+
+    /// Number of hitches in the last minute.
+    pub fn per_minute(&self) -> u32 {
+        self.recent.len() as u32
+    }
+
+    /// How long ago the last hitch happened, if any.
+    pub fn since_last(&self) -> Option<Duration> {
+        self.last.map(|t| self.now.saturating_sub(t))
+    }
+
+    fn record(&mut self, threshold: PerfUiHitchThreshold, median_window: PerfUiStatsWindow, now: Duration, frame_time: Option<f64>) {
+        self.now = now;
+        while self.recent.front().is_some_and(|t| now.saturating_sub(*t) > Duration::from_secs(60)) {
+            self.recent.pop_front();
+        }
+        let limit = match threshold {
+            PerfUiHitchThreshold::Millis(ms) => Some(ms as f64),
+            PerfUiHitchThreshold::MedianMultiple(x) => {
+                let median = self.frame_times.compute(PerfUiStat::Percentile(50.0), median_window);
+                self.frame_times.record(PerfUiStat::Percentile(50.0), median_window, now, frame_time);
+                median.map(|m| m * x as f64)
+            }
+        };
+        if let (Some(frame_time), Some(limit)) = (frame_time, limit) {
+            if frame_time > limit {
+                self.count = self.count.saturating_add(1);
+                self.recent.push_back(now);
+                self.last = Some(now);
+            }
+        }
+    }
+}
+
 /// Perf UI Entry to display Bevy's built-in frame counter.
-#[derive(Component, Debug, Clone)]
+#[derive(Component, Debug, Clone, Reflect)]
 #[require(PerfUiRoot)]
+#[reflect(Component, Default, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct PerfUiEntryFrameCount {
     /// Custom label. If empty (default), the default label will be used.
     pub label: String,
@@ -382,6 +848,10 @@ pub struct PerfUiEntryFrameCount {
     ///
     /// Default: `6`
     pub digits: u8,
+    /// Update the displayed value at this interval, instead of the Perf UI's.
+    ///
+    /// Default: `None` (use the `update_interval` of the `PerfUiRoot`)
+    pub update_interval: Option<PerfUiUpdateInterval>,
     /// Sort Key (control where the entry will appear in the Perf UI).
     pub sort_key: i32,
 }
@@ -391,14 +861,18 @@ impl Default for PerfUiEntryFrameCount {
         PerfUiEntryFrameCount {
             label: String::new(),
             digits: 6,
+            update_interval: None,
             sort_key: next_sort_key(),
         }
     }
 }
 
 /// Perf UI Entry to display Bevy's built-in ECS entity counter.
-#[derive(Component, Debug, Clone)]
+#[derive(Component, Debug, Clone, Reflect)]
 #[require(PerfUiRoot)]
+#[reflect(Component, Default, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct PerfUiEntryEntityCount {
     /// Custom label. If empty (default), the default label will be used.
     pub label: String,
@@ -406,7 +880,7 @@ pub struct PerfUiEntryEntityCount {
     ///
     /// To disable (always use default color), set to empty `ColorGradient::default()`.
     ///
-    /// Default: Green-Yellow-Red gradient between 100-1000-10000.
+    /// Default: Green-Yellow-Red gradient (or the default `ColorGradientPreset`, reversed) between 100-1000-10000.
     pub color_gradient: ColorGradient,
     /// Highlight the value if above this threshold.
     ///
@@ -424,6 +898,14 @@ pub struct PerfUiEntryEntityCount {
     ///
     /// Default: `6`
     pub digits: u8,
+    /// History of recent values, for widgets that can display it.
+    ///
+    /// Default: last 300 values, sampled every frame.
+    pub history: PerfUiHistory<u32>,
+    /// Update the displayed value at this interval, instead of the Perf UI's.
+    ///
+    /// Default: `None` (use the `update_interval` of the `PerfUiRoot`)
+    pub update_interval: Option<PerfUiUpdateInterval>,
     /// Sort Key (control where the entry will appear in the Perf UI).
     pub sort_key: i32,
 }
@@ -432,10 +914,12 @@ impl Default for PerfUiEntryEntityCount {
     fn default() -> Self {
         PerfUiEntryEntityCount {
             label: String::new(),
-            color_gradient: ColorGradient::new_preset_gyr(100.0, 1000.0, 10000.0).unwrap(),
+            color_gradient: ColorGradient::new_preset_default_reversed(100.0, 1000.0, 10000.0).unwrap(),
             threshold_highlight: Some(20000),
             max_value_hint: None,
             digits: 6,
+            history: PerfUiHistory::new(300),
+            update_interval: None,
             sort_key: next_sort_key(),
         }
     }
@@ -445,8 +929,11 @@ impl Default for PerfUiEntryEntityCount {
 ///
 /// Displays the CPU usage of the current process (your game) as a percentage.
 #[cfg(feature = "sysinfo")]
-#[derive(Component, Debug, Clone)]
+#[derive(Component, Debug, Clone, Reflect)]
 #[require(PerfUiRoot)]
+#[reflect(Component, Default, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct PerfUiEntryCpuUsage {
     /// Custom label. If empty (default), the default label will be used.
     pub label: String,
@@ -454,7 +941,7 @@ pub struct PerfUiEntryCpuUsage {
     ///
     /// To disable (always use default color), set to empty `ColorGradient::default()`.
     ///
-    /// Default: Green-Yellow-Red gradient between 25%-50%-75%.
+    /// Default: Green-Yellow-Red gradient (or the default `ColorGradientPreset`, reversed) between 25%-50%-75%.
     pub color_gradient: ColorGradient,
     /// Highlight the value if above this threshold.
     ///
@@ -468,6 +955,14 @@ pub struct PerfUiEntryCpuUsage {
     ///
     /// Default: `2`
     pub precision: u8,
+    /// History of recent values, for widgets that can display it.
+    ///
+    /// Default: last 60 values, sampled every second.
+    pub history: PerfUiHistory<f64>,
+    /// Update the displayed value at this interval, instead of the Perf UI's.
+    ///
+    /// Default: every second (the system info is only refreshed that often)
+    pub update_interval: Option<PerfUiUpdateInterval>,
     /// Sort Key (control where the entry will appear in the Perf UI).
     pub sort_key: i32,
 }
@@ -477,10 +972,13 @@ impl Default for PerfUiEntryCpuUsage {
     fn default() -> Self {
         PerfUiEntryCpuUsage {
             label: String::new(),
-            color_gradient: ColorGradient::new_preset_gyr(25.0, 50.0, 75.0).unwrap(),
+            color_gradient: ColorGradient::new_preset_default_reversed(25.0, 50.0, 75.0).unwrap(),
             threshold_highlight: Some(90.0),
             smoothed: true,
             precision: 2,
+            history: PerfUiHistory::new(60)
+                .with_sample_interval(Duration::from_secs(1)),
+            update_interval: Some(PerfUiUpdateInterval::Seconds(1.0)),
             sort_key: next_sort_key(),
         }
     }
@@ -490,8 +988,11 @@ impl Default for PerfUiEntryCpuUsage {
 ///
 /// Displays the Total System CPU usage as a percentage.
 #[cfg(feature = "sysinfo")]
-#[derive(Component, Debug, Clone)]
+#[derive(Component, Debug, Clone, Reflect)]
 #[require(PerfUiRoot)]
+#[reflect(Component, Default, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct PerfUiEntrySystemCpuUsage {
     /// Custom label. If empty (default), the default label will be used.
     pub label: String,
@@ -499,7 +1000,7 @@ pub struct PerfUiEntrySystemCpuUsage {
     ///
     /// To disable (always use default color), set to empty `ColorGradient::default()`.
     ///
-    /// Default: Green-Yellow-Red gradient between 25%-50%-75%.
+    /// Default: Green-Yellow-Red gradient (or the default `ColorGradientPreset`, reversed) between 25%-50%-75%.
     pub color_gradient: ColorGradient,
     /// Highlight the value if above this threshold.
     ///
@@ -513,6 +1014,14 @@ pub struct PerfUiEntrySystemCpuUsage {
     ///
     /// Default: `2`
     pub precision: u8,
+    /// History of recent values, for widgets that can display it.
+    ///
+    /// Default: last 60 values, sampled every second.
+    pub history: PerfUiHistory<f64>,
+    /// Update the displayed value at this interval, instead of the Perf UI's.
+    ///
+    /// Default: every second (the system info is only refreshed that often)
+    pub update_interval: Option<PerfUiUpdateInterval>,
     /// Sort Key (control where the entry will appear in the Perf UI).
     pub sort_key: i32,
 }
@@ -522,10 +1031,13 @@ impl Default for PerfUiEntrySystemCpuUsage {
     fn default() -> Self {
         PerfUiEntrySystemCpuUsage {
             label: String::new(),
-            color_gradient: ColorGradient::new_preset_gyr(25.0, 50.0, 75.0).unwrap(),
+            color_gradient: ColorGradient::new_preset_default_reversed(25.0, 50.0, 75.0).unwrap(),
             threshold_highlight: Some(90.0),
             smoothed: true,
             precision: 2,
+            history: PerfUiHistory::new(60)
+                .with_sample_interval(Duration::from_secs(1)),
+            update_interval: Some(PerfUiUpdateInterval::Seconds(1.0)),
             sort_key: next_sort_key(),
         }
     }
@@ -535,8 +1047,11 @@ impl Default for PerfUiEntrySystemCpuUsage {
 ///
 /// Displays the amount of RAM used by the current process (your game) in GiB.
 #[cfg(feature = "sysinfo")]
-#[derive(Component, Debug, Clone)]
+#[derive(Component, Debug, Clone, Reflect)]
 #[require(PerfUiRoot)]
+#[reflect(Component, Default, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct PerfUiEntryMemUsage {
     /// Custom label. If empty (default), the default label will be used.
     pub label: String,
@@ -548,7 +1063,7 @@ pub struct PerfUiEntryMemUsage {
     ///
     /// To disable (always use default color), set to empty `ColorGradient::default()`.
     ///
-    /// Default: Green-Yellow-Red gradient between 0.5-1.0-2.0 GiB.
+    /// Default: Green-Yellow-Red gradient (or the default `ColorGradientPreset`, reversed) between 0.5-1.0-2.0 GiB.
     pub color_gradient: ColorGradient,
     /// Highlight the value if above this threshold.
     ///
@@ -570,6 +1085,14 @@ pub struct PerfUiEntryMemUsage {
     ///
     /// Default: `3`
     pub precision: u8,
+    /// History of recent values, for widgets that can display it.
+    ///
+    /// Default: last 60 values, sampled every second.
+    pub history: PerfUiHistory<f64>,
+    /// Update the displayed value at this interval, instead of the Perf UI's.
+    ///
+    /// Default: every second (the system info is only refreshed that often)
+    pub update_interval: Option<PerfUiUpdateInterval>,
     /// Sort Key (control where the entry will appear in the Perf UI).
     pub sort_key: i32,
 }
@@ -580,11 +1103,14 @@ impl Default for PerfUiEntryMemUsage {
         PerfUiEntryMemUsage {
             label: String::new(),
             display_units: true,
-            color_gradient: ColorGradient::new_preset_gyr(0.5, 1.0, 2.0).unwrap(),
+            color_gradient: ColorGradient::new_preset_default_reversed(0.5, 1.0, 2.0).unwrap(),
             threshold_highlight: Some(3.0),
             max_value_hint: Some(4.0),
             smoothed: true,
             precision: 3,
+            history: PerfUiHistory::new(60)
+                .with_sample_interval(Duration::from_secs(1)),
+            update_interval: Some(PerfUiUpdateInterval::Seconds(1.0)),
             sort_key: next_sort_key(),
         }
     }
@@ -594,8 +1120,11 @@ impl Default for PerfUiEntryMemUsage {
 ///
 /// Displays the Total System RAM usage as a percentage.
 #[cfg(feature = "sysinfo")]
-#[derive(Component, Debug, Clone)]
+#[derive(Component, Debug, Clone, Reflect)]
 #[require(PerfUiRoot)]
+#[reflect(Component, Default, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct PerfUiEntrySystemMemUsage {
     /// Custom label. If empty (default), the default label will be used.
     pub label: String,
@@ -603,7 +1132,7 @@ pub struct PerfUiEntrySystemMemUsage {
     ///
     /// To disable (always use default color), set to empty `ColorGradient::default()`.
     ///
-    /// Default: Green-Yellow-Red gradient between 25%-50%-75%.
+    /// Default: Green-Yellow-Red gradient (or the default `ColorGradientPreset`, reversed) between 25%-50%-75%.
     pub color_gradient: ColorGradient,
     /// Highlight the value if above this threshold.
     ///
@@ -617,6 +1146,14 @@ pub struct PerfUiEntrySystemMemUsage {
     ///
     /// Default: `2`
     pub precision: u8,
+    /// History of recent values, for widgets that can display it.
+    ///
+    /// Default: last 60 values, sampled every second.
+    pub history: PerfUiHistory<f64>,
+    /// Update the displayed value at this interval, instead of the Perf UI's.
+    ///
+    /// Default: every second (the system info is only refreshed that often)
+    pub update_interval: Option<PerfUiUpdateInterval>,
     /// Sort Key (control where the entry will appear in the Perf UI).
     pub sort_key: i32,
 }
@@ -626,62 +1163,254 @@ impl Default for PerfUiEntrySystemMemUsage {
     fn default() -> Self {
         PerfUiEntrySystemMemUsage {
             label: String::new(),
-            color_gradient: ColorGradient::new_preset_gyr(25.0, 50.0, 75.0).unwrap(),
+            color_gradient: ColorGradient::new_preset_default_reversed(25.0, 50.0, 75.0).unwrap(),
             threshold_highlight: Some(90.0),
             smoothed: true,
             precision: 2,
+            history: PerfUiHistory::new(60)
+                .with_sample_interval(Duration::from_secs(1)),
+            update_interval: Some(PerfUiUpdateInterval::Seconds(1.0)),
             sort_key: next_sort_key(),
         }
     }
 }
 
-impl PerfUiEntry for PerfUiEntryFPS {
-    type SystemParam = SRes<DiagnosticsStore>;
-    type Value = f64;
-
-    fn label(&self) -> &str {
-        if self.label.is_empty() {
-            "FPS"
-        } else {
-            &self.label
-        }
-    }
-    fn update_value(
-        &self,
-        diagnostics: &mut <Self::SystemParam as SystemParam>::Item<'_, '_>,
-    ) -> Option<Self::Value> {
-        Some(if self.smoothed {
-            diagnostics.get(&FrameTimeDiagnosticsPlugin::FPS)?.smoothed()?
-        } else {
-            diagnostics.get(&FrameTimeDiagnosticsPlugin::FPS)?.value()?
-        })
-    }
-    fn format_value(
-        &self,
-        value: &Self::Value,
-    ) -> String {
-        format_pretty_float(self.digits, self.precision, *value)
-    }
-    fn value_color(
-        &self,
-        value: &Self::Value,
-    ) -> Option<Color> {
-        self.color_gradient.get_color_for_value(*value as f32)
-    }
-    fn value_highlight(
-        &self,
-        value: &Self::Value,
-    ) -> bool {
-        self.threshold_highlight
-            .map(|t| (*value as f32) < t)
-            .unwrap_or(false)
-    }
-    fn sort_key(&self) -> i32 {
-        self.sort_key
-    }
-}
-
-impl PerfUiEntryDisplayRange for PerfUiEntryFPS {
+/// Perf UI Entry to display any Bevy diagnostic, selected at runtime by its path.
+///
+/// Use this to display your own custom diagnostics, without having to
+/// implement a new entry type for each one.
+///
+/// ```rust
+/// commands.spawn((
+///     PerfUiRoot::default(),
+///     PerfUiEntryDiagnostic {
+///         label: "Pathfinding".into(),
+///         unit: "ms".into(),
+///         ..PerfUiEntryDiagnostic::new(MyPathfindingPlugin::PATHFINDING_TIME)
+///     },
+/// ));
+/// ```
+///
+/// The displayed value is the diagnostic's value multiplied by `scale`.
+/// All other settings (gradient, thresholds, value hints) apply to the
+/// scaled value.
+///
+/// `DiagnosticPath` does not support reflection, so this entry is reflected
+/// as an opaque value. To save it in scenes, enable the `serde` cargo feature.
+#[derive(Component, Debug, Clone, Reflect)]
+#[require(PerfUiRoot)]
+#[reflect(opaque)]
+#[reflect(Component, Debug, Clone)]
+#[cfg_attr(feature = "serde", reflect(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PerfUiEntryDiagnostic {
+    /// The path of the diagnostic to display.
+    #[cfg_attr(feature = "serde", serde(with = "serde_diagnostic::path"))]
+    pub path: DiagnosticPath,
+    /// Custom label. If empty (default), the diagnostic path will be used.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub label: String,
+    /// The unit to display alongside the number.
+    ///
+    /// If empty, no unit will be displayed.
+    ///
+    /// Default: empty
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub unit: String,
+    /// Multiply the diagnostic's value by this factor before displaying it.
+    ///
+    /// Useful for unit conversions, such as seconds to milliseconds.
+    ///
+    /// Default: `1.0`
+    #[cfg_attr(feature = "serde", serde(default = "serde_diagnostic::scale"))]
+    pub scale: f64,
+    /// Enable color based on value.
+    ///
+    /// To disable (always use default color), set to empty `ColorGradient::default()`.
+    ///
+    /// Default: empty (disabled)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub color_gradient: ColorGradient,
+    /// Highlight the value if above this threshold.
+    ///
+    /// Default: `None`
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub threshold_highlight_above: Option<f32>,
+    /// Highlight the value if below this threshold.
+    ///
+    /// Default: `None`
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub threshold_highlight_below: Option<f32>,
+    /// If displayed using a Bar (or other similar) widget that can
+    /// show the value within a range, what should its max value be?
+    ///
+    /// If `None`, the value will be computed from the maximum of the
+    /// color gradient and the highlight thresholds.
+    ///
+    /// Default: `None`
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub max_value_hint: Option<f32>,
+    /// If displayed using a Bar (or other similar) widget that can
+    /// show the value within a range, what should its min value be?
+    ///
+    /// If `None`, the value will be computed from the minimum of the
+    /// color gradient and the highlight thresholds.
+    ///
+    /// Default: `None`
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub min_value_hint: Option<f32>,
+    /// Should we display the smoothed value or the raw value?
+    ///
+    /// Default: false (raw)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub smoothed: bool,
+    /// Number of digits to display for the integer (whole number) part.
+    ///
+    /// Default: `4`
+    #[cfg_attr(feature = "serde", serde(default = "serde_diagnostic::digits"))]
+    pub digits: u8,
+    /// Number of digits to display for the fractional (after the decimal point) part.
+    ///
+    /// Default: `2`
+    #[cfg_attr(feature = "serde", serde(default = "serde_diagnostic::precision"))]
+    pub precision: u8,
+    /// History of recent values, for widgets that can display it.
+    ///
+    /// Default: last 300 values, sampled every frame.
+    #[cfg_attr(feature = "serde", serde(default = "serde_diagnostic::history"))]
+    pub history: PerfUiHistory<f64>,
+    /// Update the displayed value at this interval, instead of the Perf UI's.
+    ///
+    /// Default: `None` (use the `update_interval` of the `PerfUiRoot`)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub update_interval: Option<PerfUiUpdateInterval>,
+    /// Sort Key (control where the entry will appear in the Perf UI).
+    #[cfg_attr(feature = "serde", serde(default = "next_sort_key"))]
+    pub sort_key: i32,
+}
+
+impl PerfUiEntryDiagnostic {
+    /// Create an entry for the diagnostic with the given path, with default settings.
+    pub fn new(path: DiagnosticPath) -> Self {
+        PerfUiEntryDiagnostic {
+            path,
+            label: String::new(),
+            unit: String::new(),
+            scale: 1.0,
+            color_gradient: ColorGradient::new(),
+            threshold_highlight_above: None,
+            threshold_highlight_below: None,
+            max_value_hint: None,
+            min_value_hint: None,
+            smoothed: false,
+            digits: 4,
+            precision: 2,
+            history: PerfUiHistory::new(300),
+            update_interval: None,
+            sort_key: next_sort_key(),
+        }
+    }
+}
+
+/// Serde support for `PerfUiEntryDiagnostic`, which does not implement `Default`.
+#[cfg(feature = "serde")]
+mod serde_diagnostic {
+    use super::*;
+
+    /// `DiagnosticPath` as a string.
+    pub(super) mod path {
+        use bevy::diagnostic::DiagnosticPath;
+        use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+        pub fn serialize<S: Serializer>(path: &DiagnosticPath, serializer: S) -> Result<S::Ok, S::Error> {
+            path.as_str().serialize(serializer)
+        }
+
+        pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<DiagnosticPath, D::Error> {
+            let path = String::deserialize(deserializer)?;
+            if path.is_empty() || path.split('/').any(|c| c.is_empty()) {
+                return Err(serde::de::Error::custom(format!("invalid diagnostic path: {:?}", path)));
+            }
+            Ok(DiagnosticPath::new(path))
+        }
+    }
+
+    pub(super) fn scale() -> f64 {
+        1.0
+    }
+
+    pub(super) fn digits() -> u8 {
+        4
+    }
+
+    pub(super) fn precision() -> u8 {
+        2
+    }
+
+    pub(super) fn history() -> PerfUiHistory<f64> {
+        PerfUiHistory::new(300)
+    }
+}
+
+impl PerfUiEntry for PerfUiEntryFPS {
+    type SystemParam = SRes<DiagnosticsStore>;
+    type Value = f64;
+
+    fn label(&self) -> &str {
+        if self.label.is_empty() {
+            "FPS"
+        } else {
+            &self.label
+        }
+    }
+    fn update_value(
+        &self,
+        diagnostics: &mut <Self::SystemParam as SystemParam>::Item<'_, '_>,
+    ) -> Option<Self::Value> {
+        Some(if self.smoothed {
+            diagnostics.get(&FrameTimeDiagnosticsPlugin::FPS)?.smoothed()?
+        } else {
+            diagnostics.get(&FrameTimeDiagnosticsPlugin::FPS)?.value()?
+        })
+    }
+    fn format_value(
+        &self,
+        value: &Self::Value,
+    ) -> String {
+        format_pretty_float(self.digits, self.precision, *value)
+    }
+    fn value_color(
+        &self,
+        value: &Self::Value,
+    ) -> Option<Color> {
+        self.color_gradient.get_color_for_value(*value as f32)
+    }
+    fn value_highlight(
+        &self,
+        value: &Self::Value,
+    ) -> bool {
+        self.threshold_highlight
+            .map(|t| (*value as f32) < t)
+            .unwrap_or(false)
+    }
+    fn highlight_threshold(&self, _value: &Self::Value) -> Option<f64> {
+        self.threshold_highlight.map(|t| t as f64)
+    }
+    fn value_as_f64(
+        &self,
+        value: &Self::Value,
+    ) -> Option<f64> {
+        Some(*value)
+    }
+    fn update_interval(&self) -> Option<PerfUiUpdateInterval> {
+        self.update_interval
+    }
+    fn sort_key(&self) -> i32 {
+        self.sort_key
+    }
+}
+
+impl PerfUiEntryDisplayRange for PerfUiEntryFPS {
     fn max_value_hint(&self) -> Option<Self::Value> {
         self.max_value_hint.or(
             match (self.threshold_highlight, self.color_gradient.max_stop()) {
@@ -697,6 +1426,15 @@ impl PerfUiEntryDisplayRange for PerfUiEntryFPS {
     }
 }
 
+impl PerfUiEntryHistory for PerfUiEntryFPS {
+    fn history(&self) -> &PerfUiHistory<Self::Value> {
+        &self.history
+    }
+    fn history_mut(&mut self) -> &mut PerfUiHistory<Self::Value> {
+        &mut self.history
+    }
+}
+
 impl PerfUiEntry for PerfUiEntryFPSWorst {
     type SystemParam = SRes<DiagnosticsStore>;
     type Value = f32;
@@ -710,18 +1448,23 @@ impl PerfUiEntry for PerfUiEntryFPSWorst {
     }
     fn update_value(
         &self,
-        diagnostics: &mut <Self::SystemParam as SystemParam>::Item<'_, '_>,
+        _: &mut <Self::SystemParam as SystemParam>::Item<'_, '_>,
     ) -> Option<Self::Value> {
-        Some(diagnostics.get(&FrameTimeDiagnosticsPlugin::FPS)?
-            .values()
-            .filter_map(|f| if !f.is_nan() {
-                Some(FloatOrd(*f as f32))
-            } else {
-                None
-            })
+        Some(self.history().iter()
+            .map(|f| FloatOrd(*f))
             .min()?.0
         )
     }
+    fn sample(
+        &mut self,
+        diagnostics: &mut <Self::SystemParam as SystemParam>::Item<'_, '_>,
+        now: Duration,
+    ) {
+        sample_diagnostic(self, diagnostics, &FrameTimeDiagnosticsPlugin::FPS, now);
+    }
+    fn clear_samples(&mut self) {
+        self.history.clear();
+    }
     fn format_value(
         &self,
         value: &Self::Value,
@@ -742,6 +1485,18 @@ impl PerfUiEntry for PerfUiEntryFPSWorst {
             .map(|t| *value < t)
             .unwrap_or(false)
     }
+    fn highlight_threshold(&self, _value: &Self::Value) -> Option<f64> {
+        self.threshold_highlight.map(|t| t as f64)
+    }
+    fn value_as_f64(
+        &self,
+        value: &Self::Value,
+    ) -> Option<f64> {
+        Some(*value as f64)
+    }
+    fn update_interval(&self) -> Option<PerfUiUpdateInterval> {
+        self.update_interval
+    }
     fn sort_key(&self) -> i32 {
         self.sort_key
     }
@@ -802,6 +1557,18 @@ impl PerfUiEntry for PerfUiEntryFPSAverage {
             .map(|t| *value < t)
             .unwrap_or(false)
     }
+    fn highlight_threshold(&self, _value: &Self::Value) -> Option<f64> {
+        self.threshold_highlight.map(|t| t as f64)
+    }
+    fn value_as_f64(
+        &self,
+        value: &Self::Value,
+    ) -> Option<f64> {
+        Some(*value as f64)
+    }
+    fn update_interval(&self) -> Option<PerfUiUpdateInterval> {
+        self.update_interval
+    }
     fn sort_key(&self) -> i32 {
         self.sort_key
     }
@@ -836,16 +1603,10 @@ impl PerfUiEntry for PerfUiEntryFPSPctLow {
     }
     fn update_value(
         &self,
-        diagnostics: &mut <Self::SystemParam as SystemParam>::Item<'_, '_>,
+        _: &mut <Self::SystemParam as SystemParam>::Item<'_, '_>,
     ) -> Option<Self::Value> {
-        let mut values: Vec<_> = diagnostics
-            .get(&FrameTimeDiagnosticsPlugin::FPS)?
-            .values()
-            .filter_map(|f| if !f.is_nan() {
-                Some(FloatOrd(*f as f32))
-            } else {
-                None
-            })
+        let mut values: Vec<_> = self.history().iter()
+            .map(|f| FloatOrd(*f))
             .collect();
 
         if values.is_empty() {
@@ -861,6 +1622,16 @@ impl PerfUiEntry for PerfUiEntryFPSPctLow {
         let sum: f32 = values.into_iter().take(bottom_len).map(|fo| fo.0).sum();
         Some(sum / bottom_len as f32)
     }
+    fn sample(
+        &mut self,
+        diagnostics: &mut <Self::SystemParam as SystemParam>::Item<'_, '_>,
+        now: Duration,
+    ) {
+        sample_diagnostic(self, diagnostics, &FrameTimeDiagnosticsPlugin::FPS, now);
+    }
+    fn clear_samples(&mut self) {
+        self.history.clear();
+    }
     fn format_value(
         &self,
         value: &Self::Value,
@@ -881,6 +1652,18 @@ impl PerfUiEntry for PerfUiEntryFPSPctLow {
             .map(|t| *value < t)
             .unwrap_or(false)
     }
+    fn highlight_threshold(&self, _value: &Self::Value) -> Option<f64> {
+        self.threshold_highlight.map(|t| t as f64)
+    }
+    fn value_as_f64(
+        &self,
+        value: &Self::Value,
+    ) -> Option<f64> {
+        Some(*value as f64)
+    }
+    fn update_interval(&self) -> Option<PerfUiUpdateInterval> {
+        self.update_interval
+    }
     fn sort_key(&self) -> i32 {
         self.sort_key
     }
@@ -902,6 +1685,44 @@ impl PerfUiEntryDisplayRange for PerfUiEntryFPSPctLow {
     }
 }
 
+impl PerfUiEntryHistory for PerfUiEntryFPSWorst {
+    fn history(&self) -> &PerfUiHistory<Self::Value> {
+        &self.history
+    }
+    fn history_mut(&mut self) -> &mut PerfUiHistory<Self::Value> {
+        &mut self.history
+    }
+}
+
+impl PerfUiEntryHistory for PerfUiEntryFPSPctLow {
+    fn history(&self) -> &PerfUiHistory<Self::Value> {
+        &self.history
+    }
+    fn history_mut(&mut self) -> &mut PerfUiHistory<Self::Value> {
+        &mut self.history
+    }
+}
+
+/// Record the latest value of a diagnostic into the history of an entry,
+/// for entries that compute their value from recent measurements.
+fn sample_diagnostic<E: PerfUiEntryHistory<Value = f32>>(
+    entry: &mut E,
+    diagnostics: &DiagnosticsStore,
+    path: &DiagnosticPath,
+    now: Duration,
+) {
+    if !entry.history().wants_sample(now) {
+        return;
+    }
+    let Some(value) = diagnostics.get(path)
+        .and_then(|diag| diag.value())
+        .filter(|v| !v.is_nan())
+    else {
+        return;
+    };
+    entry.history_mut().record(now, value as f32);
+}
+
 impl PerfUiEntry for PerfUiEntryFrameTime {
     type SystemParam = SRes<DiagnosticsStore>;
     type Value = f64;
@@ -947,6 +1768,18 @@ impl PerfUiEntry for PerfUiEntryFrameTime {
             .map(|t| (*value as f32) > t)
             .unwrap_or(false)
     }
+    fn highlight_threshold(&self, _value: &Self::Value) -> Option<f64> {
+        self.threshold_highlight.map(|t| t as f64)
+    }
+    fn value_as_f64(
+        &self,
+        value: &Self::Value,
+    ) -> Option<f64> {
+        Some(*value)
+    }
+    fn update_interval(&self) -> Option<PerfUiUpdateInterval> {
+        self.update_interval
+    }
     fn sort_key(&self) -> i32 {
         self.sort_key
     }
@@ -968,6 +1801,15 @@ impl PerfUiEntryDisplayRange for PerfUiEntryFrameTime {
     }
 }
 
+impl PerfUiEntryHistory for PerfUiEntryFrameTime {
+    fn history(&self) -> &PerfUiHistory<Self::Value> {
+        &self.history
+    }
+    fn history_mut(&mut self) -> &mut PerfUiHistory<Self::Value> {
+        &mut self.history
+    }
+}
+
 impl PerfUiEntry for PerfUiEntryFrameTimeWorst {
     type SystemParam = SRes<DiagnosticsStore>;
     type Value = f32;
@@ -981,18 +1823,23 @@ impl PerfUiEntry for PerfUiEntryFrameTimeWorst {
     }
     fn update_value(
         &self,
-        diagnostics: &mut <Self::SystemParam as SystemParam>::Item<'_, '_>,
+        _: &mut <Self::SystemParam as SystemParam>::Item<'_, '_>,
     ) -> Option<Self::Value> {
-        Some(diagnostics.get(&FrameTimeDiagnosticsPlugin::FRAME_TIME)?
-            .values()
-            .filter_map(|f| if !f.is_nan() {
-                Some(FloatOrd(*f as f32))
-            } else {
-                None
-            })
+        Some(self.history().iter()
+            .map(|f| FloatOrd(*f))
             .max()?.0
         )
     }
+    fn sample(
+        &mut self,
+        diagnostics: &mut <Self::SystemParam as SystemParam>::Item<'_, '_>,
+        now: Duration,
+    ) {
+        sample_diagnostic(self, diagnostics, &FrameTimeDiagnosticsPlugin::FRAME_TIME, now);
+    }
+    fn clear_samples(&mut self) {
+        self.history.clear();
+    }
     fn format_value(
         &self,
         value: &Self::Value,
@@ -1017,6 +1864,18 @@ impl PerfUiEntry for PerfUiEntryFrameTimeWorst {
             .map(|t| *value > t)
             .unwrap_or(false)
     }
+    fn highlight_threshold(&self, _value: &Self::Value) -> Option<f64> {
+        self.threshold_highlight.map(|t| t as f64)
+    }
+    fn value_as_f64(
+        &self,
+        value: &Self::Value,
+    ) -> Option<f64> {
+        Some(*value as f64)
+    }
+    fn update_interval(&self) -> Option<PerfUiUpdateInterval> {
+        self.update_interval
+    }
     fn sort_key(&self) -> i32 {
         self.sort_key
     }
@@ -1038,6 +1897,303 @@ impl PerfUiEntryDisplayRange for PerfUiEntryFrameTimeWorst {
     }
 }
 
+impl PerfUiEntryHistory for PerfUiEntryFrameTimeWorst {
+    fn history(&self) -> &PerfUiHistory<Self::Value> {
+        &self.history
+    }
+    fn history_mut(&mut self) -> &mut PerfUiHistory<Self::Value> {
+        &mut self.history
+    }
+}
+
+impl PerfUiEntry for PerfUiEntryFrameTimeJitter {
+    type SystemParam = SRes<DiagnosticsStore>;
+    type Value = f64;
+
+    fn label(&self) -> &str {
+        if self.label.is_empty() {
+            "Frame Jitter"
+        } else {
+            &self.label
+        }
+    }
+    fn update_value(
+        &self,
+        diagnostics: &mut <Self::SystemParam as SystemParam>::Item<'_, '_>,
+    ) -> Option<Self::Value> {
+        let values: Vec<f64> = diagnostics.get(&FrameTimeDiagnosticsPlugin::FRAME_TIME)?
+            .values()
+            .copied()
+            .filter(|f| !f.is_nan())
+            .collect();
+        if values.len() < 2 {
+            return None;
+        }
+        let n = values.len() as f64;
+        Some(match self.method {
+            PerfUiJitterMethod::MeanAbsDelta => {
+                let sum: f64 = values.windows(2)
+                    .map(|w| (w[1] - w[0]).abs())
+                    .sum();
+                sum / (n - 1.0)
+            }
+            PerfUiJitterMethod::StdDev => {
+                let mean = values.iter().sum::<f64>() / n;
+                let variance = values.iter()
+                    .map(|f| (f - mean) * (f - mean))
+                    .sum::<f64>() / n;
+                variance.sqrt()
+            }
+        })
+    }
+    fn format_value(
+        &self,
+        value: &Self::Value,
+    ) -> String {
+        let mut s = format_pretty_float(self.digits, self.precision, *value);
+        if self.display_units {
+            s.push_str(" ms");
+        }
+        s
+    }
+    fn value_color(
+        &self,
+        value: &Self::Value,
+    ) -> Option<Color> {
+        self.color_gradient.get_color_for_value(*value as f32)
+    }
+    fn value_highlight(
+        &self,
+        value: &Self::Value,
+    ) -> bool {
+        self.threshold_highlight
+            .map(|t| (*value as f32) > t)
+            .unwrap_or(false)
+    }
+    fn highlight_threshold(&self, _value: &Self::Value) -> Option<f64> {
+        self.threshold_highlight.map(|t| t as f64)
+    }
+    fn value_as_f64(
+        &self,
+        value: &Self::Value,
+    ) -> Option<f64> {
+        Some(*value)
+    }
+    fn update_interval(&self) -> Option<PerfUiUpdateInterval> {
+        self.update_interval
+    }
+    fn sort_key(&self) -> i32 {
+        self.sort_key
+    }
+}
+
+impl PerfUiEntryDisplayRange for PerfUiEntryFrameTimeJitter {
+    fn max_value_hint(&self) -> Option<Self::Value> {
+        self.max_value_hint.or(
+            match (self.threshold_highlight, self.color_gradient.max_stop()) {
+                (Some(x), None) => Some(x),
+                (None, Some((x, _))) => Some(*x),
+                (Some(a), Some((b, _))) => Some(a.max(*b)),
+                (None, None) => None,
+            }
+        ).map(|v| v as f64)
+    }
+    fn min_value_hint(&self) -> Option<Self::Value> {
+        Some(0.0)
+    }
+}
+
+impl PerfUiEntry for PerfUiEntryFrameTimePercentile {
+    type SystemParam = SRes<Time<Real>>;
+    type Value = f64;
+
+    fn label(&self) -> &str {
+        if !self.label.is_empty() {
+            &self.label
+        } else if !self.samples.auto_label.is_empty() {
+            &self.samples.auto_label
+        } else {
+            "Frame Time (pct)"
+        }
+    }
+    fn update_value(
+        &self,
+        _: &mut <Self::SystemParam as SystemParam>::Item<'_, '_>,
+    ) -> Option<Self::Value> {
+        self.samples.compute(PerfUiStat::Percentile(self.percentile), self.window)
+    }
+    fn sample(
+        &mut self,
+        time: &mut <Self::SystemParam as SystemParam>::Item<'_, '_>,
+        now: Duration,
+    ) {
+        // there is no frame time on the first frame
+        let frame_time = Some(time.delta_secs_f64() * 1000.0)
+            .filter(|t| *t > 0.0);
+        let stat = PerfUiStat::Percentile(self.percentile);
+        if self.samples.record(stat, self.window, now, frame_time) {
+            self.samples.auto_label = format!("Frame Time (p{})", self.percentile);
+        }
+    }
+    fn clear_samples(&mut self) {
+        self.samples.clear();
+    }
+    fn format_value(
+        &self,
+        value: &Self::Value,
+    ) -> String {
+        let mut s = format_pretty_float(self.digits, self.precision, *value);
+        if self.display_units {
+            s.push_str(" ms");
+        }
+        s
+    }
+    fn value_color(
+        &self,
+        value: &Self::Value,
+    ) -> Option<Color> {
+        self.color_gradient.get_color_for_value(*value as f32)
+    }
+    fn value_highlight(
+        &self,
+        value: &Self::Value,
+    ) -> bool {
+        self.threshold_highlight
+            .map(|t| (*value as f32) > t)
+            .unwrap_or(false)
+    }
+    fn highlight_threshold(&self, _value: &Self::Value) -> Option<f64> {
+        self.threshold_highlight.map(|t| t as f64)
+    }
+    fn value_as_f64(
+        &self,
+        value: &Self::Value,
+    ) -> Option<f64> {
+        Some(*value)
+    }
+    fn update_interval(&self) -> Option<PerfUiUpdateInterval> {
+        self.update_interval
+    }
+    fn sort_key(&self) -> i32 {
+        self.sort_key
+    }
+}
+
+impl PerfUiEntryDisplayRange for PerfUiEntryFrameTimePercentile {
+    fn max_value_hint(&self) -> Option<Self::Value> {
+        self.max_value_hint.or(
+            match (self.threshold_highlight, self.color_gradient.max_stop()) {
+                (Some(x), None) => Some(x),
+                (None, Some((x, _))) => Some(*x),
+                (Some(a), Some((b, _))) => Some(a.max(*b)),
+                (None, None) => None,
+            }
+        ).map(|v| v as f64)
+    }
+    fn min_value_hint(&self) -> Option<Self::Value> {
+        Some(0.0)
+    }
+}
+
+impl PerfUiEntry for PerfUiEntryHitches {
+    type SystemParam = SRes<Time<Real>>;
+    type Value = u32;
+
+    fn label(&self) -> &str {
+        if self.label.is_empty() {
+            "Hitches"
+        } else {
+            &self.label
+        }
+    }
+    fn update_value(
+        &self,
+        _: &mut <Self::SystemParam as SystemParam>::Item<'_, '_>,
+    ) -> Option<Self::Value> {
+        Some(self.state.per_minute())
+    }
+    fn sample(
+        &mut self,
+        time: &mut <Self::SystemParam as SystemParam>::Item<'_, '_>,
+        now: Duration,
+    ) {
+        // there is no frame time on the first frame
+        let frame_time = Some(time.delta_secs_f64() * 1000.0)
+            .filter(|t| *t > 0.0);
+        self.state.record(self.threshold, self.median_window, now, frame_time);
+    }
+    fn clear_samples(&mut self) {
+        self.reset();
+    }
+    fn format_value(
+        &self,
+        value: &Self::Value,
+    ) -> String {
+        let mut details = vec![];
+        if self.display_per_minute {
+            details.push(format!("{}/min", value));
+        }
+        if let (true, Some(since_last)) = (self.display_since_last, self.state.since_last()) {
+            let t = format_pretty_time(0, since_last);
+            if since_last.as_secs() < 60 {
+                details.push(format!("{}s ago", t.trim_start()));
+            } else {
+                details.push(format!("{} ago", t.trim_start()));
+            }
+        }
+        if details.is_empty() {
+            format!("{}", self.state.count())
+        } else {
+            format!("{} ({})", self.state.count(), details.join(", "))
+        }
+    }
+    fn value_color(
+        &self,
+        value: &Self::Value,
+    ) -> Option<Color> {
+        self.color_gradient.get_color_for_value(*value as f32)
+    }
+    fn value_highlight(
+        &self,
+        value: &Self::Value,
+    ) -> bool {
+        self.threshold_highlight
+            .map(|t| (*value as f32) > t)
+            .unwrap_or(false)
+    }
+    fn highlight_threshold(&self, _value: &Self::Value) -> Option<f64> {
+        self.threshold_highlight.map(|t| t as f64)
+    }
+    fn value_as_f64(
+        &self,
+        value: &Self::Value,
+    ) -> Option<f64> {
+        Some(*value as f64)
+    }
+    fn update_interval(&self) -> Option<PerfUiUpdateInterval> {
+        self.update_interval
+    }
+    fn sort_key(&self) -> i32 {
+        self.sort_key
+    }
+}
+
+impl PerfUiEntryDisplayRange for PerfUiEntryHitches {
+    fn max_value_hint(&self) -> Option<Self::Value> {
+        self.max_value_hint.or(
+            match (self.threshold_highlight, self.color_gradient.max_stop()) {
+                (Some(x), None) => Some(x.ceil() as u32),
+                (None, Some((x, _))) => Some(x.ceil() as u32),
+                (Some(a), Some((b, _))) => Some(a.max(*b).ceil() as u32),
+                (None, None) => None,
+            }
+        )
+    }
+    fn min_value_hint(&self) -> Option<Self::Value> {
+        Some(0)
+    }
+}
+
 impl PerfUiEntry for PerfUiEntryFrameCount {
     type SystemParam = SRes<DiagnosticsStore>;
     type Value = u32;
@@ -1061,6 +2217,15 @@ impl PerfUiEntry for PerfUiEntryFrameCount {
     ) -> String {
         format_pretty_int(self.digits, *value as i64)
     }
+    fn value_as_f64(
+        &self,
+        value: &Self::Value,
+    ) -> Option<f64> {
+        Some(*value as f64)
+    }
+    fn update_interval(&self) -> Option<PerfUiUpdateInterval> {
+        self.update_interval
+    }
     fn sort_key(&self) -> i32 {
         self.sort_key
     }
@@ -1103,6 +2268,18 @@ impl PerfUiEntry for PerfUiEntryEntityCount {
             .map(|t| *value > t)
             .unwrap_or(false)
     }
+    fn highlight_threshold(&self, _value: &Self::Value) -> Option<f64> {
+        self.threshold_highlight.map(|t| t as f64)
+    }
+    fn value_as_f64(
+        &self,
+        value: &Self::Value,
+    ) -> Option<f64> {
+        Some(*value as f64)
+    }
+    fn update_interval(&self) -> Option<PerfUiUpdateInterval> {
+        self.update_interval
+    }
     fn sort_key(&self) -> i32 {
         self.sort_key
     }
@@ -1124,6 +2301,15 @@ impl PerfUiEntryDisplayRange for PerfUiEntryEntityCount {
     }
 }
 
+impl PerfUiEntryHistory for PerfUiEntryEntityCount {
+    fn history(&self) -> &PerfUiHistory<Self::Value> {
+        &self.history
+    }
+    fn history_mut(&mut self) -> &mut PerfUiHistory<Self::Value> {
+        &mut self.history
+    }
+}
+
 #[cfg(feature = "sysinfo")]
 impl PerfUiEntry for PerfUiEntryCpuUsage {
     type SystemParam = SRes<DiagnosticsStore>;
@@ -1168,6 +2354,18 @@ impl PerfUiEntry for PerfUiEntryCpuUsage {
             .map(|t| (*value as f32) > t)
             .unwrap_or(false)
     }
+    fn highlight_threshold(&self, _value: &Self::Value) -> Option<f64> {
+        self.threshold_highlight.map(|t| t as f64)
+    }
+    fn value_as_f64(
+        &self,
+        value: &Self::Value,
+    ) -> Option<f64> {
+        Some(*value)
+    }
+    fn update_interval(&self) -> Option<PerfUiUpdateInterval> {
+        self.update_interval
+    }
     fn sort_key(&self) -> i32 {
         self.sort_key
     }
@@ -1183,6 +2381,16 @@ impl PerfUiEntryDisplayRange for PerfUiEntryCpuUsage {
     }
 }
 
+#[cfg(feature = "sysinfo")]
+impl PerfUiEntryHistory for PerfUiEntryCpuUsage {
+    fn history(&self) -> &PerfUiHistory<Self::Value> {
+        &self.history
+    }
+    fn history_mut(&mut self) -> &mut PerfUiHistory<Self::Value> {
+        &mut self.history
+    }
+}
+
 #[cfg(feature = "sysinfo")]
 impl PerfUiEntry for PerfUiEntrySystemCpuUsage {
     type SystemParam = SRes<DiagnosticsStore>;
@@ -1227,6 +2435,18 @@ impl PerfUiEntry for PerfUiEntrySystemCpuUsage {
             .map(|t| (*value as f32) > t)
             .unwrap_or(false)
     }
+    fn highlight_threshold(&self, _value: &Self::Value) -> Option<f64> {
+        self.threshold_highlight.map(|t| t as f64)
+    }
+    fn value_as_f64(
+        &self,
+        value: &Self::Value,
+    ) -> Option<f64> {
+        Some(*value)
+    }
+    fn update_interval(&self) -> Option<PerfUiUpdateInterval> {
+        self.update_interval
+    }
     fn sort_key(&self) -> i32 {
         self.sort_key
     }
@@ -1242,6 +2462,16 @@ impl PerfUiEntryDisplayRange for PerfUiEntrySystemCpuUsage {
     }
 }
 
+#[cfg(feature = "sysinfo")]
+impl PerfUiEntryHistory for PerfUiEntrySystemCpuUsage {
+    fn history(&self) -> &PerfUiHistory<Self::Value> {
+        &self.history
+    }
+    fn history_mut(&mut self) -> &mut PerfUiHistory<Self::Value> {
+        &mut self.history
+    }
+}
+
 #[cfg(feature = "sysinfo")]
 impl PerfUiEntry for PerfUiEntryMemUsage {
     type SystemParam = SRes<DiagnosticsStore>;
@@ -1288,6 +2518,18 @@ impl PerfUiEntry for PerfUiEntryMemUsage {
             .map(|t| (*value as f32) > t)
             .unwrap_or(false)
     }
+    fn highlight_threshold(&self, _value: &Self::Value) -> Option<f64> {
+        self.threshold_highlight.map(|t| t as f64)
+    }
+    fn value_as_f64(
+        &self,
+        value: &Self::Value,
+    ) -> Option<f64> {
+        Some(*value)
+    }
+    fn update_interval(&self) -> Option<PerfUiUpdateInterval> {
+        self.update_interval
+    }
     fn sort_key(&self) -> i32 {
         self.sort_key
     }
@@ -1310,6 +2552,16 @@ impl PerfUiEntryDisplayRange for PerfUiEntryMemUsage {
     }
 }
 
+#[cfg(feature = "sysinfo")]
+impl PerfUiEntryHistory for PerfUiEntryMemUsage {
+    fn history(&self) -> &PerfUiHistory<Self::Value> {
+        &self.history
+    }
+    fn history_mut(&mut self) -> &mut PerfUiHistory<Self::Value> {
+        &mut self.history
+    }
+}
+
 #[cfg(feature = "sysinfo")]
 impl PerfUiEntry for PerfUiEntrySystemMemUsage {
     type SystemParam = SRes<DiagnosticsStore>;
@@ -1354,6 +2606,18 @@ impl PerfUiEntry for PerfUiEntrySystemMemUsage {
             .map(|t| (*value as f32) > t)
             .unwrap_or(false)
     }
+    fn highlight_threshold(&self, _value: &Self::Value) -> Option<f64> {
+        self.threshold_highlight.map(|t| t as f64)
+    }
+    fn value_as_f64(
+        &self,
+        value: &Self::Value,
+    ) -> Option<f64> {
+        Some(*value)
+    }
+    fn update_interval(&self) -> Option<PerfUiUpdateInterval> {
+        self.update_interval
+    }
     fn sort_key(&self) -> i32 {
         self.sort_key
     }
@@ -1368,3 +2632,116 @@ impl PerfUiEntryDisplayRange for PerfUiEntrySystemMemUsage {
         Some(0.0)
     }
 }
+
+#[cfg(feature = "sysinfo")]
+impl PerfUiEntryHistory for PerfUiEntrySystemMemUsage {
+    fn history(&self) -> &PerfUiHistory<Self::Value> {
+        &self.history
+    }
+    fn history_mut(&mut self) -> &mut PerfUiHistory<Self::Value> {
+        &mut self.history
+    }
+}
+
+impl PerfUiEntry for PerfUiEntryDiagnostic {
+    type SystemParam = SRes<DiagnosticsStore>;
+    type Value = f64;
+
+    fn label(&self) -> &str {
+        if self.label.is_empty() {
+            self.path.as_str()
+        } else {
+            &self.label
+        }
+    }
+    fn update_value(
+        &self,
+        diagnostics: &mut <Self::SystemParam as SystemParam>::Item<'_, '_>,
+    ) -> Option<Self::Value> {
+        let value = if self.smoothed {
+            diagnostics.get(&self.path)?.smoothed()?
+        } else {
+            diagnostics.get(&self.path)?.value()?
+        };
+        Some(value * self.scale)
+    }
+    fn format_value(
+        &self,
+        value: &Self::Value,
+    ) -> String {
+        let mut s = format_pretty_float(self.digits, self.precision, *value);
+        if !self.unit.is_empty() {
+            s.push(' ');
+            s.push_str(&self.unit);
+        }
+        s
+    }
+    fn value_color(
+        &self,
+        value: &Self::Value,
+    ) -> Option<Color> {
+        self.color_gradient.get_color_for_value(*value as f32)
+    }
+    fn value_highlight(
+        &self,
+        value: &Self::Value,
+    ) -> bool {
+        let above = self.threshold_highlight_above
+            .map(|t| (*value as f32) > t)
+            .unwrap_or(false);
+        let below = self.threshold_highlight_below
+            .map(|t| (*value as f32) < t)
+            .unwrap_or(false);
+        above || below
+    }
+    fn highlight_threshold(&self, value: &Self::Value) -> Option<f64> {
+        // the nearest one, if both are set
+        [self.threshold_highlight_above, self.threshold_highlight_below]
+            .into_iter()
+            .flatten()
+            .map(|t| t as f64)
+            .min_by(|a, b| (a - value).abs().total_cmp(&(b - value).abs()))
+    }
+    fn value_as_f64(
+        &self,
+        value: &Self::Value,
+    ) -> Option<f64> {
+        Some(*value)
+    }
+    fn update_interval(&self) -> Option<PerfUiUpdateInterval> {
+        self.update_interval
+    }
+    fn sort_key(&self) -> i32 {
+        self.sort_key
+    }
+}
+
+impl PerfUiEntryDisplayRange for PerfUiEntryDiagnostic {
+    fn max_value_hint(&self) -> Option<Self::Value> {
+        self.max_value_hint.or(
+            [
+                self.threshold_highlight_above,
+                self.threshold_highlight_below,
+                self.color_gradient.max_stop().map(|(x, _)| *x),
+            ].into_iter().flatten().map(FloatOrd).max().map(|x| x.0)
+        ).map(|v| v as f64)
+    }
+    fn min_value_hint(&self) -> Option<Self::Value> {
+        self.min_value_hint.or(
+            [
+                self.threshold_highlight_above,
+                self.threshold_highlight_below,
+                self.color_gradient.min_stop().map(|(x, _)| *x),
+            ].into_iter().flatten().map(FloatOrd).min().map(|x| x.0)
+        ).map(|v| v as f64)
+    }
+}
+
+impl PerfUiEntryHistory for PerfUiEntryDiagnostic {
+    fn history(&self) -> &PerfUiHistory<Self::Value> {
+        &self.history
+    }
+    fn history_mut(&mut self) -> &mut PerfUiHistory<Self::Value> {
+        &mut self.history
+    }
+}
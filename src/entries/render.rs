@@ -18,8 +18,11 @@ use crate::utils::*;
 /// Better API usage will reduce the value.
 ///
 /// Displays the CPU time in *milliseconds*.
-#[derive(Component, Debug, Clone)]
+#[derive(Component, Debug, Clone, Reflect)]
 #[require(PerfUiRoot)]
+#[reflect(Component, Default, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct PerfUiEntryRenderCpuTime {
     /// Custom label. If empty (default), the default label will be used.
     pub label: String,
@@ -31,7 +34,7 @@ pub struct PerfUiEntryRenderCpuTime {
     ///
     /// To disable (always use default color), set to empty `ColorGradient::default()`.
     ///
-    /// Default: Green-Yellow-Red gradient between the frametimes equivalent to 120-60-30 FPS.
+    /// Default: Green-Yellow-Red gradient (or the default `ColorGradientPreset`, reversed) between the frametimes equivalent to 120-60-30 FPS.
     pub color_gradient: ColorGradient,
     /// Highlight the value if above this threshold.
     ///
@@ -57,6 +60,14 @@ pub struct PerfUiEntryRenderCpuTime {
     ///
     /// Default: `3`
     pub precision: u8,
+    /// History of recent values, for widgets that can display it.
+    ///
+    /// Default: last 300 values, sampled every frame.
+    pub history: PerfUiHistory<f64>,
+    /// Update the displayed value at this interval, instead of the Perf UI's.
+    ///
+    /// Default: `None` (use the `update_interval` of the `PerfUiRoot`)
+    pub update_interval: Option<PerfUiUpdateInterval>,
     /// Sort Key (control where the entry will appear in the Perf UI).
     pub sort_key: i32,
 }
@@ -66,7 +77,7 @@ impl Default for PerfUiEntryRenderCpuTime {
         PerfUiEntryRenderCpuTime {
             label: String::new(),
             display_units: true,
-            color_gradient: ColorGradient::new_preset_gyr(
+            color_gradient: ColorGradient::new_preset_default_reversed(
                 1000.0 / 120.0,
                 1000.0 / 60.0,
                 1000.0 / 30.0,
@@ -76,6 +87,8 @@ impl Default for PerfUiEntryRenderCpuTime {
             smoothed: false,
             digits: 2,
             precision: 3,
+            history: PerfUiHistory::new(300),
+            update_interval: None,
             sort_key: next_sort_key(),
         }
     }
@@ -89,8 +102,11 @@ impl Default for PerfUiEntryRenderCpuTime {
 /// optimizing your shaders and drawing less stuff will make this value go down.
 ///
 /// Displays the GPU time in *milliseconds*.
-#[derive(Component, Debug, Clone)]
+#[derive(Component, Debug, Clone, Reflect)]
 #[require(PerfUiRoot)]
+#[reflect(Component, Default, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct PerfUiEntryRenderGpuTime {
     /// Custom label. If empty (default), the default label will be used.
     pub label: String,
@@ -102,7 +118,7 @@ pub struct PerfUiEntryRenderGpuTime {
     ///
     /// To disable (always use default color), set to empty `ColorGradient::default()`.
     ///
-    /// Default: Green-Yellow-Red gradient between the frametimes equivalent to 120-60-30 FPS.
+    /// Default: Green-Yellow-Red gradient (or the default `ColorGradientPreset`, reversed) between the frametimes equivalent to 120-60-30 FPS.
     pub color_gradient: ColorGradient,
     /// Highlight the value if above this threshold.
     ///
@@ -128,6 +144,14 @@ pub struct PerfUiEntryRenderGpuTime {
     ///
     /// Default: `3`
     pub precision: u8,
+    /// History of recent values, for widgets that can display it.
+    ///
+    /// Default: last 300 values, sampled every frame.
+    pub history: PerfUiHistory<f64>,
+    /// Update the displayed value at this interval, instead of the Perf UI's.
+    ///
+    /// Default: `None` (use the `update_interval` of the `PerfUiRoot`)
+    pub update_interval: Option<PerfUiUpdateInterval>,
     /// Sort Key (control where the entry will appear in the Perf UI).
     pub sort_key: i32,
 }
@@ -137,7 +161,7 @@ impl Default for PerfUiEntryRenderGpuTime {
         PerfUiEntryRenderGpuTime {
             label: String::new(),
             display_units: true,
-            color_gradient: ColorGradient::new_preset_gyr(
+            color_gradient: ColorGradient::new_preset_default_reversed(
                 1000.0 / 120.0,
                 1000.0 / 60.0,
                 1000.0 / 30.0,
@@ -147,6 +171,8 @@ impl Default for PerfUiEntryRenderGpuTime {
             smoothed: false,
             digits: 2,
             precision: 3,
+            history: PerfUiHistory::new(300),
+            update_interval: None,
             sort_key: next_sort_key(),
         }
     }
@@ -212,6 +238,18 @@ impl PerfUiEntry for PerfUiEntryRenderCpuTime {
             .map(|t| (*value as f32) > t)
             .unwrap_or(false)
     }
+    fn highlight_threshold(&self, _value: &Self::Value) -> Option<f64> {
+        self.threshold_highlight.map(|t| t as f64)
+    }
+    fn value_as_f64(
+        &self,
+        value: &Self::Value,
+    ) -> Option<f64> {
+        Some(*value)
+    }
+    fn update_interval(&self) -> Option<PerfUiUpdateInterval> {
+        self.update_interval
+    }
     fn sort_key(&self) -> i32 {
         self.sort_key
     }
@@ -233,6 +271,15 @@ impl PerfUiEntryDisplayRange for PerfUiEntryRenderCpuTime {
     }
 }
 
+impl PerfUiEntryHistory for PerfUiEntryRenderCpuTime {
+    fn history(&self) -> &PerfUiHistory<Self::Value> {
+        &self.history
+    }
+    fn history_mut(&mut self) -> &mut PerfUiHistory<Self::Value> {
+        &mut self.history
+    }
+}
+
 impl PerfUiEntry for PerfUiEntryRenderGpuTime {
     type SystemParam = SRes<DiagnosticsStore>;
     type Value = f64;
@@ -293,6 +340,18 @@ impl PerfUiEntry for PerfUiEntryRenderGpuTime {
             .map(|t| (*value as f32) > t)
             .unwrap_or(false)
     }
+    fn highlight_threshold(&self, _value: &Self::Value) -> Option<f64> {
+        self.threshold_highlight.map(|t| t as f64)
+    }
+    fn value_as_f64(
+        &self,
+        value: &Self::Value,
+    ) -> Option<f64> {
+        Some(*value)
+    }
+    fn update_interval(&self) -> Option<PerfUiUpdateInterval> {
+        self.update_interval
+    }
     fn sort_key(&self) -> i32 {
         self.sort_key
     }
@@ -313,3 +372,272 @@ impl PerfUiEntryDisplayRange for PerfUiEntryRenderGpuTime {
         Some(0.0)
     }
 }
+
+impl PerfUiEntryHistory for PerfUiEntryRenderGpuTime {
+    fn history(&self) -> &PerfUiHistory<Self::Value> {
+        &self.history
+    }
+    fn history_mut(&mut self) -> &mut PerfUiHistory<Self::Value> {
+        &mut self.history
+    }
+}
+
+/// Which time should be measured for each render pass?
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Debug, Default, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PerfUiRenderTimeKind {
+    /// The CPU time (the `render/*/elapsed_cpu` diagnostics).
+    Cpu,
+    /// The GPU time (the `render/*/elapsed_gpu` diagnostics).
+    #[default]
+    Gpu,
+}
+
+/// Perf UI Entry to display the time spent on each render pass separately.
+///
+/// Where [`PerfUiEntryRenderCpuTime`] and [`PerfUiEntryRenderGpuTime`] show
+/// the sum of all the `render/*/elapsed_cpu|gpu` diagnostics, this entry lists
+/// every render pass with its own time, sorted from the most expensive one.
+/// Use it to find out what is to blame, when rendering is slow.
+///
+/// The value as a whole is the total time (the sum of all passes). To see
+/// the individual passes in the UI, display it using
+/// [`PerfUiWidgetTable`](crate::widgets::table::PerfUiWidgetTable).
+///
+/// Displays the times in *milliseconds*.
+#[derive(Component, Debug, Clone, Reflect)]
+#[require(PerfUiRoot)]
+#[reflect(Component, Default, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct PerfUiEntryRenderPasses {
+    /// Custom label. If empty (default), the default label will be used.
+    pub label: String,
+    /// Display the CPU time or the GPU time of the render passes?
+    ///
+    /// Default: `Gpu`
+    pub kind: PerfUiRenderTimeKind,
+    /// Only list the N most expensive render passes.
+    ///
+    /// Default: `None` (list all of them)
+    pub top_n: Option<usize>,
+    /// If `top_n` leaves out some render passes, add their sum as an extra "(other)" part.
+    ///
+    /// Default: `true`
+    pub display_other: bool,
+    /// Display the unit ("ms") alongside the numbers.
+    ///
+    /// Default: `true`
+    pub display_units: bool,
+    /// Enable color based on value. Applies to the total and to each render pass.
+    ///
+    /// To disable (always use default color), set to empty `ColorGradient::default()`.
+    ///
+    /// Default: Green-Yellow-Red gradient (or the default `ColorGradientPreset`, reversed) between the frametimes equivalent to 120-60-30 FPS.
+    pub color_gradient: ColorGradient,
+    /// Highlight the value if above this threshold. Applies to the total and to each render pass.
+    ///
+    /// Default: frametime equivalent to 20 FPS
+    pub threshold_highlight: Option<f32>,
+    /// If displayed using a Stacked Bar (or other similar) widget that can
+    /// show the total within a range, what should its max value be?
+    ///
+    /// If `None`, the value will be computed from the maximum of the
+    /// color gradient and the highlight threshold.
+    ///
+    /// Default: `None`
+    pub max_value_hint: Option<f32>,
+    /// Should we display the smoothed values or the raw values?
+    ///
+    /// The raw values can change the order of the render passes every frame.
+    ///
+    /// Default: true (smoothed)
+    pub smoothed: bool,
+    /// Number of digits to display for the integer (whole number) part.
+    ///
+    /// Default: `2`
+    pub digits: u8,
+    /// Number of digits to display for the fractional (after the decimal point) part.
+    ///
+    /// Default: `3`
+    pub precision: u8,
+    /// Update the displayed value at this interval, instead of the Perf UI's.
+    ///
+    /// Default: `None` (use the `update_interval` of the `PerfUiRoot`)
+    pub update_interval: Option<PerfUiUpdateInterval>,
+    /// Sort Key (control where the entry will appear in the Perf UI).
+    pub sort_key: i32,
+}
+
+impl Default for PerfUiEntryRenderPasses {
+    fn default() -> Self {
+        PerfUiEntryRenderPasses {
+            label: String::new(),
+            kind: PerfUiRenderTimeKind::Gpu,
+            top_n: None,
+            display_other: true,
+            display_units: true,
+            color_gradient: ColorGradient::new_preset_default_reversed(
+                1000.0 / 120.0,
+                1000.0 / 60.0,
+                1000.0 / 30.0,
+            ).unwrap(),
+            threshold_highlight: Some(1000.0 / 20.0),
+            max_value_hint: None,
+            smoothed: true,
+            digits: 2,
+            precision: 3,
+            update_interval: None,
+            sort_key: next_sort_key(),
+        }
+    }
+}
+
+impl PerfUiEntryRenderPasses {
+    fn format_ms(&self, value: f64) -> String {
+        let mut s = format_pretty_float(self.digits, self.precision, value);
+        if self.display_units {
+            s.push_str(" ms");
+        }
+        s
+    }
+}
+
+impl PerfUiEntry for PerfUiEntryRenderPasses {
+    type SystemParam = SRes<DiagnosticsStore>;
+    type Value = PerfUiBreakdown;
+
+    fn label(&self) -> &str {
+        if !self.label.is_empty() {
+            return &self.label;
+        }
+        match self.kind {
+            PerfUiRenderTimeKind::Cpu => "Render Passes (CPU)",
+            PerfUiRenderTimeKind::Gpu => "Render Passes (GPU)",
+        }
+    }
+    fn update_value(
+        &self,
+        diagnostics: &mut <Self::SystemParam as SystemParam>::Item<'_, '_>,
+    ) -> Option<Self::Value> {
+        let suffix = match self.kind {
+            PerfUiRenderTimeKind::Cpu => "/elapsed_cpu",
+            PerfUiRenderTimeKind::Gpu => "/elapsed_gpu",
+        };
+        let mut parts = Vec::new();
+        for diag in diagnostics.iter() {
+            let path = diag.path().as_str();
+            let Some(name) = path.strip_prefix("render/")
+                .and_then(|p| p.strip_suffix(suffix))
+            else {
+                continue;
+            };
+            let value = if self.smoothed {
+                diag.smoothed()
+            } else {
+                diag.value()
+            };
+            if let Some(value) = value {
+                parts.push(PerfUiBreakdownPart {
+                    label: name.to_owned(),
+                    value,
+                });
+            }
+        }
+        if parts.is_empty() {
+            return None;
+        }
+        // most expensive first; by name if equal, for a stable order
+        parts.sort_by(|a, b| {
+            b.value.total_cmp(&a.value)
+                .then_with(|| a.label.cmp(&b.label))
+        });
+        if let Some(n) = self.top_n {
+            if parts.len() > n {
+                let other: f64 = parts.drain(n..).map(|part| part.value).sum();
+                if self.display_other {
+                    parts.push(PerfUiBreakdownPart {
+                        label: "(other)".to_owned(),
+                        value: other,
+                    });
+                }
+            }
+        }
+        Some(PerfUiBreakdown { parts })
+    }
+    fn format_value(
+        &self,
+        value: &Self::Value,
+    ) -> String {
+        self.format_ms(value.total())
+    }
+    fn value_color(
+        &self,
+        value: &Self::Value,
+    ) -> Option<Color> {
+        self.color_gradient.get_color_for_value(value.total() as f32)
+    }
+    fn value_highlight(
+        &self,
+        value: &Self::Value,
+    ) -> bool {
+        self.threshold_highlight
+            .map(|t| (value.total() as f32) > t)
+            .unwrap_or(false)
+    }
+    fn highlight_threshold(&self, _value: &Self::Value) -> Option<f64> {
+        self.threshold_highlight.map(|t| t as f64)
+    }
+    fn value_as_f64(
+        &self,
+        value: &Self::Value,
+    ) -> Option<f64> {
+        Some(value.total())
+    }
+    fn update_interval(&self) -> Option<PerfUiUpdateInterval> {
+        self.update_interval
+    }
+    fn sort_key(&self) -> i32 {
+        self.sort_key
+    }
+}
+
+impl PerfUiEntryBreakdown for PerfUiEntryRenderPasses {
+    fn format_part(
+        &self,
+        part: &PerfUiBreakdownPart,
+    ) -> String {
+        self.format_ms(part.value)
+    }
+    fn part_color(
+        &self,
+        part: &PerfUiBreakdownPart,
+    ) -> Option<Color> {
+        self.color_gradient.get_color_for_value(part.value as f32)
+    }
+    fn part_highlight(
+        &self,
+        part: &PerfUiBreakdownPart,
+    ) -> bool {
+        self.threshold_highlight
+            .map(|t| (part.value as f32) > t)
+            .unwrap_or(false)
+    }
+}
+
+impl PerfUiEntryDisplayRange for PerfUiEntryRenderPasses {
+    fn max_value_hint(&self) -> Option<Self::Value> {
+        self.max_value_hint.or(
+            match (self.threshold_highlight, self.color_gradient.max_stop()) {
+                (Some(x), None) => Some(x),
+                (None, Some((x, _))) => Some(*x),
+                (Some(a), Some((b, _))) => Some(a.max(*b)),
+                (None, None) => None,
+            }
+        ).map(|v| PerfUiBreakdown::from_total(v as f64))
+    }
+    fn min_value_hint(&self) -> Option<Self::Value> {
+        Some(PerfUiBreakdown::from_total(0.0))
+    }
+}
@@ -0,0 +1,589 @@
+//! Perf UI Entries for the CPU time spent in each of Bevy's main schedules
+//!
+//! These require [`PerfUiScheduleTimingPlugin`] to be added to your app.
+
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy::app::{FixedMainScheduleOrder, MainScheduleOrder};
+use bevy::diagnostic::{Diagnostic, DiagnosticPath, Diagnostics, DiagnosticsStore, RegisterDiagnostic};
+use bevy::ecs::schedule::ScheduleLabel;
+use bevy::ecs::system::lifetimeless::SRes;
+use bevy::ecs::system::SystemParam;
+use bevy::platform::time::Instant;
+
+use crate::prelude::*;
+use crate::entry::*;
+use crate::utils::*;
+
+/// Plugin to measure the time spent in each of Bevy's main schedules.
+///
+/// The measurements are made by running a tiny schedule just before and just
+/// after each of the schedules listed in [`PerfUiSchedule`]. The results are
+/// the wall-clock time, on the main thread, from the start to the end of
+/// each schedule, including any time spent waiting for its systems to run
+/// on other threads. `FixedUpdate` can run several times per frame; the
+/// reported time is the sum of all runs during the frame.
+///
+/// The results are published as Bevy diagnostics (see
+/// [`PerfUiSchedule::diagnostic_path`]), every frame, in milliseconds.
+/// They can be displayed using [`PerfUiEntryScheduleTime`] and
+/// [`PerfUiEntryScheduleBreakdown`].
+///
+/// This does not require the `trace` feature or an external profiler.
+pub struct PerfUiScheduleTimingPlugin;
+
+impl PerfUiScheduleTimingPlugin {
+    /// Time spent in `First` (milliseconds).
+    pub const FIRST: DiagnosticPath = DiagnosticPath::const_new("perf_ui/schedule/first");
+    /// Time spent in `PreUpdate` (milliseconds).
+    pub const PRE_UPDATE: DiagnosticPath = DiagnosticPath::const_new("perf_ui/schedule/pre_update");
+    /// Time spent in `Update` (milliseconds).
+    pub const UPDATE: DiagnosticPath = DiagnosticPath::const_new("perf_ui/schedule/update");
+    /// Time spent in `FixedUpdate` (milliseconds).
+    pub const FIXED_UPDATE: DiagnosticPath = DiagnosticPath::const_new("perf_ui/schedule/fixed_update");
+    /// Time spent in `PostUpdate` (milliseconds).
+    pub const POST_UPDATE: DiagnosticPath = DiagnosticPath::const_new("perf_ui/schedule/post_update");
+    /// Time spent in `Last` (milliseconds).
+    pub const LAST: DiagnosticPath = DiagnosticPath::const_new("perf_ui/schedule/last");
+}
+
+impl Plugin for PerfUiScheduleTimingPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PerfUiScheduleTimings>();
+        for schedule in PerfUiSchedule::ALL {
+            app.register_diagnostic(
+                Diagnostic::new(schedule.diagnostic_path())
+                    .with_suffix("ms")
+            );
+            let start = ScheduleTimingMark {
+                schedule,
+                end: false,
+            };
+            let end = ScheduleTimingMark {
+                schedule,
+                end: true,
+            };
+            app.add_systems(start, move |mut timings: ResMut<PerfUiScheduleTimings>| {
+                timings.started[schedule as usize] = Some(Instant::now());
+            });
+            let record_end = move |mut timings: ResMut<PerfUiScheduleTimings>| {
+                let timings = &mut *timings;
+                if let Some(started) = timings.started[schedule as usize].take() {
+                    timings.elapsed[schedule as usize] += started.elapsed();
+                }
+            };
+            if schedule == PerfUiSchedule::Last {
+                // after the end of `Last` has been measured, the frame is complete
+                app.add_systems(end, (record_end, publish_schedule_timings).chain());
+            } else {
+                app.add_systems(end, record_end);
+            }
+            if schedule == PerfUiSchedule::FixedUpdate {
+                let mut order = app.world_mut().resource_mut::<FixedMainScheduleOrder>();
+                order.insert_before(FixedUpdate, start);
+                order.insert_after(FixedUpdate, end);
+            } else {
+                let mut order = app.world_mut().resource_mut::<MainScheduleOrder>();
+                order.insert_before(schedule.label(), start);
+                order.insert_after(schedule.label(), end);
+            }
+        }
+    }
+}
+
+/// The schedules that run just before and after the measured schedules.
+#[derive(ScheduleLabel, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct ScheduleTimingMark {
+    schedule: PerfUiSchedule,
+    end: bool,
+}
+
+/// The measurements for the current frame.
+#[derive(Resource, Default)]
+struct PerfUiScheduleTimings {
+    started: [Option<Instant>; PerfUiSchedule::ALL.len()],
+    elapsed: [Duration; PerfUiSchedule::ALL.len()],
+}
+
+fn publish_schedule_timings(
+    mut timings: ResMut<PerfUiScheduleTimings>,
+    mut diagnostics: Diagnostics,
+) {
+    for schedule in PerfUiSchedule::ALL {
+        let elapsed = std::mem::take(&mut timings.elapsed[schedule as usize]);
+        diagnostics.add_measurement(&schedule.diagnostic_path(), || {
+            elapsed.as_secs_f64() * 1000.0
+        });
+    }
+}
+
+/// One of Bevy's main schedules, measured by [`PerfUiScheduleTimingPlugin`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+#[reflect(Debug, Default, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PerfUiSchedule {
+    /// The [`First`] schedule.
+    First,
+    /// The [`PreUpdate`] schedule.
+    PreUpdate,
+    /// The [`Update`] schedule.
+    #[default]
+    Update,
+    /// The [`FixedUpdate`] schedule (all runs during the frame).
+    FixedUpdate,
+    /// The [`PostUpdate`] schedule.
+    PostUpdate,
+    /// The [`Last`] schedule.
+    Last,
+}
+
+impl PerfUiSchedule {
+    /// All the measured schedules, in the order they run.
+    pub const ALL: [PerfUiSchedule; 6] = [
+        PerfUiSchedule::First,
+        PerfUiSchedule::PreUpdate,
+        PerfUiSchedule::Update,
+        PerfUiSchedule::FixedUpdate,
+        PerfUiSchedule::PostUpdate,
+        PerfUiSchedule::Last,
+    ];
+
+    /// The name of the schedule.
+    pub fn name(self) -> &'static str {
+        match self {
+            PerfUiSchedule::First => "First",
+            PerfUiSchedule::PreUpdate => "PreUpdate",
+            PerfUiSchedule::Update => "Update",
+            PerfUiSchedule::FixedUpdate => "FixedUpdate",
+            PerfUiSchedule::PostUpdate => "PostUpdate",
+            PerfUiSchedule::Last => "Last",
+        }
+    }
+
+    /// The path of the diagnostic with the time spent in the schedule.
+    pub fn diagnostic_path(self) -> DiagnosticPath {
+        match self {
+            PerfUiSchedule::First => PerfUiScheduleTimingPlugin::FIRST,
+            PerfUiSchedule::PreUpdate => PerfUiScheduleTimingPlugin::PRE_UPDATE,
+            PerfUiSchedule::Update => PerfUiScheduleTimingPlugin::UPDATE,
+            PerfUiSchedule::FixedUpdate => PerfUiScheduleTimingPlugin::FIXED_UPDATE,
+            PerfUiSchedule::PostUpdate => PerfUiScheduleTimingPlugin::POST_UPDATE,
+            PerfUiSchedule::Last => PerfUiScheduleTimingPlugin::LAST,
+        }
+    }
+
+    fn label(self) -> bevy::ecs::schedule::InternedScheduleLabel {
+        match self {
+            PerfUiSchedule::First => First.intern(),
+            PerfUiSchedule::PreUpdate => PreUpdate.intern(),
+            PerfUiSchedule::Update => Update.intern(),
+            PerfUiSchedule::FixedUpdate => FixedUpdate.intern(),
+            PerfUiSchedule::PostUpdate => PostUpdate.intern(),
+            PerfUiSchedule::Last => Last.intern(),
+        }
+    }
+}
+
+/// Perf UI Entry to display the time spent in one of Bevy's main schedules.
+///
+/// Requires [`PerfUiScheduleTimingPlugin`].
+///
+/// To display several schedules, spawn additional entries as child entities
+/// of your Perf UI, or use [`PerfUiEntryScheduleBreakdown`].
+///
+/// Displays the time in *milliseconds*.
+#[derive(Component, Debug, Clone, Reflect)]
+#[require(PerfUiRoot)]
+#[reflect(Component, Default, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct PerfUiEntryScheduleTime {
+    /// Custom label. If empty (default), the default label will be used.
+    pub label: String,
+    /// Which schedule to display.
+    ///
+    /// Default: `Update`
+    pub schedule: PerfUiSchedule,
+    /// Display the unit ("ms") alongside the number.
+    ///
+    /// Default: `true`
+    pub display_units: bool,
+    /// Enable color based on value.
+    ///
+    /// To disable (always use default color), set to empty `ColorGradient::default()`.
+    ///
+    /// Default: Green-Yellow-Red gradient (or the default `ColorGradientPreset`, reversed) between the frametimes equivalent to 120-60-30 FPS.
+    pub color_gradient: ColorGradient,
+    /// Highlight the value if above this threshold.
+    ///
+    /// Default: frametime equivalent to 20 FPS
+    pub threshold_highlight: Option<f32>,
+    /// If displayed using a Bar (or other similar) widget that can
+    /// show the value within a range, what should its max value be?
+    ///
+    /// If `None`, the value will be computed from the maximum of the
+    /// color gradient and the highlight threshold.
+    ///
+    /// Default: `None`
+    pub max_value_hint: Option<f32>,
+    /// Should we display the smoothed value or the raw value?
+    ///
+    /// Default: false (raw)
+    pub smoothed: bool,
+    /// Number of digits to display for the integer (whole number) part.
+    ///
+    /// Default: `2`
+    pub digits: u8,
+    /// Number of digits to display for the fractional (after the decimal point) part.
+    ///
+    /// Default: `3`
+    pub precision: u8,
+    /// History of recent values, for widgets that can display it.
+    ///
+    /// Default: last 300 values, sampled every frame.
+    pub history: PerfUiHistory<f64>,
+    /// Update the displayed value at this interval, instead of the Perf UI's.
+    ///
+    /// Default: `None` (use the `update_interval` of the `PerfUiRoot`)
+    pub update_interval: Option<PerfUiUpdateInterval>,
+    /// Sort Key (control where the entry will appear in the Perf UI).
+    pub sort_key: i32,
+}
+
+impl Default for PerfUiEntryScheduleTime {
+    fn default() -> Self {
+        PerfUiEntryScheduleTime {
+            label: String::new(),
+            schedule: PerfUiSchedule::Update,
+            display_units: true,
+            color_gradient: ColorGradient::new_preset_default_reversed(
+                1000.0 / 120.0,
+                1000.0 / 60.0,
+                1000.0 / 30.0,
+            ).unwrap(),
+            threshold_highlight: Some(1000.0 / 20.0),
+            max_value_hint: None,
+            smoothed: false,
+            digits: 2,
+            precision: 3,
+            history: PerfUiHistory::new(300),
+            update_interval: None,
+            sort_key: next_sort_key(),
+        }
+    }
+}
+
+impl PerfUiEntryScheduleTime {
+    /// Create a new entry for the given schedule, with default settings
+    pub fn new(schedule: PerfUiSchedule) -> Self {
+        PerfUiEntryScheduleTime {
+            schedule,
+            ..default()
+        }
+    }
+}
+
+impl PerfUiEntry for PerfUiEntryScheduleTime {
+    type SystemParam = SRes<DiagnosticsStore>;
+    type Value = f64;
+
+    fn label(&self) -> &str {
+        if !self.label.is_empty() {
+            return &self.label;
+        }
+        match self.schedule {
+            PerfUiSchedule::First => "First Time",
+            PerfUiSchedule::PreUpdate => "PreUpdate Time",
+            PerfUiSchedule::Update => "Update Time",
+            PerfUiSchedule::FixedUpdate => "FixedUpdate Time",
+            PerfUiSchedule::PostUpdate => "PostUpdate Time",
+            PerfUiSchedule::Last => "Last Time",
+        }
+    }
+    fn update_value(
+        &self,
+        diagnostics: &mut <Self::SystemParam as SystemParam>::Item<'_, '_>,
+    ) -> Option<Self::Value> {
+        let diag = diagnostics.get(&self.schedule.diagnostic_path())?;
+        if self.smoothed {
+            diag.smoothed()
+        } else {
+            diag.value()
+        }
+    }
+    fn format_value(
+        &self,
+        value: &Self::Value,
+    ) -> String {
+        let mut s = format_pretty_float(self.digits, self.precision, *value);
+        if self.display_units {
+            s.push_str(" ms");
+        }
+        s
+    }
+    fn value_color(
+        &self,
+        value: &Self::Value,
+    ) -> Option<Color> {
+        self.color_gradient.get_color_for_value(*value as f32)
+    }
+    fn value_highlight(
+        &self,
+        value: &Self::Value,
+    ) -> bool {
+        self.threshold_highlight
+            .map(|t| (*value as f32) > t)
+            .unwrap_or(false)
+    }
+    fn highlight_threshold(&self, _value: &Self::Value) -> Option<f64> {
+        self.threshold_highlight.map(|t| t as f64)
+    }
+    fn value_as_f64(
+        &self,
+        value: &Self::Value,
+    ) -> Option<f64> {
+        Some(*value)
+    }
+    fn update_interval(&self) -> Option<PerfUiUpdateInterval> {
+        self.update_interval
+    }
+    fn sort_key(&self) -> i32 {
+        self.sort_key
+    }
+}
+
+impl PerfUiEntryDisplayRange for PerfUiEntryScheduleTime {
+    fn max_value_hint(&self) -> Option<Self::Value> {
+        self.max_value_hint.or(
+            match (self.threshold_highlight, self.color_gradient.max_stop()) {
+                (Some(x), None) => Some(x),
+                (None, Some((x, _))) => Some(*x),
+                (Some(a), Some((b, _))) => Some(a.max(*b)),
+                (None, None) => None,
+            }
+        ).map(|v| v as f64)
+    }
+    fn min_value_hint(&self) -> Option<Self::Value> {
+        Some(0.0)
+    }
+}
+
+impl PerfUiEntryHistory for PerfUiEntryScheduleTime {
+    fn history(&self) -> &PerfUiHistory<Self::Value> {
+        &self.history
+    }
+    fn history_mut(&mut self) -> &mut PerfUiHistory<Self::Value> {
+        &mut self.history
+    }
+}
+
+/// Perf UI Entry to display the time spent in each of Bevy's main schedules.
+///
+/// Requires [`PerfUiScheduleTimingPlugin`].
+///
+/// The value as a whole is the total time (the sum of all schedules). The
+/// schedules are listed in the order they run. To see the individual
+/// schedules in the UI, display it using
+/// [`PerfUiWidgetTable`](crate::widgets::table::PerfUiWidgetTable) or
+/// [`PerfUiWidgetStackedBar`](crate::widgets::stacked_bar::PerfUiWidgetStackedBar).
+///
+/// Displays the times in *milliseconds*.
+#[derive(Component, Debug, Clone, Reflect)]
+#[require(PerfUiRoot)]
+#[reflect(Component, Default, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct PerfUiEntryScheduleBreakdown {
+    /// Custom label. If empty (default), the default label will be used.
+    pub label: String,
+    /// Which schedules to list.
+    ///
+    /// Default: all of them
+    pub schedules: Vec<PerfUiSchedule>,
+    /// Display the unit ("ms") alongside the numbers.
+    ///
+    /// Default: `true`
+    pub display_units: bool,
+    /// Enable color based on value. Applies to the total and to each schedule.
+    ///
+    /// To disable (always use default color), set to empty `ColorGradient::default()`.
+    ///
+    /// Default: Green-Yellow-Red gradient (or the default `ColorGradientPreset`, reversed) between the frametimes equivalent to 120-60-30 FPS.
+    pub color_gradient: ColorGradient,
+    /// Highlight the value if above this threshold. Applies to the total and to each schedule.
+    ///
+    /// Default: frametime equivalent to 20 FPS
+    pub threshold_highlight: Option<f32>,
+    /// If displayed using a Stacked Bar (or other similar) widget that can
+    /// show the total within a range, what should its max value be?
+    ///
+    /// If `None`, the value will be computed from the maximum of the
+    /// color gradient and the highlight threshold.
+    ///
+    /// Default: `None`
+    pub max_value_hint: Option<f32>,
+    /// Should we display the smoothed values or the raw values?
+    ///
+    /// Default: false (raw)
+    pub smoothed: bool,
+    /// Number of digits to display for the integer (whole number) part.
+    ///
+    /// Default: `2`
+    pub digits: u8,
+    /// Number of digits to display for the fractional (after the decimal point) part.
+    ///
+    /// Default: `3`
+    pub precision: u8,
+    /// Update the displayed value at this interval, instead of the Perf UI's.
+    ///
+    /// Default: `None` (use the `update_interval` of the `PerfUiRoot`)
+    pub update_interval: Option<PerfUiUpdateInterval>,
+    /// Sort Key (control where the entry will appear in the Perf UI).
+    pub sort_key: i32,
+}
+
+impl Default for PerfUiEntryScheduleBreakdown {
+    fn default() -> Self {
+        PerfUiEntryScheduleBreakdown {
+            label: String::new(),
+            schedules: PerfUiSchedule::ALL.to_vec(),
+            display_units: true,
+            color_gradient: ColorGradient::new_preset_default_reversed(
+                1000.0 / 120.0,
+                1000.0 / 60.0,
+                1000.0 / 30.0,
+            ).unwrap(),
+            threshold_highlight: Some(1000.0 / 20.0),
+            max_value_hint: None,
+            smoothed: false,
+            digits: 2,
+            precision: 3,
+            update_interval: None,
+            sort_key: next_sort_key(),
+        }
+    }
+}
+
+impl PerfUiEntryScheduleBreakdown {
+    fn format_ms(&self, value: f64) -> String {
+        let mut s = format_pretty_float(self.digits, self.precision, value);
+        if self.display_units {
+            s.push_str(" ms");
+        }
+        s
+    }
+}
+
+impl PerfUiEntry for PerfUiEntryScheduleBreakdown {
+    type SystemParam = SRes<DiagnosticsStore>;
+    type Value = PerfUiBreakdown;
+
+    fn label(&self) -> &str {
+        if self.label.is_empty() {
+            "Schedules"
+        } else {
+            &self.label
+        }
+    }
+    fn update_value(
+        &self,
+        diagnostics: &mut <Self::SystemParam as SystemParam>::Item<'_, '_>,
+    ) -> Option<Self::Value> {
+        let mut parts = Vec::with_capacity(self.schedules.len());
+        for schedule in &self.schedules {
+            let Some(diag) = diagnostics.get(&schedule.diagnostic_path()) else {
+                continue;
+            };
+            let value = if self.smoothed {
+                diag.smoothed()
+            } else {
+                diag.value()
+            };
+            if let Some(value) = value {
+                parts.push(PerfUiBreakdownPart {
+                    label: schedule.name().to_owned(),
+                    value,
+                });
+            }
+        }
+        if parts.is_empty() {
+            return None;
+        }
+        Some(PerfUiBreakdown { parts })
+    }
+    fn format_value(
+        &self,
+        value: &Self::Value,
+    ) -> String {
+        self.format_ms(value.total())
+    }
+    fn value_color(
+        &self,
+        value: &Self::Value,
+    ) -> Option<Color> {
+        self.color_gradient.get_color_for_value(value.total() as f32)
+    }
+    fn value_highlight(
+        &self,
+        value: &Self::Value,
+    ) -> bool {
+        self.threshold_highlight
+            .map(|t| (value.total() as f32) > t)
+            .unwrap_or(false)
+    }
+    fn highlight_threshold(&self, _value: &Self::Value) -> Option<f64> {
+        self.threshold_highlight.map(|t| t as f64)
+    }
+    fn value_as_f64(
+        &self,
+        value: &Self::Value,
+    ) -> Option<f64> {
+        Some(value.total())
+    }
+    fn update_interval(&self) -> Option<PerfUiUpdateInterval> {
+        self.update_interval
+    }
+    fn sort_key(&self) -> i32 {
+        self.sort_key
+    }
+}
+
+impl PerfUiEntryBreakdown for PerfUiEntryScheduleBreakdown {
+    fn format_part(
+        &self,
+        part: &PerfUiBreakdownPart,
+    ) -> String {
+        self.format_ms(part.value)
+    }
+    fn part_color(
+        &self,
+        part: &PerfUiBreakdownPart,
+    ) -> Option<Color> {
+        self.color_gradient.get_color_for_value(part.value as f32)
+    }
+    fn part_highlight(
+        &self,
+        part: &PerfUiBreakdownPart,
+    ) -> bool {
+        self.threshold_highlight
+            .map(|t| (part.value as f32) > t)
+            .unwrap_or(false)
+    }
+}
+
+impl PerfUiEntryDisplayRange for PerfUiEntryScheduleBreakdown {
+    fn max_value_hint(&self) -> Option<Self::Value> {
+        self.max_value_hint.or(
+            match (self.threshold_highlight, self.color_gradient.max_stop()) {
+                (Some(x), None) => Some(x),
+                (None, Some((x, _))) => Some(*x),
+                (Some(a), Some((b, _))) => Some(a.max(*b)),
+                (None, None) => None,
+            }
+        ).map(|v| PerfUiBreakdown::from_total(v as f64))
+    }
+    fn min_value_hint(&self) -> Option<Self::Value> {
+        Some(PerfUiBreakdown::from_total(0.0))
+    }
+}
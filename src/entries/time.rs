@@ -10,8 +10,11 @@ use crate::entry::*;
 use crate::utils::*;
 
 /// Perf UI Entry to display the time the Bevy app has been running.
-#[derive(Component, Debug, Clone)]
+#[derive(Component, Debug, Clone, Reflect)]
 #[require(PerfUiRoot)]
+#[reflect(Component, Default, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct PerfUiEntryRunningTime {
     /// Custom label. If empty (default), the default label will be used.
     pub label: String,
@@ -42,6 +45,10 @@ pub struct PerfUiEntryRunningTime {
     ///
     /// Default: `3`
     pub precision: u8,
+    /// Update the displayed value at this interval, instead of the Perf UI's.
+    ///
+    /// Default: `None` (use the `update_interval` of the `PerfUiRoot`)
+    pub update_interval: Option<PerfUiUpdateInterval>,
     /// Sort Key (control where the entry will appear in the Perf UI).
     pub sort_key: i32,
 }
@@ -55,6 +62,7 @@ impl Default for PerfUiEntryRunningTime {
             display_units: true,
             digits: 5,
             precision: 3,
+            update_interval: None,
             sort_key: next_sort_key(),
         }
     }
@@ -64,8 +72,11 @@ impl Default for PerfUiEntryRunningTime {
 ///
 /// This time is in UTC, unless you enable the optional `chrono` dependency on
 /// this crate. If `chrono` is enabled, it will be in local time.
-#[derive(Component, Debug, Clone)]
+#[derive(Component, Debug, Clone, Reflect)]
 #[require(PerfUiRoot)]
+#[reflect(Component, Default, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct PerfUiEntryClock {
     /// Custom label. If empty (default), the default label will be used.
     pub label: String,
@@ -80,6 +91,10 @@ pub struct PerfUiEntryClock {
     ///
     /// Default: `0`
     pub precision: u8,
+    /// Update the displayed value at this interval, instead of the Perf UI's.
+    ///
+    /// Default: `None` (use the `update_interval` of the `PerfUiRoot`)
+    pub update_interval: Option<PerfUiUpdateInterval>,
     /// Sort Key (control where the entry will appear in the Perf UI).
     pub sort_key: i32,
 }
@@ -90,14 +105,18 @@ impl Default for PerfUiEntryClock {
             label: String::new(),
             prefer_utc: false,
             precision: 0,
+            update_interval: None,
             sort_key: next_sort_key(),
         }
     }
 }
 
 /// Perf UI Entry to display Bevy's Fixed Time Step duration.
-#[derive(Component, Debug, Clone)]
+#[derive(Component, Debug, Clone, Reflect)]
 #[require(PerfUiRoot)]
+#[reflect(Component, Default, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct PerfUiEntryFixedTimeStep {
     /// Custom label. If empty (default), the default label will be used.
     pub label: String,
@@ -117,6 +136,10 @@ pub struct PerfUiEntryFixedTimeStep {
     ///
     /// Default: `2`
     pub precision: u8,
+    /// Update the displayed value at this interval, instead of the Perf UI's.
+    ///
+    /// Default: `None` (use the `update_interval` of the `PerfUiRoot`)
+    pub update_interval: Option<PerfUiUpdateInterval>,
     /// Sort Key (control where the entry will appear in the Perf UI).
     pub sort_key: i32,
 }
@@ -129,14 +152,18 @@ impl Default for PerfUiEntryFixedTimeStep {
             as_hz: true,
             digits: 3,
             precision: 2,
+            update_interval: None,
             sort_key: next_sort_key(),
         }
     }
 }
 
 /// Perf UI Entry to display Bevy's Fixed Time overstep.
-#[derive(Component, Debug, Clone)]
+#[derive(Component, Debug, Clone, Reflect)]
 #[require(PerfUiRoot)]
+#[reflect(Component, Default, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct PerfUiEntryFixedOverstep {
     /// Custom label. If empty (default), the default label will be used.
     pub label: String,
@@ -156,6 +183,14 @@ pub struct PerfUiEntryFixedOverstep {
     ///
     /// Default: `3`
     pub precision: u8,
+    /// History of recent values, for widgets that can display it.
+    ///
+    /// Default: last 300 values, sampled every frame.
+    pub history: PerfUiHistory<f64>,
+    /// Update the displayed value at this interval, instead of the Perf UI's.
+    ///
+    /// Default: `None` (use the `update_interval` of the `PerfUiRoot`)
+    pub update_interval: Option<PerfUiUpdateInterval>,
     /// Sort Key (control where the entry will appear in the Perf UI).
     pub sort_key: i32,
 }
@@ -168,6 +203,8 @@ impl Default for PerfUiEntryFixedOverstep {
             as_percent: true,
             digits: 3,
             precision: 2,
+            history: PerfUiHistory::new(300),
+            update_interval: None,
             sort_key: next_sort_key(),
         }
     }
@@ -184,6 +221,15 @@ impl PerfUiEntry for PerfUiEntryRunningTime {
             &self.label
         }
     }
+    fn value_as_f64(
+        &self,
+        value: &Self::Value,
+    ) -> Option<f64> {
+        Some(value.as_secs_f64())
+    }
+    fn update_interval(&self) -> Option<PerfUiUpdateInterval> {
+        self.update_interval
+    }
     fn sort_key(&self) -> i32 {
         self.sort_key
     }
@@ -230,6 +276,9 @@ impl PerfUiEntry for PerfUiEntryClock {
             &self.label
         }
     }
+    fn update_interval(&self) -> Option<PerfUiUpdateInterval> {
+        self.update_interval
+    }
     fn sort_key(&self) -> i32 {
         self.sort_key
     }
@@ -263,6 +312,15 @@ impl PerfUiEntry for PerfUiEntryFixedTimeStep {
             &self.label
         }
     }
+    fn value_as_f64(
+        &self,
+        value: &Self::Value,
+    ) -> Option<f64> {
+        Some(value.as_secs_f64())
+    }
+    fn update_interval(&self) -> Option<PerfUiUpdateInterval> {
+        self.update_interval
+    }
     fn sort_key(&self) -> i32 {
         self.sort_key
     }
@@ -300,6 +358,15 @@ impl PerfUiEntry for PerfUiEntryFixedOverstep {
             &self.label
         }
     }
+    fn value_as_f64(
+        &self,
+        value: &Self::Value,
+    ) -> Option<f64> {
+        Some(*value)
+    }
+    fn update_interval(&self) -> Option<PerfUiUpdateInterval> {
+        self.update_interval
+    }
     fn sort_key(&self) -> i32 {
         self.sort_key
     }
@@ -336,6 +403,15 @@ impl PerfUiEntryDisplayRange for PerfUiEntryFixedOverstep {
     }
 }
 
+impl PerfUiEntryHistory for PerfUiEntryFixedOverstep {
+    fn history(&self) -> &PerfUiHistory<Self::Value> {
+        &self.history
+    }
+    fn history_mut(&mut self) -> &mut PerfUiHistory<Self::Value> {
+        &mut self.history
+    }
+}
+
 #[cfg(feature = "chrono")]
 fn get_system_clock_local() -> Option<(u32, u32, u32, u32)> {
     use chrono::Timelike;
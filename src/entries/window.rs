@@ -12,8 +12,11 @@ use crate::entry::*;
 use crate::utils::*;
 
 /// Perf UI Entry to display the window mode (windowed, fullscreen, etc).
-#[derive(Component, Debug, Clone)]
+#[derive(Component, Debug, Clone, Reflect)]
 #[require(PerfUiRoot)]
+#[reflect(Component, Default, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct PerfUiEntryWindowMode {
     /// Custom label. If empty (default), the default label will be used.
     pub label: String,
@@ -21,6 +24,10 @@ pub struct PerfUiEntryWindowMode {
     ///
     /// If `None` (the default), the primary window is selected.
     pub window: Option<Entity>,
+    /// Update the displayed value at this interval, instead of the Perf UI's.
+    ///
+    /// Default: `None` (use the `update_interval` of the `PerfUiRoot`)
+    pub update_interval: Option<PerfUiUpdateInterval>,
     /// Sort Key (control where the entry will appear in the Perf UI).
     pub sort_key: i32,
 }
@@ -30,14 +37,18 @@ impl Default for PerfUiEntryWindowMode {
         PerfUiEntryWindowMode {
             label: String::new(),
             window: None,
+            update_interval: None,
             sort_key: next_sort_key(),
         }
     }
 }
 
 /// Perf UI Entry to display the window present mode (vsync).
-#[derive(Component, Debug, Clone)]
+#[derive(Component, Debug, Clone, Reflect)]
 #[require(PerfUiRoot)]
+#[reflect(Component, Default, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct PerfUiEntryWindowPresentMode {
     /// Custom label. If empty (default), the default label will be used.
     pub label: String,
@@ -45,6 +56,10 @@ pub struct PerfUiEntryWindowPresentMode {
     ///
     /// If `None` (the default), the primary window is selected.
     pub window: Option<Entity>,
+    /// Update the displayed value at this interval, instead of the Perf UI's.
+    ///
+    /// Default: `None` (use the `update_interval` of the `PerfUiRoot`)
+    pub update_interval: Option<PerfUiUpdateInterval>,
     /// Sort Key (control where the entry will appear in the Perf UI).
     pub sort_key: i32,
 }
@@ -54,14 +69,18 @@ impl Default for PerfUiEntryWindowPresentMode {
         PerfUiEntryWindowPresentMode {
             label: String::new(),
             window: None,
+            update_interval: None,
             sort_key: next_sort_key(),
         }
     }
 }
 
 /// Perf UI Entry to display the window size / resolution.
-#[derive(Component, Debug, Clone)]
+#[derive(Component, Debug, Clone, Reflect)]
 #[require(PerfUiRoot)]
+#[reflect(Component, Default, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct PerfUiEntryWindowScaleFactor {
     /// Custom label. If empty (default), the default label will be used.
     pub label: String,
@@ -77,6 +96,10 @@ pub struct PerfUiEntryWindowScaleFactor {
     ///
     /// Default: `2`
     pub precision: u8,
+    /// Update the displayed value at this interval, instead of the Perf UI's.
+    ///
+    /// Default: `None` (use the `update_interval` of the `PerfUiRoot`)
+    pub update_interval: Option<PerfUiUpdateInterval>,
     /// Sort Key (control where the entry will appear in the Perf UI).
     pub sort_key: i32,
 }
@@ -88,20 +111,26 @@ impl Default for PerfUiEntryWindowScaleFactor {
             window: None,
             digits: 2,
             precision: 2,
+            update_interval: None,
             sort_key: next_sort_key(),
         }
     }
 }
 
 /// Perf UI Entry to display the window size / resolution.
-#[derive(Component, Debug, Clone)]
+#[derive(Component, Debug, Clone, Reflect)]
 #[require(PerfUiRoot)]
+#[reflect(Component, Default, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct PerfUiEntryWindowResolution {
     /// Custom label. If empty (default), the default label will be used.
     pub label: String,
     /// Separate the X and Y values by this string.
     ///
     /// Default: `"x"`.
+    #[reflect(ignore)]
+    #[cfg_attr(feature = "serde", serde(skip_deserializing))]
     pub separator: &'static str,
     /// Display the unit ("px") alongside the numbers.
     ///
@@ -127,6 +156,10 @@ pub struct PerfUiEntryWindowResolution {
     ///
     /// Default: `8` (assuming common up to 4-digit resolutions, precision = 0)
     pub width: u8,
+    /// Update the displayed value at this interval, instead of the Perf UI's.
+    ///
+    /// Default: `None` (use the `update_interval` of the `PerfUiRoot`)
+    pub update_interval: Option<PerfUiUpdateInterval>,
     /// Sort Key (control where the entry will appear in the Perf UI).
     pub sort_key: i32,
 }
@@ -142,20 +175,26 @@ impl Default for PerfUiEntryWindowResolution {
             window: None,
             precision: 0,
             width: 8,
+            update_interval: None,
             sort_key: next_sort_key(),
         }
     }
 }
 
 /// Perf UI Entry to display the current coordinates of the mouse cursor.
-#[derive(Component, Debug, Clone)]
+#[derive(Component, Debug, Clone, Reflect)]
 #[require(PerfUiRoot)]
+#[reflect(Component, Default, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct PerfUiEntryCursorPosition {
     /// Custom label. If empty (default), the default label will be used.
     pub label: String,
     /// Separate the X and Y values by this string.
     ///
     /// Default: `", "`.
+    #[reflect(ignore)]
+    #[cfg_attr(feature = "serde", serde(skip_deserializing))]
     pub separator: &'static str,
     /// Display the unit ("px") alongside the numbers.
     ///
@@ -181,6 +220,10 @@ pub struct PerfUiEntryCursorPosition {
     ///
     /// Default: `8` (assuming common up to 4-digit resolutions, precision = 0)
     pub width: u8,
+    /// Update the displayed value at this interval, instead of the Perf UI's.
+    ///
+    /// Default: `None` (use the `update_interval` of the `PerfUiRoot`)
+    pub update_interval: Option<PerfUiUpdateInterval>,
     /// Sort Key (control where the entry will appear in the Perf UI).
     pub sort_key: i32,
 }
@@ -196,6 +239,7 @@ impl Default for PerfUiEntryCursorPosition {
             window: None,
             precision: 0,
             width: 8,
+            update_interval: None,
             sort_key: next_sort_key(),
         }
     }
@@ -215,6 +259,9 @@ impl PerfUiEntry for PerfUiEntryWindowMode {
             &self.label
         }
     }
+    fn update_interval(&self) -> Option<PerfUiUpdateInterval> {
+        self.update_interval
+    }
     fn sort_key(&self) -> i32 {
         self.sort_key
     }
@@ -244,6 +291,9 @@ impl PerfUiEntry for PerfUiEntryWindowPresentMode {
             &self.label
         }
     }
+    fn update_interval(&self) -> Option<PerfUiUpdateInterval> {
+        self.update_interval
+    }
     fn sort_key(&self) -> i32 {
         self.sort_key
     }
@@ -273,6 +323,15 @@ impl PerfUiEntry for PerfUiEntryWindowScaleFactor {
             &self.label
         }
     }
+    fn value_as_f64(
+        &self,
+        value: &Self::Value,
+    ) -> Option<f64> {
+        Some(*value as f64)
+    }
+    fn update_interval(&self) -> Option<PerfUiUpdateInterval> {
+        self.update_interval
+    }
     fn sort_key(&self) -> i32 {
         self.sort_key
     }
@@ -308,6 +367,9 @@ impl PerfUiEntry for PerfUiEntryWindowResolution {
             &self.label
         }
     }
+    fn update_interval(&self) -> Option<PerfUiUpdateInterval> {
+        self.update_interval
+    }
     fn sort_key(&self) -> i32 {
         self.sort_key
     }
@@ -380,6 +442,9 @@ impl PerfUiEntry for PerfUiEntryCursorPosition {
             &self.label
         }
     }
+    fn update_interval(&self) -> Option<PerfUiUpdateInterval> {
+        self.update_interval
+    }
     fn sort_key(&self) -> i32 {
         self.sort_key
     }
@@ -0,0 +1,825 @@
+//! Perf UI Entries for statistics about the ECS World
+//!
+//! These require [`PerfUiWorldStatsPlugin`] to be added to your app.
+//!
+//! Unlike the entity count, these numbers should stay mostly flat once your
+//! app is up and running. If they keep climbing, something is probably
+//! creating new combinations of components all the time (an "archetype
+//! explosion"), which makes every query slower and slower.
+
+use bevy::prelude::*;
+use bevy::diagnostic::{Diagnostic, DiagnosticPath, Diagnostics, DiagnosticsStore, RegisterDiagnostic};
+use bevy::ecs::system::lifetimeless::SRes;
+use bevy::ecs::system::SystemParam;
+
+use crate::prelude::*;
+use crate::entry::*;
+use crate::ui::root::PerfUiUpdateTimer;
+use crate::utils::*;
+
+/// Plugin to measure statistics about the ECS World.
+///
+/// Periodically (see `interval`) counts the archetypes, tables, component
+/// types, resources, and component instances in the `World`, and publishes
+/// the results as Bevy diagnostics (see the associated constants). They can
+/// be displayed using [`PerfUiEntryArchetypeCount`], [`PerfUiEntryTableCount`],
+/// [`PerfUiEntryComponentCount`], [`PerfUiEntryResourceCount`], and
+/// [`PerfUiEntryComponentInstanceCount`].
+///
+/// The measurement is done by a system in `Last` that has read-only access
+/// to the whole `World`, so it cannot run in parallel with any system that
+/// mutates anything. Counting the component instances has to look at every
+/// archetype. To keep the cost down, it does not run every frame by default.
+#[derive(Debug, Clone)]
+pub struct PerfUiWorldStatsPlugin {
+    /// How often to measure the statistics.
+    ///
+    /// Default: `PerfUiUpdateInterval::Seconds(1.0)`
+    pub interval: PerfUiUpdateInterval,
+}
+
+impl Default for PerfUiWorldStatsPlugin {
+    fn default() -> Self {
+        PerfUiWorldStatsPlugin {
+            interval: PerfUiUpdateInterval::Seconds(1.0),
+        }
+    }
+}
+
+impl PerfUiWorldStatsPlugin {
+    /// Number of archetypes.
+    pub const ARCHETYPE_COUNT: DiagnosticPath = DiagnosticPath::const_new("perf_ui/world/archetype_count");
+    /// Number of tables.
+    pub const TABLE_COUNT: DiagnosticPath = DiagnosticPath::const_new("perf_ui/world/table_count");
+    /// Number of registered component types (not including resource types).
+    pub const COMPONENT_COUNT: DiagnosticPath = DiagnosticPath::const_new("perf_ui/world/component_count");
+    /// Number of resources (including non-send resources).
+    pub const RESOURCE_COUNT: DiagnosticPath = DiagnosticPath::const_new("perf_ui/world/resource_count");
+    /// Total number of components on all entities.
+    pub const COMPONENT_INSTANCE_COUNT: DiagnosticPath = DiagnosticPath::const_new("perf_ui/world/component_instance_count");
+}
+
+impl Plugin for PerfUiWorldStatsPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_diagnostic(Diagnostic::new(Self::ARCHETYPE_COUNT));
+        app.register_diagnostic(Diagnostic::new(Self::TABLE_COUNT));
+        app.register_diagnostic(Diagnostic::new(Self::COMPONENT_COUNT));
+        app.register_diagnostic(Diagnostic::new(Self::RESOURCE_COUNT));
+        app.register_diagnostic(Diagnostic::new(Self::COMPONENT_INSTANCE_COUNT));
+        let interval = self.interval;
+        app.add_systems(Last, measure_world_stats.run_if(
+            move |time: Res<Time<Real>>, mut timer: Local<PerfUiUpdateTimer>| {
+                timer.update_due(interval, time.elapsed_secs())
+            }
+        ));
+    }
+}
+
+fn measure_world_stats(
+    world: &World,
+    mut diagnostics: Diagnostics,
+) {
+    diagnostics.add_measurement(&PerfUiWorldStatsPlugin::ARCHETYPE_COUNT, || {
+        world.archetypes().len() as f64
+    });
+    diagnostics.add_measurement(&PerfUiWorldStatsPlugin::TABLE_COUNT, || {
+        world.storages().tables.len() as f64
+    });
+    diagnostics.add_measurement(&PerfUiWorldStatsPlugin::COMPONENT_COUNT, || {
+        // resource types are registered the same way as component types
+        let components = world.components();
+        components.iter_registered()
+            .filter(|info| {
+                info.type_id().is_none_or(|type_id| {
+                    components.get_valid_resource_id(type_id) != Some(info.id())
+                })
+            })
+            .count() as f64
+    });
+    diagnostics.add_measurement(&PerfUiWorldStatsPlugin::RESOURCE_COUNT, || {
+        let storages = world.storages();
+        let send = storages.resources.iter()
+            .filter(|(_, data)| data.is_present())
+            .count();
+        let non_send = storages.non_send_resources.iter()
+            .filter(|(_, data)| data.is_present())
+            .count();
+        (send + non_send) as f64
+    });
+    diagnostics.add_measurement(&PerfUiWorldStatsPlugin::COMPONENT_INSTANCE_COUNT, || {
+        world.archetypes().iter()
+            .map(|archetype| archetype.len() * archetype.component_count())
+            .sum::<usize>() as f64
+    });
+}
+
+/// Perf UI Entry to display the number of archetypes in the ECS World.
+///
+/// Requires [`PerfUiWorldStatsPlugin`].
+///
+/// Every unique combination of components gets its own archetype. Archetypes
+/// are never removed, so this number should stop growing soon after startup.
+#[derive(Component, Debug, Clone, Reflect)]
+#[require(PerfUiRoot)]
+#[reflect(Component, Default, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct PerfUiEntryArchetypeCount {
+    /// Custom label. If empty (default), the default label will be used.
+    pub label: String,
+    /// Enable color based on value.
+    ///
+    /// To disable (always use default color), set to empty `ColorGradient::default()`.
+    ///
+    /// Default: Green-Yellow-Red gradient (or the default `ColorGradientPreset`, reversed) between 250-1000-4000.
+    pub color_gradient: ColorGradient,
+    /// Highlight the value if above this threshold.
+    ///
+    /// Default: `10000`
+    pub threshold_highlight: Option<u32>,
+    /// If displayed using a Bar (or other similar) widget that can
+    /// show the value within a range, what should its max value be?
+    ///
+    /// If `None`, the value will be computed from the maximum of the
+    /// color gradient and the highlight threshold.
+    ///
+    /// Default: `None`
+    pub max_value_hint: Option<u32>,
+    /// Number of digits to display.
+    ///
+    /// Default: `5`
+    pub digits: u8,
+    /// History of recent values, for widgets that can display it.
+    ///
+    /// Default: last 300 values, sampled every frame.
+    pub history: PerfUiHistory<u32>,
+    /// Update the displayed value at this interval, instead of the Perf UI's.
+    ///
+    /// Default: `None` (use the `update_interval` of the `PerfUiRoot`)
+    pub update_interval: Option<PerfUiUpdateInterval>,
+    /// Sort Key (control where the entry will appear in the Perf UI).
+    pub sort_key: i32,
+}
+
+impl Default for PerfUiEntryArchetypeCount {
+    fn default() -> Self {
+        PerfUiEntryArchetypeCount {
+            label: String::new(),
+            color_gradient: ColorGradient::new_preset_default_reversed(250.0, 1000.0, 4000.0).unwrap(),
+            threshold_highlight: Some(10000),
+            max_value_hint: None,
+            digits: 5,
+            history: PerfUiHistory::new(300),
+            update_interval: None,
+            sort_key: next_sort_key(),
+        }
+    }
+}
+
+impl PerfUiEntry for PerfUiEntryArchetypeCount {
+    type SystemParam = SRes<DiagnosticsStore>;
+    type Value = u32;
+
+    fn label(&self) -> &str {
+        if self.label.is_empty() {
+            "Archetype Count"
+        } else {
+            &self.label
+        }
+    }
+    fn update_value(
+        &self,
+        diagnostics: &mut <Self::SystemParam as SystemParam>::Item<'_, '_>,
+    ) -> Option<Self::Value> {
+        Some(diagnostics.get(&PerfUiWorldStatsPlugin::ARCHETYPE_COUNT)?.value()? as u32)
+    }
+    fn format_value(
+        &self,
+        value: &Self::Value,
+    ) -> String {
+        format_pretty_int(self.digits, *value as i64)
+    }
+    fn value_color(
+        &self,
+        value: &Self::Value,
+    ) -> Option<Color> {
+        self.color_gradient.get_color_for_value(*value as f32)
+    }
+    fn value_highlight(
+        &self,
+        value: &Self::Value,
+    ) -> bool {
+        self.threshold_highlight
+            .map(|t| *value > t)
+            .unwrap_or(false)
+    }
+    fn highlight_threshold(&self, _value: &Self::Value) -> Option<f64> {
+        self.threshold_highlight.map(|t| t as f64)
+    }
+    fn value_as_f64(
+        &self,
+        value: &Self::Value,
+    ) -> Option<f64> {
+        Some(*value as f64)
+    }
+    fn update_interval(&self) -> Option<PerfUiUpdateInterval> {
+        self.update_interval
+    }
+    fn sort_key(&self) -> i32 {
+        self.sort_key
+    }
+}
+
+impl PerfUiEntryDisplayRange for PerfUiEntryArchetypeCount {
+    fn max_value_hint(&self) -> Option<Self::Value> {
+        self.max_value_hint.or(
+            match (self.threshold_highlight, self.color_gradient.max_stop()) {
+                (Some(x), None) => Some(x),
+                (None, Some((x, _))) => Some(*x as u32),
+                (Some(a), Some((b, _))) => Some(a.max(*b as u32)),
+                (None, None) => None,
+            }
+        )
+    }
+    fn min_value_hint(&self) -> Option<Self::Value> {
+        Some(0)
+    }
+}
+
+impl PerfUiEntryHistory for PerfUiEntryArchetypeCount {
+    fn history(&self) -> &PerfUiHistory<Self::Value> {
+        &self.history
+    }
+    fn history_mut(&mut self) -> &mut PerfUiHistory<Self::Value> {
+        &mut self.history
+    }
+}
+
+/// Perf UI Entry to display the number of tables in the ECS World.
+///
+/// Requires [`PerfUiWorldStatsPlugin`].
+///
+/// Every unique combination of table-stored components gets its own table
+/// (archetypes that differ only in sparse-set components share a table).
+/// Tables are never removed, so this number should stop growing soon after
+/// startup.
+#[derive(Component, Debug, Clone, Reflect)]
+#[require(PerfUiRoot)]
+#[reflect(Component, Default, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct PerfUiEntryTableCount {
+    /// Custom label. If empty (default), the default label will be used.
+    pub label: String,
+    /// Enable color based on value.
+    ///
+    /// To disable (always use default color), set to empty `ColorGradient::default()`.
+    ///
+    /// Default: Green-Yellow-Red gradient (or the default `ColorGradientPreset`, reversed) between 250-1000-4000.
+    pub color_gradient: ColorGradient,
+    /// Highlight the value if above this threshold.
+    ///
+    /// Default: `10000`
+    pub threshold_highlight: Option<u32>,
+    /// If displayed using a Bar (or other similar) widget that can
+    /// show the value within a range, what should its max value be?
+    ///
+    /// If `None`, the value will be computed from the maximum of the
+    /// color gradient and the highlight threshold.
+    ///
+    /// Default: `None`
+    pub max_value_hint: Option<u32>,
+    /// Number of digits to display.
+    ///
+    /// Default: `5`
+    pub digits: u8,
+    /// History of recent values, for widgets that can display it.
+    ///
+    /// Default: last 300 values, sampled every frame.
+    pub history: PerfUiHistory<u32>,
+    /// Update the displayed value at this interval, instead of the Perf UI's.
+    ///
+    /// Default: `None` (use the `update_interval` of the `PerfUiRoot`)
+    pub update_interval: Option<PerfUiUpdateInterval>,
+    /// Sort Key (control where the entry will appear in the Perf UI).
+    pub sort_key: i32,
+}
+
+impl Default for PerfUiEntryTableCount {
+    fn default() -> Self {
+        PerfUiEntryTableCount {
+            label: String::new(),
+            color_gradient: ColorGradient::new_preset_default_reversed(250.0, 1000.0, 4000.0).unwrap(),
+            threshold_highlight: Some(10000),
+            max_value_hint: None,
+            digits: 5,
+            history: PerfUiHistory::new(300),
+            update_interval: None,
+            sort_key: next_sort_key(),
+        }
+    }
+}
+
+impl PerfUiEntry for PerfUiEntryTableCount {
+    type SystemParam = SRes<DiagnosticsStore>;
+    type Value = u32;
+
+    fn label(&self) -> &str {
+        if self.label.is_empty() {
+            "Table Count"
+        } else {
+            &self.label
+        }
+    }
+    fn update_value(
+        &self,
+        diagnostics: &mut <Self::SystemParam as SystemParam>::Item<'_, '_>,
+    ) -> Option<Self::Value> {
+        Some(diagnostics.get(&PerfUiWorldStatsPlugin::TABLE_COUNT)?.value()? as u32)
+    }
+    fn format_value(
+        &self,
+        value: &Self::Value,
+    ) -> String {
+        format_pretty_int(self.digits, *value as i64)
+    }
+    fn value_color(
+        &self,
+        value: &Self::Value,
+    ) -> Option<Color> {
+        self.color_gradient.get_color_for_value(*value as f32)
+    }
+    fn value_highlight(
+        &self,
+        value: &Self::Value,
+    ) -> bool {
+        self.threshold_highlight
+            .map(|t| *value > t)
+            .unwrap_or(false)
+    }
+    fn highlight_threshold(&self, _value: &Self::Value) -> Option<f64> {
+        self.threshold_highlight.map(|t| t as f64)
+    }
+    fn value_as_f64(
+        &self,
+        value: &Self::Value,
+    ) -> Option<f64> {
+        Some(*value as f64)
+    }
+    fn update_interval(&self) -> Option<PerfUiUpdateInterval> {
+        self.update_interval
+    }
+    fn sort_key(&self) -> i32 {
+        self.sort_key
+    }
+}
+
+impl PerfUiEntryDisplayRange for PerfUiEntryTableCount {
+    fn max_value_hint(&self) -> Option<Self::Value> {
+        self.max_value_hint.or(
+            match (self.threshold_highlight, self.color_gradient.max_stop()) {
+                (Some(x), None) => Some(x),
+                (None, Some((x, _))) => Some(*x as u32),
+                (Some(a), Some((b, _))) => Some(a.max(*b as u32)),
+                (None, None) => None,
+            }
+        )
+    }
+    fn min_value_hint(&self) -> Option<Self::Value> {
+        Some(0)
+    }
+}
+
+impl PerfUiEntryHistory for PerfUiEntryTableCount {
+    fn history(&self) -> &PerfUiHistory<Self::Value> {
+        &self.history
+    }
+    fn history_mut(&mut self) -> &mut PerfUiHistory<Self::Value> {
+        &mut self.history
+    }
+}
+
+/// Perf UI Entry to display the number of registered component types.
+///
+/// Requires [`PerfUiWorldStatsPlugin`].
+///
+/// Resource types are not counted (see [`PerfUiEntryResourceCount`]).
+#[derive(Component, Debug, Clone, Reflect)]
+#[require(PerfUiRoot)]
+#[reflect(Component, Default, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct PerfUiEntryComponentCount {
+    /// Custom label. If empty (default), the default label will be used.
+    pub label: String,
+    /// Enable color based on value.
+    ///
+    /// To disable (always use default color), set to empty `ColorGradient::default()`.
+    ///
+    /// Default: Green-Yellow-Red gradient (or the default `ColorGradientPreset`, reversed) between 1000-2500-5000.
+    pub color_gradient: ColorGradient,
+    /// Highlight the value if above this threshold.
+    ///
+    /// Default: `10000`
+    pub threshold_highlight: Option<u32>,
+    /// If displayed using a Bar (or other similar) widget that can
+    /// show the value within a range, what should its max value be?
+    ///
+    /// If `None`, the value will be computed from the maximum of the
+    /// color gradient and the highlight threshold.
+    ///
+    /// Default: `None`
+    pub max_value_hint: Option<u32>,
+    /// Number of digits to display.
+    ///
+    /// Default: `5`
+    pub digits: u8,
+    /// History of recent values, for widgets that can display it.
+    ///
+    /// Default: last 300 values, sampled every frame.
+    pub history: PerfUiHistory<u32>,
+    /// Update the displayed value at this interval, instead of the Perf UI's.
+    ///
+    /// Default: `None` (use the `update_interval` of the `PerfUiRoot`)
+    pub update_interval: Option<PerfUiUpdateInterval>,
+    /// Sort Key (control where the entry will appear in the Perf UI).
+    pub sort_key: i32,
+}
+
+impl Default for PerfUiEntryComponentCount {
+    fn default() -> Self {
+        PerfUiEntryComponentCount {
+            label: String::new(),
+            color_gradient: ColorGradient::new_preset_default_reversed(1000.0, 2500.0, 5000.0).unwrap(),
+            threshold_highlight: Some(10000),
+            max_value_hint: None,
+            digits: 5,
+            history: PerfUiHistory::new(300),
+            update_interval: None,
+            sort_key: next_sort_key(),
+        }
+    }
+}
+
+impl PerfUiEntry for PerfUiEntryComponentCount {
+    type SystemParam = SRes<DiagnosticsStore>;
+    type Value = u32;
+
+    fn label(&self) -> &str {
+        if self.label.is_empty() {
+            "Component Count"
+        } else {
+            &self.label
+        }
+    }
+    fn update_value(
+        &self,
+        diagnostics: &mut <Self::SystemParam as SystemParam>::Item<'_, '_>,
+    ) -> Option<Self::Value> {
+        Some(diagnostics.get(&PerfUiWorldStatsPlugin::COMPONENT_COUNT)?.value()? as u32)
+    }
+    fn format_value(
+        &self,
+        value: &Self::Value,
+    ) -> String {
+        format_pretty_int(self.digits, *value as i64)
+    }
+    fn value_color(
+        &self,
+        value: &Self::Value,
+    ) -> Option<Color> {
+        self.color_gradient.get_color_for_value(*value as f32)
+    }
+    fn value_highlight(
+        &self,
+        value: &Self::Value,
+    ) -> bool {
+        self.threshold_highlight
+            .map(|t| *value > t)
+            .unwrap_or(false)
+    }
+    fn highlight_threshold(&self, _value: &Self::Value) -> Option<f64> {
+        self.threshold_highlight.map(|t| t as f64)
+    }
+    fn value_as_f64(
+        &self,
+        value: &Self::Value,
+    ) -> Option<f64> {
+        Some(*value as f64)
+    }
+    fn update_interval(&self) -> Option<PerfUiUpdateInterval> {
+        self.update_interval
+    }
+    fn sort_key(&self) -> i32 {
+        self.sort_key
+    }
+}
+
+impl PerfUiEntryDisplayRange for PerfUiEntryComponentCount {
+    fn max_value_hint(&self) -> Option<Self::Value> {
+        self.max_value_hint.or(
+            match (self.threshold_highlight, self.color_gradient.max_stop()) {
+                (Some(x), None) => Some(x),
+                (None, Some((x, _))) => Some(*x as u32),
+                (Some(a), Some((b, _))) => Some(a.max(*b as u32)),
+                (None, None) => None,
+            }
+        )
+    }
+    fn min_value_hint(&self) -> Option<Self::Value> {
+        Some(0)
+    }
+}
+
+impl PerfUiEntryHistory for PerfUiEntryComponentCount {
+    fn history(&self) -> &PerfUiHistory<Self::Value> {
+        &self.history
+    }
+    fn history_mut(&mut self) -> &mut PerfUiHistory<Self::Value> {
+        &mut self.history
+    }
+}
+
+/// Perf UI Entry to display the number of resources in the ECS World.
+///
+/// Requires [`PerfUiWorldStatsPlugin`].
+///
+/// Includes non-send resources.
+#[derive(Component, Debug, Clone, Reflect)]
+#[require(PerfUiRoot)]
+#[reflect(Component, Default, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct PerfUiEntryResourceCount {
+    /// Custom label. If empty (default), the default label will be used.
+    pub label: String,
+    /// Enable color based on value.
+    ///
+    /// To disable (always use default color), set to empty `ColorGradient::default()`.
+    ///
+    /// Default: Green-Yellow-Red gradient (or the default `ColorGradientPreset`, reversed) between 500-1000-2000.
+    pub color_gradient: ColorGradient,
+    /// Highlight the value if above this threshold.
+    ///
+    /// Default: `5000`
+    pub threshold_highlight: Option<u32>,
+    /// If displayed using a Bar (or other similar) widget that can
+    /// show the value within a range, what should its max value be?
+    ///
+    /// If `None`, the value will be computed from the maximum of the
+    /// color gradient and the highlight threshold.
+    ///
+    /// Default: `None`
+    pub max_value_hint: Option<u32>,
+    /// Number of digits to display.
+    ///
+    /// Default: `5`
+    pub digits: u8,
+    /// History of recent values, for widgets that can display it.
+    ///
+    /// Default: last 300 values, sampled every frame.
+    pub history: PerfUiHistory<u32>,
+    /// Update the displayed value at this interval, instead of the Perf UI's.
+    ///
+    /// Default: `None` (use the `update_interval` of the `PerfUiRoot`)
+    pub update_interval: Option<PerfUiUpdateInterval>,
+    /// Sort Key (control where the entry will appear in the Perf UI).
+    pub sort_key: i32,
+}
+
+impl Default for PerfUiEntryResourceCount {
+    fn default() -> Self {
+        PerfUiEntryResourceCount {
+            label: String::new(),
+            color_gradient: ColorGradient::new_preset_default_reversed(500.0, 1000.0, 2000.0).unwrap(),
+            threshold_highlight: Some(5000),
+            max_value_hint: None,
+            digits: 5,
+            history: PerfUiHistory::new(300),
+            update_interval: None,
+            sort_key: next_sort_key(),
+        }
+    }
+}
+
+impl PerfUiEntry for PerfUiEntryResourceCount {
+    type SystemParam = SRes<DiagnosticsStore>;
+    type Value = u32;
+
+    fn label(&self) -> &str {
+        if self.label.is_empty() {
+            "Resource Count"
+        } else {
+            &self.label
+        }
+    }
+    fn update_value(
+        &self,
+        diagnostics: &mut <Self::SystemParam as SystemParam>::Item<'_, '_>,
+    ) -> Option<Self::Value> {
+        Some(diagnostics.get(&PerfUiWorldStatsPlugin::RESOURCE_COUNT)?.value()? as u32)
+    }
+    fn format_value(
+        &self,
+        value: &Self::Value,
+    ) -> String {
+        format_pretty_int(self.digits, *value as i64)
+    }
+    fn value_color(
+        &self,
+        value: &Self::Value,
+    ) -> Option<Color> {
+        self.color_gradient.get_color_for_value(*value as f32)
+    }
+    fn value_highlight(
+        &self,
+        value: &Self::Value,
+    ) -> bool {
+        self.threshold_highlight
+            .map(|t| *value > t)
+            .unwrap_or(false)
+    }
+    fn highlight_threshold(&self, _value: &Self::Value) -> Option<f64> {
+        self.threshold_highlight.map(|t| t as f64)
+    }
+    fn value_as_f64(
+        &self,
+        value: &Self::Value,
+    ) -> Option<f64> {
+        Some(*value as f64)
+    }
+    fn update_interval(&self) -> Option<PerfUiUpdateInterval> {
+        self.update_interval
+    }
+    fn sort_key(&self) -> i32 {
+        self.sort_key
+    }
+}
+
+impl PerfUiEntryDisplayRange for PerfUiEntryResourceCount {
+    fn max_value_hint(&self) -> Option<Self::Value> {
+        self.max_value_hint.or(
+            match (self.threshold_highlight, self.color_gradient.max_stop()) {
+                (Some(x), None) => Some(x),
+                (None, Some((x, _))) => Some(*x as u32),
+                (Some(a), Some((b, _))) => Some(a.max(*b as u32)),
+                (None, None) => None,
+            }
+        )
+    }
+    fn min_value_hint(&self) -> Option<Self::Value> {
+        Some(0)
+    }
+}
+
+impl PerfUiEntryHistory for PerfUiEntryResourceCount {
+    fn history(&self) -> &PerfUiHistory<Self::Value> {
+        &self.history
+    }
+    fn history_mut(&mut self) -> &mut PerfUiHistory<Self::Value> {
+        &mut self.history
+    }
+}
+
+/// Perf UI Entry to display the total number of components on all entities.
+///
+/// Requires [`PerfUiWorldStatsPlugin`].
+///
+/// This is the sum, over all entities, of how many components each entity
+/// has. It grows with the entity count, but also when entities get more
+/// components.
+#[derive(Component, Debug, Clone, Reflect)]
+#[require(PerfUiRoot)]
+#[reflect(Component, Default, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct PerfUiEntryComponentInstanceCount {
+    /// Custom label. If empty (default), the default label will be used.
+    pub label: String,
+    /// Enable color based on value.
+    ///
+    /// To disable (always use default color), set to empty `ColorGradient::default()`.
+    ///
+    /// Default: Green-Yellow-Red gradient (or the default `ColorGradientPreset`, reversed) between 10000-100000-1000000.
+    pub color_gradient: ColorGradient,
+    /// Highlight the value if above this threshold.
+    ///
+    /// Default: `2000000`
+    pub threshold_highlight: Option<u32>,
+    /// If displayed using a Bar (or other similar) widget that can
+    /// show the value within a range, what should its max value be?
+    ///
+    /// If `None`, the value will be computed from the maximum of the
+    /// color gradient and the highlight threshold.
+    ///
+    /// Default: `None`
+    pub max_value_hint: Option<u32>,
+    /// Number of digits to display.
+    ///
+    /// Default: `8`
+    pub digits: u8,
+    /// History of recent values, for widgets that can display it.
+    ///
+    /// Default: last 300 values, sampled every frame.
+    pub history: PerfUiHistory<u32>,
+    /// Update the displayed value at this interval, instead of the Perf UI's.
+    ///
+    /// Default: `None` (use the `update_interval` of the `PerfUiRoot`)
+    pub update_interval: Option<PerfUiUpdateInterval>,
+    /// Sort Key (control where the entry will appear in the Perf UI).
+    pub sort_key: i32,
+}
+
+impl Default for PerfUiEntryComponentInstanceCount {
+    fn default() -> Self {
+        PerfUiEntryComponentInstanceCount {
+            label: String::new(),
+            color_gradient: ColorGradient::new_preset_default_reversed(10000.0, 100000.0, 1000000.0).unwrap(),
+            threshold_highlight: Some(2000000),
+            max_value_hint: None,
+            digits: 8,
+            history: PerfUiHistory::new(300),
+            update_interval: None,
+            sort_key: next_sort_key(),
+        }
+    }
+}
+
+impl PerfUiEntry for PerfUiEntryComponentInstanceCount {
+    type SystemParam = SRes<DiagnosticsStore>;
+    type Value = u32;
+
+    fn label(&self) -> &str {
+        if self.label.is_empty() {
+            "Component Instances"
+        } else {
+            &self.label
+        }
+    }
+    fn update_value(
+        &self,
+        diagnostics: &mut <Self::SystemParam as SystemParam>::Item<'_, '_>,
+    ) -> Option<Self::Value> {
+        Some(diagnostics.get(&PerfUiWorldStatsPlugin::COMPONENT_INSTANCE_COUNT)?.value()? as u32)
+    }
+    fn format_value(
+        &self,
+        value: &Self::Value,
+    ) -> String {
+        format_pretty_int(self.digits, *value as i64)
+    }
+    fn value_color(
+        &self,
+        value: &Self::Value,
+    ) -> Option<Color> {
+        self.color_gradient.get_color_for_value(*value as f32)
+    }
+    fn value_highlight(
+        &self,
+        value: &Self::Value,
+    ) -> bool {
+        self.threshold_highlight
+            .map(|t| *value > t)
+            .unwrap_or(false)
+    }
+    fn highlight_threshold(&self, _value: &Self::Value) -> Option<f64> {
+        self.threshold_highlight.map(|t| t as f64)
+    }
+    fn value_as_f64(
+        &self,
+        value: &Self::Value,
+    ) -> Option<f64> {
+        Some(*value as f64)
+    }
+    fn update_interval(&self) -> Option<PerfUiUpdateInterval> {
+        self.update_interval
+    }
+    fn sort_key(&self) -> i32 {
+        self.sort_key
+    }
+}
+
+impl PerfUiEntryDisplayRange for PerfUiEntryComponentInstanceCount {
+    fn max_value_hint(&self) -> Option<Self::Value> {
+        self.max_value_hint.or(
+            match (self.threshold_highlight, self.color_gradient.max_stop()) {
+                (Some(x), None) => Some(x),
+                (None, Some((x, _))) => Some(*x as u32),
+                (Some(a), Some((b, _))) => Some(a.max(*b as u32)),
+                (None, None) => None,
+            }
+        )
+    }
+    fn min_value_hint(&self) -> Option<Self::Value> {
+        Some(0)
+    }
+}
+
+impl PerfUiEntryHistory for PerfUiEntryComponentInstanceCount {
+    fn history(&self) -> &PerfUiHistory<Self::Value> {
+        &self.history
+    }
+    fn history_mut(&mut self) -> &mut PerfUiHistory<Self::Value> {
+        &mut self.history
+    }
+}
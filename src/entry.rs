@@ -1,10 +1,16 @@
 //! Common framework for Perf UI Entry types (data providers)
 
+use std::collections::VecDeque;
+use std::time::Duration;
+
 use bevy::prelude::*;
+use bevy::ecs::component::Mutable;
 use bevy::ecs::system::SystemParam;
+use bevy::ecs::system::StaticSystemParam;
 
 #[allow(unused_imports)]
 use crate::prelude::*;
+use crate::ui::widget::PerfUiWidget;
 
 /// Trait for components representing entries (rows) in the Perf UI.
 ///
@@ -13,7 +19,7 @@ use crate::prelude::*;
 ///
 /// If you also have meaningful historical data available, rather than
 /// just the current value, consider also implementing [`PerfUiEntryHistory`].
-pub trait PerfUiEntry: Component {
+pub trait PerfUiEntry: Component<Mutability = Mutable> {
     /// Any system parameters you need to fetch/update the value.
     type SystemParam: SystemParam + 'static;
 
@@ -33,9 +39,17 @@ pub trait PerfUiEntry: Component {
     /// user creates the entries.
     fn sort_key(&self) -> i32;
 
+    /// Optional: override how often the displayed value should be updated.
+    ///
+    /// `None` means the `update_interval` of the `PerfUiRoot` should be used.
+    fn update_interval(&self) -> Option<PerfUiUpdateInterval> {
+        None
+    }
+
     /// Update the value to display in the Perf UI.
     ///
-    /// This function will be called once per frame,
+    /// This function will be called once per frame (or less often,
+    /// depending on the update interval),
     /// in the `Update` schedule,
     /// in the `PerfUiSet::Update` set.
     fn update_value(
@@ -43,6 +57,38 @@ pub trait PerfUiEntry: Component {
         param: &mut <Self::SystemParam as SystemParam>::Item<'_, '_>,
     ) -> Option<Self::Value>;
 
+    /// Optional: collect data every frame, to compute the value from.
+    ///
+    /// Implement this if your value is computed from data collected over
+    /// time (such as the worst FPS in the last few seconds), rather than
+    /// being available at any time.
+    ///
+    /// This function will be called once per frame, for every instance of
+    /// the entry (including entries wrapped in widgets or other entries),
+    /// even if the Perf UI is hidden, regardless of the update interval.
+    /// It runs in the `Update` schedule, before `PerfUiSet::Setup` and
+    /// `PerfUiSet::Update` (and after Bevy's `FrameTimeDiagnosticsPlugin`
+    /// has measured the frame).
+    ///
+    /// `now` is the current time since startup, as per `Time<Real>::elapsed()`.
+    ///
+    /// Changes made to the entry here do not cause its UI to be respawned.
+    fn sample(
+        &mut self,
+        _param: &mut <Self::SystemParam as SystemParam>::Item<'_, '_>,
+        _now: Duration,
+    ) {
+    }
+
+    /// Optional: discard all the data collected by `sample`.
+    ///
+    /// Called when a [`PerfUiClearSamples`] event is sent for the Perf UI
+    /// (for example, by [`PerfUiBenchmark`](crate::benchmark::PerfUiBenchmark)
+    /// after its warm-up), so that the value only reflects what happens from
+    /// now on.
+    fn clear_samples(&mut self) {
+    }
+
     /// Format the raw value into a string for display
     ///
     /// Called every frame after `update_value`, unless it returned `None`.
@@ -79,6 +125,39 @@ pub trait PerfUiEntry: Component {
     ) -> bool {
         false
     }
+
+    /// Optional: the threshold at which values are highlighted, as a number.
+    ///
+    /// If there are multiple thresholds, return the one nearest to `value`.
+    ///
+    /// Used for hysteresis (see `PerfUiHighlightDebounce::clear_margin`),
+    /// together with `value_as_f64`. If `None`, no margin is applied.
+    fn highlight_threshold(
+        &self,
+        _value: &Self::Value,
+    ) -> Option<f64> {
+        None
+    }
+
+    /// Optional: override the debounce/hysteresis settings for highlighting.
+    ///
+    /// `None` means the `highlight_debounce` of the `PerfUiRoot` should be used.
+    fn highlight_debounce(&self) -> Option<PerfUiHighlightDebounce> {
+        None
+    }
+
+    /// Optional: convert the value to a number.
+    ///
+    /// Used for machine-readable output (such as by the recorder), to
+    /// output the raw numeric value, rather than the formatted string.
+    ///
+    /// If unimplemented (or `None`), the value's `Debug` representation is used.
+    fn value_as_f64(
+        &self,
+        _value: &Self::Value,
+    ) -> Option<f64> {
+        None
+    }
 }
 
 /// Extension to [`PerfUiEntry`] to provide an expected range of values.
@@ -102,3 +181,344 @@ pub trait PerfUiEntryDisplayRange: PerfUiEntry {
     /// If the value is below this, it may be clipped in the UI.
     fn min_value_hint(&self) -> Option<Self::Value>;
 }
+
+/// A value made of several named parts.
+///
+/// This is the value type of entries that implement [`PerfUiEntryBreakdown`],
+/// such as the time spent on rendering, broken down by render pass.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct PerfUiBreakdown {
+    /// The parts, in the order they should be displayed.
+    pub parts: Vec<PerfUiBreakdownPart>,
+}
+
+/// One part of a [`PerfUiBreakdown`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct PerfUiBreakdownPart {
+    /// The name of the part.
+    pub label: String,
+    /// The value of the part.
+    pub value: f64,
+}
+
+impl PerfUiBreakdown {
+    /// The sum of the values of all the parts.
+    pub fn total(&self) -> f64 {
+        self.parts.iter().map(|part| part.value).sum()
+    }
+
+    /// A value with a single part, that only represents a total.
+    ///
+    /// Useful for implementing [`PerfUiEntryDisplayRange`]
+    /// for entries whose value is a breakdown.
+    pub fn from_total(total: f64) -> Self {
+        PerfUiBreakdown {
+            parts: vec![PerfUiBreakdownPart {
+                label: String::new(),
+                value: total,
+            }],
+        }
+    }
+}
+
+/// A breakdown converts to a number as its total.
+///
+/// This allows widgets to handle breakdowns using [`PerfUiEntryDisplayRange`].
+impl num_traits::ToPrimitive for PerfUiBreakdown {
+    fn to_i64(&self) -> Option<i64> {
+        self.total().to_i64()
+    }
+    fn to_u64(&self) -> Option<u64> {
+        self.total().to_u64()
+    }
+    fn to_f64(&self) -> Option<f64> {
+        Some(self.total())
+    }
+}
+
+/// Extension to [`PerfUiEntry`] for entries whose value is made of several parts.
+///
+/// Used by widgets which display each part separately,
+/// such as [`PerfUiWidgetTable`](crate::widgets::table::PerfUiWidgetTable)
+/// and [`PerfUiWidgetStackedBar`](crate::widgets::stacked_bar::PerfUiWidgetStackedBar).
+///
+/// The entry's own methods (`format_value`, `value_color`, etc.) apply to the
+/// value as a whole. Implement the methods of this trait for the individual parts.
+pub trait PerfUiEntryBreakdown: PerfUiEntry<Value = PerfUiBreakdown> {
+    /// Format the value of one part into a string for display.
+    fn format_part(
+        &self,
+        part: &PerfUiBreakdownPart,
+    ) -> String {
+        format!("{}", part.value)
+    }
+
+    /// Optional: set a custom color for the value of one part.
+    ///
+    /// `None` means the value should be displayed using the default color.
+    fn part_color(
+        &self,
+        _part: &PerfUiBreakdownPart,
+    ) -> Option<Color> {
+        None
+    }
+
+    /// Optional: set whether the value of one part should be displayed highlighted.
+    fn part_highlight(
+        &self,
+        _part: &PerfUiBreakdownPart,
+    ) -> bool {
+        false
+    }
+}
+
+/// Keeps track of whether an entry should be highlighted, over time.
+///
+/// This implements [`PerfUiHighlightDebounce`]: instead of highlighting
+/// whenever `value_highlight` returns `true`, the value has to stay past
+/// the threshold for some time, and cross back by a margin to clear.
+///
+/// The framework keeps one of these for every entry instance, and tells
+/// widgets whether to highlight the value when calling `PerfUiWidget::update`.
+#[derive(Debug, Default, Clone)]
+pub struct PerfUiHighlightFilter {
+    highlighted: bool,
+    /// Number of consecutive updates past the threshold.
+    count: u32,
+    /// When the value went past the threshold (seconds of real time).
+    since: Option<f32>,
+}
+
+impl PerfUiHighlightFilter {
+    /// Check if the entry should be highlighted, given its current value.
+    ///
+    /// Must be called whenever a new value is obtained. `value` is `None`
+    /// if `update_value` returned `None`. `now` is the elapsed (real)
+    /// time in seconds.
+    pub fn update<E: PerfUiEntry>(
+        &mut self,
+        entry: &E,
+        value: Option<&E::Value>,
+        root: &PerfUiRoot,
+        now: f32,
+    ) -> bool {
+        let Some(value) = value else {
+            *self = default();
+            return false;
+        };
+        let debounce = entry.highlight_debounce()
+            .unwrap_or(root.highlight_debounce);
+        if entry.value_highlight(value) {
+            self.count = self.count.saturating_add(1);
+            let since = *self.since.get_or_insert(now);
+            if self.count >= debounce.updates && now - since >= debounce.seconds {
+                self.highlighted = true;
+            }
+        } else {
+            self.count = 0;
+            self.since = None;
+            if self.highlighted {
+                let within_margin = match (entry.value_as_f64(value), entry.highlight_threshold(value)) {
+                    (Some(v), Some(t)) => (v - t).abs() < debounce.clear_margin as f64 * t.abs(),
+                    _ => false,
+                };
+                self.highlighted = within_margin;
+            }
+        }
+        self.highlighted
+    }
+
+    /// Is the entry currently highlighted? (as of the last `update`)
+    pub fn is_highlighted(&self) -> bool {
+        self.highlighted
+    }
+}
+
+/// Extension to [`PerfUiEntry`] for entries that keep a history of past values.
+///
+/// The history is stored inside the entry itself, as a [`PerfUiHistory`].
+/// For every entry type registered using `app.add_perf_ui_history::<E>()`,
+/// the framework records the values it obtains from `update_value` into
+/// the history (no matter which widget displays the entry). Widgets (and
+/// anything else) can then read it, to visualize how the value has
+/// changed over time.
+///
+/// Entries that compute their value from recent measurements can instead
+/// record those measurements into their history themselves, from
+/// `PerfUiEntry::sample`. Such entries should not be registered using
+/// `add_perf_ui_history`.
+///
+/// To control how many values are kept and how often they are
+/// sampled, configure the [`PerfUiHistory`] of your entry.
+pub trait PerfUiEntryHistory: PerfUiEntry {
+    /// Access the history of past values.
+    fn history(&self) -> &PerfUiHistory<Self::Value>;
+
+    /// Access the history of past values mutably.
+    ///
+    /// Used by the framework to record new values.
+    fn history_mut(&mut self) -> &mut PerfUiHistory<Self::Value>;
+}
+
+/// Ring buffer of past values of a Perf UI entry.
+///
+/// Keeps up to `max_len` of the most recent values, together with
+/// the (real) time at which each of them was sampled.
+///
+/// If `max_len` is `0`, nothing is recorded.
+///
+/// If serialized, only the settings are included, not the values.
+#[derive(Debug, Clone, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PerfUiHistory<V> {
+    /// The maximum number of values to keep.
+    ///
+    /// Older values are discarded when new ones are recorded.
+    pub max_len: usize,
+    /// Minimum amount of time between samples.
+    ///
+    /// `Duration::ZERO` means a new value will be recorded every frame.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub sample_interval: Duration,
+    #[reflect(ignore)]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    values: VecDeque<(Duration, V)>,
+}
+
+impl<V> PerfUiHistory<V> {
+    /// Create a new empty history that keeps up to `max_len` values,
+    /// sampled every frame.
+    ///
+    /// Use `with_sample_interval` to sample less often.
+    pub fn new(max_len: usize) -> Self {
+        PerfUiHistory {
+            max_len,
+            sample_interval: Duration::ZERO,
+            values: VecDeque::new(),
+        }
+    }
+
+    /// Set the minimum amount of time between samples (builder-style API).
+    pub fn with_sample_interval(mut self, interval: Duration) -> Self {
+        self.sample_interval = interval;
+        self
+    }
+
+    /// Is it time to record a new value?
+    ///
+    /// `now` is the current time since startup, as per `Time<Real>::elapsed()`.
+    pub fn wants_sample(&self, now: Duration) -> bool {
+        if self.max_len == 0 {
+            return false;
+        }
+        match self.values.back() {
+            // never record more than one value per point in time
+            Some((t, _)) => now > *t && now - *t >= self.sample_interval,
+            None => true,
+        }
+    }
+
+    /// Add a new value to the history.
+    ///
+    /// `now` is the current time since startup, as per `Time<Real>::elapsed()`.
+    ///
+    /// This does not check `sample_interval`. See `wants_sample`.
+    pub fn record(&mut self, now: Duration, value: V) {
+        if self.max_len == 0 {
+            self.values.clear();
+            return;
+        }
+        while self.values.len() >= self.max_len {
+            self.values.pop_front();
+        }
+        self.values.push_back((now, value));
+    }
+
+    /// Discard all recorded values.
+    pub fn clear(&mut self) {
+        self.values.clear();
+    }
+
+    /// How many values are currently recorded.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Is the history empty?
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// The most recently recorded value.
+    pub fn latest(&self) -> Option<&V> {
+        self.values.back().map(|(_, v)| v)
+    }
+
+    /// Iterate over the recorded values, from oldest to newest.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &V> + ExactSizeIterator {
+        self.values.iter().map(|(_, v)| v)
+    }
+
+    /// Iterate over the recorded values, from oldest to newest,
+    /// together with the time when each value was sampled.
+    pub fn iter_timed(&self) -> impl DoubleEndedIterator<Item = (Duration, &V)> + ExactSizeIterator {
+        self.values.iter().map(|(t, v)| (*t, v))
+    }
+}
+
+/// Records values into the history of entries of a given type.
+///
+/// Inserted by `add_perf_ui_history`, so that `update_perf_ui_widget`
+/// knows to record the values it obtains.
+#[derive(Resource)]
+pub(crate) struct PerfUiHistoryRecorder<E: PerfUiEntry> {
+    pub(crate) wants_sample: fn(&E, Duration) -> bool,
+    pub(crate) record: fn(&mut E, Duration, &E::Value),
+}
+
+impl<E> Default for PerfUiHistoryRecorder<E>
+where
+    E: PerfUiEntryHistory,
+    E::Value: Clone,
+{
+    fn default() -> Self {
+        PerfUiHistoryRecorder {
+            wants_sample: |entry, now| entry.history().wants_sample(now),
+            record: |entry, now, value| entry.history_mut().record(now, value.clone()),
+        }
+    }
+}
+
+/// Event to make all the entries of a Perf UI discard their collected data.
+///
+/// See `PerfUiEntry::clear_samples`.
+#[derive(Event, Debug, Clone)]
+pub struct PerfUiClearSamples {
+    /// The Perf UI entity (with the `PerfUiRoot` component).
+    pub entity: Entity,
+}
+
+/// System that lets Perf UI entries of a given type collect data every frame
+///
+/// See `PerfUiEntry::sample`.
+///
+/// Exposed as `pub` so you can refer to it for ordering.
+pub fn sample_perf_ui_entries<E: PerfUiEntry, W: PerfUiWidget<E>>(
+    time: Res<Time<Real>>,
+    mut evr_clear: EventReader<PerfUiClearSamples>,
+    mut q_widget: Query<(Entity, &mut W, Option<&PerfUiEntryHost>)>,
+    entry_param: StaticSystemParam<E::SystemParam>,
+) {
+    let mut entry_param = entry_param.into_inner();
+    let now = time.elapsed();
+    let clear: Vec<Entity> = evr_clear.read().map(|ev| ev.entity).collect();
+    for (e_entry, mut widget, host) in &mut q_widget {
+        // Sampling must not count as a change to the component,
+        // otherwise we would respawn the widget's UI every frame.
+        let entry = widget.bypass_change_detection().entry_mut();
+        if clear.contains(&crate::ui::perf_ui_root_of(e_entry, host)) {
+            entry.clear_samples();
+        }
+        entry.sample(&mut entry_param, now);
+    }
+}
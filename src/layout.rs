@@ -0,0 +1,413 @@
+//! Perf UI layouts loaded from asset files.
+//!
+//! Instead of spawning a Perf UI from code, you can describe it in a
+//! [RON](https://github.com/ron-rs/ron) file: the settings of the [`PerfUiRoot`]
+//! (colors, fonts, position, margins, ...), plus an ordered list of entries,
+//! identified by name, with their settings. Load it as a [`PerfUiLayout`] asset
+//! and spawn an entity with a [`PerfUiLayoutHandle`]:
+//!
+//! ```rust
+//! commands.spawn(PerfUiLayoutHandle(asset_server.load("debug.perfui.ron")));
+//! ```
+//!
+//! The Perf UI is built when the asset is loaded. If you enable Bevy's
+//! `file_watcher` cargo feature, it is also rebuilt whenever the file
+//! is modified, so you can tweak the layout while your game is running.
+//!
+//! Here is an example layout file:
+//!
+//! ```ron
+//! (
+//!     root: (
+//!         position: BottomLeft,
+//!         background_color: Srgba((red: 0.0, green: 0.0, blue: 0.0, alpha: 0.75)),
+//!         values_col_width: 96.0,
+//!     ),
+//!     font_label: "Ubuntu-B.ttf",
+//!     entries: [
+//!         ("FPS", ()),
+//!         ("FrameTime", (
+//!             threshold_highlight: 20.0,
+//!             color_gradient: [
+//!                 (8.0, Srgba((red: 0.0, green: 1.0, blue: 0.0, alpha: 1.0))),
+//!                 (16.0, Srgba((red: 1.0, green: 0.0, blue: 0.0, alpha: 1.0))),
+//!             ],
+//!         )),
+//!         ("Bar<EntityCount>", (
+//!             bar_length_px: 200.0,
+//!         )),
+//!     ],
+//! )
+//! ```
+//!
+//! Any settings that are omitted get their default values. Optional settings
+//! can be written without `Some(...)`. The font paths are relative to your
+//! assets folder.
+//!
+//! The names of the built-in entries are their type names without the
+//! `PerfUiEntry` prefix (such as `"FPS"` for `PerfUiEntryFPS`). Built-in
+//! widgets are written as `"Bar<FPS>"`, `"Graph<FPS>"`, `"Histogram<FPS>"`.
+//! To use your own entry or widget types in layout files, register them with
+//! a name, using `app.add_perf_ui_layout_entry::<T>("MyName")`.
+//!
+//! The entries are displayed in the order they are listed in the file
+//! (unless you set their `sort_key`). Every entry is spawned as a separate
+//! child entity of the Perf UI, so the same entry type can be listed
+//! multiple times.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, RwLock};
+
+use bevy::asset::io::Reader;
+use bevy::asset::{AssetLoader, LoadContext};
+use bevy::prelude::*;
+use serde::de::{DeserializeOwned, DeserializeSeed, Deserializer, Error as _, MapAccess, SeqAccess, Visitor};
+use serde::Deserialize;
+
+use crate::ui::root::PerfUiRoot;
+
+/// Asset describing a Perf UI: the settings of the root and the entries.
+///
+/// Typically loaded from a `.perfui.ron` file. See the
+/// [module-level documentation](self) for the file format.
+#[derive(Asset, TypePath)]
+pub struct PerfUiLayout {
+    /// The settings for the whole Perf UI (including the fonts).
+    pub root: PerfUiRoot,
+    entries: Vec<PerfUiLayoutEntry>,
+}
+
+struct PerfUiLayoutEntry {
+    name: String,
+    component: Box<dyn LayoutComponent>,
+}
+
+impl PerfUiLayout {
+    /// Iterate over the names of the entries, in the order they are listed.
+    pub fn entry_names(&self) -> impl Iterator<Item = &str> {
+        self.entries.iter().map(|entry| entry.name.as_str())
+    }
+}
+
+/// Component to build a Perf UI from a [`PerfUiLayout`] asset.
+///
+/// Spawn an entity with this component. When the asset is loaded (or modified),
+/// the `PerfUiRoot` of the entity is replaced with the one from the layout,
+/// and the entries are (re)spawned as child entities.
+#[derive(Component, Debug, Default, Clone, PartialEq, Eq)]
+#[require(PerfUiLayoutState)]
+pub struct PerfUiLayoutHandle(pub Handle<PerfUiLayout>);
+
+#[doc(hidden)]
+#[derive(Component, Default)]
+pub struct PerfUiLayoutState {
+    /// The entities spawned for the entries of the layout.
+    entities: Vec<Entity>,
+}
+
+/// Type-erased entry (or widget) component from a layout.
+trait LayoutComponent: Send + Sync + 'static {
+    fn insert_into(&self, entity: &mut EntityCommands);
+}
+
+impl<T: Component + Clone> LayoutComponent for T {
+    fn insert_into(&self, entity: &mut EntityCommands) {
+        entity.insert(self.clone());
+    }
+}
+
+type DeserializeFn = fn(&mut dyn erased_serde::Deserializer) -> Result<Box<dyn LayoutComponent>, erased_serde::Error>;
+
+fn deserialize_component<T>(deserializer: &mut dyn erased_serde::Deserializer) -> Result<Box<dyn LayoutComponent>, erased_serde::Error>
+where
+    T: Component + Clone + DeserializeOwned,
+{
+    Ok(Box::new(erased_serde::deserialize::<T>(deserializer)?))
+}
+
+/// The names of the entry types that can be used in layout files.
+///
+/// Shared with the asset loader, so that it also sees types
+/// registered after it was created.
+#[derive(Resource, Default, Clone)]
+pub(crate) struct PerfUiLayoutRegistry(Arc<RwLock<HashMap<String, DeserializeFn>>>);
+
+impl PerfUiLayoutRegistry {
+    pub(crate) fn register<T>(&self, name: String)
+    where
+        T: Component + Clone + DeserializeOwned,
+    {
+        self.0.write().unwrap().insert(name, deserialize_component::<T>);
+    }
+}
+
+/// Asset loader for [`PerfUiLayout`] (`.perfui.ron` files).
+pub struct PerfUiLayoutLoader {
+    registry: PerfUiLayoutRegistry,
+}
+
+/// Error when loading a [`PerfUiLayout`].
+#[non_exhaustive]
+#[derive(Debug)]
+pub enum PerfUiLayoutLoaderError {
+    /// The file could not be read.
+    Io(std::io::Error),
+    /// The file could not be parsed.
+    Ron(ron::error::SpannedError),
+}
+
+impl fmt::Display for PerfUiLayoutLoaderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PerfUiLayoutLoaderError::Io(e) => write!(f, "could not read Perf UI layout: {}", e),
+            PerfUiLayoutLoaderError::Ron(e) => write!(f, "could not parse Perf UI layout: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for PerfUiLayoutLoaderError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            PerfUiLayoutLoaderError::Io(e) => Some(e),
+            PerfUiLayoutLoaderError::Ron(e) => Some(e),
+        }
+    }
+}
+
+impl From<std::io::Error> for PerfUiLayoutLoaderError {
+    fn from(e: std::io::Error) -> Self {
+        PerfUiLayoutLoaderError::Io(e)
+    }
+}
+
+impl From<ron::error::SpannedError> for PerfUiLayoutLoaderError {
+    fn from(e: ron::error::SpannedError) -> Self {
+        PerfUiLayoutLoaderError::Ron(e)
+    }
+}
+
+impl AssetLoader for PerfUiLayoutLoader {
+    type Asset = PerfUiLayout;
+    type Settings = ();
+    type Error = PerfUiLayoutLoaderError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        load_context: &mut LoadContext<'_>,
+    ) -> Result<PerfUiLayout, PerfUiLayoutLoaderError> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+
+        let file = {
+            let registry = self.registry.0.read().unwrap();
+            ron::Options::default()
+                .with_default_extension(ron::extensions::Extensions::IMPLICIT_SOME)
+                .from_bytes_seed(&bytes, LayoutFileSeed(&registry))?
+        };
+
+        let mut root = file.root;
+        if let Some(path) = file.font_label {
+            root.font_label = load_context.load(path);
+        }
+        if let Some(path) = file.font_value {
+            root.font_value = load_context.load(path);
+        }
+        if let Some(path) = file.font_highlight {
+            root.font_highlight = load_context.load(path);
+        }
+
+        Ok(PerfUiLayout {
+            root,
+            entries: file.entries,
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["perfui.ron"]
+    }
+}
+
+/// The contents of a layout file, before loading the fonts.
+struct LayoutFile {
+    root: PerfUiRoot,
+    font_label: Option<String>,
+    font_value: Option<String>,
+    font_highlight: Option<String>,
+    entries: Vec<PerfUiLayoutEntry>,
+}
+
+#[derive(Deserialize)]
+#[serde(field_identifier, rename_all = "snake_case")]
+enum LayoutFileField {
+    Root,
+    FontLabel,
+    FontValue,
+    FontHighlight,
+    Entries,
+}
+
+const LAYOUT_FILE_FIELDS: &[&str] = &["root", "font_label", "font_value", "font_highlight", "entries"];
+
+struct LayoutFileSeed<'a>(&'a HashMap<String, DeserializeFn>);
+
+impl<'de> DeserializeSeed<'de> for LayoutFileSeed<'_> {
+    type Value = LayoutFile;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<LayoutFile, D::Error> {
+        deserializer.deserialize_struct("PerfUiLayout", LAYOUT_FILE_FIELDS, self)
+    }
+}
+
+impl<'de> Visitor<'de> for LayoutFileSeed<'_> {
+    type Value = LayoutFile;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "a Perf UI layout")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<LayoutFile, A::Error> {
+        let mut file = LayoutFile {
+            root: default(),
+            font_label: None,
+            font_value: None,
+            font_highlight: None,
+            entries: vec![],
+        };
+        while let Some(field) = map.next_key()? {
+            match field {
+                LayoutFileField::Root => file.root = map.next_value()?,
+                LayoutFileField::FontLabel => file.font_label = map.next_value()?,
+                LayoutFileField::FontValue => file.font_value = map.next_value()?,
+                LayoutFileField::FontHighlight => file.font_highlight = map.next_value()?,
+                LayoutFileField::Entries => file.entries = map.next_value_seed(EntriesSeed(self.0))?,
+            }
+        }
+        Ok(file)
+    }
+}
+
+/// The list of entries: `[("Name", (settings...)), ...]`
+struct EntriesSeed<'a>(&'a HashMap<String, DeserializeFn>);
+
+impl<'de> DeserializeSeed<'de> for EntriesSeed<'_> {
+    type Value = Vec<PerfUiLayoutEntry>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'de> Visitor<'de> for EntriesSeed<'_> {
+    type Value = Vec<PerfUiLayoutEntry>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "a list of Perf UI entries")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut entries = vec![];
+        while let Some(entry) = seq.next_element_seed(EntrySeed(self.0))? {
+            entries.push(entry);
+        }
+        Ok(entries)
+    }
+}
+
+/// A single entry: `("Name", (settings...))`
+struct EntrySeed<'a>(&'a HashMap<String, DeserializeFn>);
+
+impl<'de> DeserializeSeed<'de> for EntrySeed<'_> {
+    type Value = PerfUiLayoutEntry;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_tuple(2, self)
+    }
+}
+
+impl<'de> Visitor<'de> for EntrySeed<'_> {
+    type Value = PerfUiLayoutEntry;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "a Perf UI entry: (\"Name\", (settings...))")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let name: String = seq.next_element()?
+            .ok_or_else(|| A::Error::invalid_length(0, &self))?;
+        let Some(deserialize_fn) = self.0.get(&name) else {
+            return Err(A::Error::custom(format!("unknown Perf UI entry type: {:?}", name)));
+        };
+        let component = seq.next_element_seed(ComponentSeed(*deserialize_fn))?
+            .ok_or_else(|| A::Error::invalid_length(1, &self))?;
+        Ok(PerfUiLayoutEntry {
+            name,
+            component,
+        })
+    }
+}
+
+struct ComponentSeed(DeserializeFn);
+
+impl<'de> DeserializeSeed<'de> for ComponentSeed {
+    type Value = Box<dyn LayoutComponent>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        let mut erased = <dyn erased_serde::Deserializer>::erase(deserializer);
+        (self.0)(&mut erased).map_err(D::Error::custom)
+    }
+}
+
+pub(crate) fn plugin(app: &mut App) {
+    let registry = app.world_mut()
+        .get_resource_or_init::<PerfUiLayoutRegistry>()
+        .clone();
+    app.init_asset::<PerfUiLayout>();
+    app.register_asset_loader(PerfUiLayoutLoader { registry });
+    app.add_systems(Update,
+        apply_perf_ui_layouts
+            .run_if(any_with_component::<PerfUiLayoutHandle>)
+            .before(crate::theme::apply_perf_ui_theme)
+            .before(crate::PerfUiSet::Setup)
+    );
+}
+
+pub(crate) fn apply_perf_ui_layouts(
+    mut commands: Commands,
+    mut evr_asset: EventReader<AssetEvent<PerfUiLayout>>,
+    layouts: Res<Assets<PerfUiLayout>>,
+    mut q_layout: Query<(Entity, Ref<PerfUiLayoutHandle>, &mut PerfUiLayoutState)>,
+) {
+    let mut changed = vec![];
+    for ev in evr_asset.read() {
+        match ev {
+            // rebuild as soon as the layout itself is ready, the fonts can load later
+            AssetEvent::Added { id } | AssetEvent::Modified { id } => {
+                changed.push(*id);
+            }
+            _ => {}
+        }
+    }
+    for (e_root, handle, mut state) in &mut q_layout {
+        if !handle.is_changed() && !changed.contains(&handle.0.id()) {
+            continue;
+        }
+        let Some(layout) = layouts.get(&handle.0) else {
+            continue;
+        };
+        for e_entry in state.entities.drain(..) {
+            commands.entity(e_entry).try_despawn();
+        }
+        commands.entity(e_root).insert(layout.root.clone());
+        for entry in &layout.entries {
+            let mut e_entry = commands.spawn((
+                Name::new(format!("PerfUi Entry: {}", entry.name)),
+                ChildOf(e_root),
+            ));
+            entry.component.insert_into(&mut e_entry);
+            state.entities.push(e_entry.id());
+        }
+    }
+}
@@ -60,6 +60,8 @@
 
 use bevy::prelude::*;
 
+use crate::collect::PerfUiCollectSet;
+
 /// Prelude of common types for users of the library
 pub mod prelude {
     pub use crate::{
@@ -68,16 +70,79 @@ pub mod prelude {
     };
     pub use crate::ui::root::{
         PerfUiRoot,
+        PerfUiEntryHost,
         PerfUiPosition,
+        PerfUiUpdateInterval,
+        PerfUiHighlightDebounce,
+    };
+    pub use crate::benchmark::{
+        PerfUiBenchmark,
+        PerfUiBenchmarkFinished,
+        PerfUiBenchmarkLength,
+    };
+    pub use crate::collect::{
+        PerfUiSnapshot,
+        PerfUiSnapshotRoot,
+        PerfUiSnapshotEntry,
+        PerfUiSnapshotValue,
+    };
+    pub use crate::recorder::{
+        PerfUiRecorder,
+        PerfUiRecordFormat,
+    };
+    pub use crate::text::{
+        PerfUiTextOutput,
+        PerfUiTextTarget,
+    };
+    pub use crate::scope::{
+        PerfUiEntryScope,
+        PerfUiScopes,
+        PerfUiScopeTimings,
+        PerfUiScopeTiming,
+        PerfUiScopeGuard,
+    };
+    pub use crate::perf_ui_scope;
+    pub use crate::entry::{
+        PerfUiHistory,
+        PerfUiClearSamples,
+    };
+    pub use crate::stats::{
+        PerfUiEntryStats,
+        PerfUiStat,
+        PerfUiStatsWindow,
+        PerfUiStatsSamples,
+    };
+    pub use crate::theme::{
+        PerfUiTheme,
+        PerfUiThemeOverrides,
+    };
+    pub use crate::threshold::{
+        PerfUiThresholdEvents,
+        PerfUiThresholdCrossed,
+    };
+    pub use crate::utils::{ColorGradient, ColorGradientPreset};
+    #[cfg(feature = "asset")]
+    pub use crate::layout::{
+        PerfUiLayout,
+        PerfUiLayoutHandle,
     };
-    pub use crate::utils::ColorGradient;
     #[cfg(feature = "entries")]
     pub use crate::entries::prelude::*;
     #[cfg(feature = "widgets")]
     pub use crate::widgets::prelude::*;
 }
 
+pub mod benchmark;
+pub mod collect;
 pub mod entry;
+#[cfg(feature = "asset")]
+pub mod layout;
+pub mod recorder;
+pub mod scope;
+pub mod stats;
+pub mod text;
+pub mod theme;
+pub mod threshold;
 pub mod ui;
 pub mod utils;
 
@@ -92,6 +157,8 @@ pub struct PerfUiPlugin;
 
 impl Plugin for PerfUiPlugin {
     fn build(&self, app: &mut App) {
+        app.add_observer(crate::ui::root::on_add_perf_ui_root);
+        app.add_observer(crate::ui::root::on_insert_perf_ui_child_of);
         app.add_systems(Update, (
             crate::ui::root::setup_perf_ui
                 .run_if(crate::ui::root::rc_setup_perf_ui)
@@ -101,8 +168,92 @@ impl Plugin for PerfUiPlugin {
                 .after(PerfUiSet::Setup),
         )
             .run_if(crate::ui::rc_any_visible)
+            .run_if(crate::ui::rc_ui_enabled)
+        );
+        app.register_type::<crate::ui::root::PerfUiRoot>();
+        app.register_type::<crate::ui::root::PerfUiPosition>();
+        app.register_type::<crate::ui::root::PerfUiUpdateInterval>();
+        app.register_type::<crate::ui::root::PerfUiHighlightDebounce>();
+        app.register_type::<crate::utils::ColorGradient>();
+        app.register_type::<crate::utils::ColorGradientPreset>();
+        app.register_type::<crate::theme::PerfUiTheme>();
+        app.register_type::<crate::theme::PerfUiThemeOverrides>();
+        app.register_type::<crate::stats::PerfUiStat>();
+        app.register_type::<crate::stats::PerfUiStatsWindow>();
+        app.register_type::<crate::scope::PerfUiEntryScope>();
+        app.register_type::<crate::scope::PerfUiScopes>();
+        app.init_resource::<crate::scope::PerfUiScopeTimings>();
+        app.add_perf_ui_simple_entry::<crate::scope::PerfUiEntryScope>();
+        app.add_systems(First, crate::scope::collect_perf_ui_scopes);
+        app.add_systems(Update,
+            crate::scope::spawn_perf_ui_scope_entries
+                .run_if(any_with_component::<crate::scope::PerfUiScopes>)
+                .before(PerfUiSet::Setup)
+        );
+        #[cfg(feature = "asset")]
+        {
+            app.add_perf_ui_layout_entry::<crate::scope::PerfUiEntryScope>("Scope");
+            app.add_perf_ui_layout_entry::<crate::scope::PerfUiScopes>("Scopes");
+        }
+        app.add_systems(Update,
+            crate::theme::apply_perf_ui_theme
+                .run_if(crate::theme::rc_apply_perf_ui_theme)
+                .before(PerfUiSet::Setup)
+        );
+        app.add_event::<crate::entry::PerfUiClearSamples>();
+        app.add_event::<crate::benchmark::PerfUiBenchmarkFinished>();
+        app.add_event::<crate::threshold::PerfUiThresholdCrossed>();
+        app.configure_sets(Update, (
+            PerfUiCollectSet::Prepare,
+            PerfUiCollectSet::Collect,
+            PerfUiCollectSet::Output,
+        )
+            .chain()
+            .in_set(PerfUiSet::Update)
+        );
+        app.add_systems(Update, (
+            crate::collect::reset_perf_ui_collectors
+                .run_if(any_with_component::<crate::collect::PerfUiCollector>),
+            crate::text::tick_perf_ui_text_output
+                .run_if(any_with_component::<crate::text::PerfUiTextOutput>),
+            crate::recorder::tick_perf_ui_recorder
+                .run_if(any_with_component::<crate::recorder::PerfUiRecorder>),
+            crate::benchmark::tick_perf_ui_benchmark
+                .run_if(any_with_component::<crate::benchmark::PerfUiBenchmark>),
+            crate::threshold::tick_perf_ui_threshold_events
+                .run_if(any_with_component::<crate::threshold::PerfUiThresholdEvents>),
+            crate::collect::prepare_perf_ui_snapshot
+                .run_if(resource_exists::<crate::collect::PerfUiSnapshot>),
+        )
+            .chain()
+            .in_set(PerfUiCollectSet::Prepare)
+        );
+        app.add_systems(Update, (
+            crate::text::output_perf_ui_text
+                .run_if(any_with_component::<crate::text::PerfUiTextOutput>),
+            crate::recorder::output_perf_ui_recorder
+                .run_if(any_with_component::<crate::recorder::PerfUiRecorder>),
+            crate::benchmark::output_perf_ui_benchmark
+                .run_if(any_with_component::<crate::benchmark::PerfUiBenchmark>),
+            crate::threshold::output_perf_ui_threshold_events
+                .run_if(any_with_component::<crate::threshold::PerfUiThresholdEvents>),
+            crate::collect::update_perf_ui_snapshot
+                .run_if(resource_exists::<crate::collect::PerfUiSnapshot>),
+        )
+            .in_set(PerfUiCollectSet::Output)
+        );
+
+        app.add_systems(Last,
+            crate::recorder::flush_perf_ui_recorders_on_exit
+                .run_if(on_event::<AppExit>)
+                .run_if(any_with_component::<crate::recorder::PerfUiRecorder>)
         );
 
+        #[cfg(feature = "asset")]
+        if app.is_plugin_added::<bevy::asset::AssetPlugin>() {
+            app.add_plugins(layout::plugin);
+        }
+
         #[cfg(feature = "entries")]
         app.add_plugins(entries::predefined_entries_plugin);
         #[cfg(all(feature = "entries", feature = "widgets"))]
@@ -134,6 +285,38 @@ pub trait PerfUiAppExt {
         self.add_perf_ui_widget::<T, T>();
         self
     }
+
+    /// Record the history of an entry type.
+    ///
+    /// The entry must implement [`PerfUiEntryHistory`](crate::entry::PerfUiEntryHistory).
+    /// The values obtained for display are recorded into the history,
+    /// whichever widget the entry is displayed with.
+    fn add_perf_ui_history<E>(&mut self) -> &mut Self
+    where
+        E: crate::entry::PerfUiEntryHistory,
+        E::Value: Clone;
+
+    /// Add support for displaying statistics of an entry type over time.
+    ///
+    /// This allows the entry to be wrapped in
+    /// [`PerfUiEntryStats`](crate::stats::PerfUiEntryStats), which samples
+    /// its value every frame, and displays it using the "simple" widget.
+    fn add_perf_ui_stats<E>(&mut self) -> &mut Self
+    where
+        E: crate::entry::PerfUiEntry,
+        E::Value: num_traits::NumCast;
+
+    /// Allow an entry (or widget) type to be used in Perf UI layout files.
+    ///
+    /// In [`PerfUiLayout`](crate::layout::PerfUiLayout) assets, entries are
+    /// identified by name. The settings are deserialized using `serde`.
+    ///
+    /// The type must also be registered for display, using `add_perf_ui_widget`
+    /// or `add_perf_ui_simple_entry`.
+    #[cfg(feature = "asset")]
+    fn add_perf_ui_layout_entry<T>(&mut self, name: impl Into<String>) -> &mut Self
+    where
+        T: Component + Clone + serde::de::DeserializeOwned;
 }
 
 impl PerfUiAppExt for App {
@@ -143,17 +326,53 @@ impl PerfUiAppExt for App {
         W: crate::ui::widget::PerfUiWidget<E>
     {
         self.add_systems(Update, (
+            crate::entry::sample_perf_ui_entries::<E, W>
+                .run_if(any_with_component::<W>)
+                // so that entries see this frame's measurements
+                .after(bevy::diagnostic::FrameTimeDiagnosticsPlugin::diagnostic_system)
+                .before(PerfUiSet::Setup)
+                .before(PerfUiSet::Update),
             crate::ui::widget::setup_perf_ui_widget::<E, W>
                 .run_if(crate::ui::widget::rc_setup_perf_ui_widget::<E, W>)
+                .run_if(crate::ui::rc_ui_enabled)
                 .after(crate::ui::root::setup_perf_ui)
                 .in_set(PerfUiSet::Setup),
             crate::ui::widget::update_perf_ui_widget::<E, W>
-                .run_if(any_with_component::<crate::ui::widget::PerfUiWidgetMarker<W>>)
+                .run_if(any_with_component::<W>)
                 .after(crate::ui::widget::setup_perf_ui_widget::<E, W>)
-                .in_set(PerfUiSet::Update),
+                .in_set(PerfUiCollectSet::Collect),
         ));
         self
     }
+
+    fn add_perf_ui_history<E>(&mut self) -> &mut Self
+    where
+        E: crate::entry::PerfUiEntryHistory,
+        E::Value: Clone,
+    {
+        self.init_resource::<crate::entry::PerfUiHistoryRecorder<E>>();
+        self
+    }
+
+    fn add_perf_ui_stats<E>(&mut self) -> &mut Self
+    where
+        E: crate::entry::PerfUiEntry,
+        E::Value: num_traits::NumCast,
+    {
+        self.add_perf_ui_simple_entry::<crate::stats::PerfUiEntryStats<E>>();
+        self
+    }
+
+    #[cfg(feature = "asset")]
+    fn add_perf_ui_layout_entry<T>(&mut self, name: impl Into<String>) -> &mut Self
+    where
+        T: Component + Clone + serde::de::DeserializeOwned,
+    {
+        self.world_mut()
+            .get_resource_or_init::<crate::layout::PerfUiLayoutRegistry>()
+            .register::<T>(name.into());
+        self
+    }
 }
 
 /// System Set to allow you to order things relative to our systems.
@@ -0,0 +1,266 @@
+//! Recording the values of Perf UI entries to a file.
+//!
+//! Add the [`PerfUiRecorder`] component to your Perf UI entity, to write
+//! the raw values of all of its entries to a file (in CSV or JSON Lines format),
+//! every frame or at a configurable interval. This gives you machine-readable
+//! traces, which you can analyze, plot, or compare after a play session.
+//!
+//! ```rust
+//! commands.spawn((
+//!     PerfUiRecorder::new("perf.csv"),
+//!     PerfUiEntryFPS::default(),
+//!     PerfUiEntryFrameTime::default(),
+//!     // ...
+//! ));
+//! ```
+//!
+//! Every record (row/line) contains the time (in seconds since startup),
+//! followed by the values of all the entries, labeled with the entries' labels.
+//! Numeric values are written as numbers (see `PerfUiEntry::value_as_f64`),
+//! other values are written using their `Debug` representation. Missing values
+//! are written as empty fields (CSV) or `null` (JSON).
+//!
+//! The file is created (or truncated) when the recorder starts recording.
+//! Records are buffered, and flushed to the file every `flush_interval`
+//! (default: 1 second), when the app exits, and when the recording is
+//! restarted or the entity is despawned.
+
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::PathBuf;
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use crate::collect::{unique_labels, PerfUiSnapshotEntry, PerfUiCollector};
+use crate::ui::root::{PerfUiRoot, PerfUiUpdateInterval, PerfUiUpdateTimer};
+
+/// The file format to use for recording.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PerfUiRecordFormat {
+    /// Comma-Separated Values, with a header row containing the labels.
+    ///
+    /// If the set of entries changes during recording, a new header row
+    /// is written before the first record with the new entries.
+    #[default]
+    Csv,
+    /// JSON Lines: every record is a JSON object on its own line.
+    JsonLines,
+}
+
+/// Component to record the values of a Perf UI to a file.
+///
+/// Add this to the entity with the `PerfUiRoot` component.
+///
+/// If you change the `path` or `format`, a new recording is started.
+#[derive(Component, Debug, Clone)]
+#[require(PerfUiRoot, PerfUiCollector, PerfUiRecorderState)]
+pub struct PerfUiRecorder {
+    /// The file to write to.
+    pub path: PathBuf,
+    /// The file format.
+    ///
+    /// Default: CSV
+    pub format: PerfUiRecordFormat,
+    /// How often to record the values.
+    ///
+    /// Default: every frame
+    pub interval: PerfUiUpdateInterval,
+    /// How often to flush the recorded data to the file.
+    ///
+    /// Writing to the file in larger batches is cheaper than every frame.
+    /// If your app crashes, up to this much data may be lost.
+    ///
+    /// Default: 1 second
+    pub flush_interval: Duration,
+}
+
+impl PerfUiRecorder {
+    /// Create a recorder that writes CSV to the given file, every frame.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        PerfUiRecorder {
+            path: path.into(),
+            format: default(),
+            interval: default(),
+            flush_interval: Duration::from_secs(1),
+        }
+    }
+
+    /// Set the file format (builder-style API).
+    pub fn with_format(mut self, format: PerfUiRecordFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Set the recording interval (builder-style API).
+    pub fn with_interval(mut self, interval: PerfUiUpdateInterval) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Set the flush interval (builder-style API).
+    pub fn with_flush_interval(mut self, flush_interval: Duration) -> Self {
+        self.flush_interval = flush_interval;
+        self
+    }
+}
+
+#[doc(hidden)]
+#[derive(Component, Default)]
+pub struct PerfUiRecorderState {
+    timer: PerfUiUpdateTimer,
+    due: bool,
+    /// Dropping the `BufWriter` flushes it, so any buffered data is written
+    /// when the state is reset or the entity is despawned.
+    file: Option<BufWriter<File>>,
+    /// Time of the last flush.
+    last_flush: f64,
+    /// Column names of the last CSV header written.
+    columns: Vec<String>,
+    /// Stop trying to record after an I/O error.
+    failed: bool,
+}
+
+pub(crate) fn tick_perf_ui_recorder(
+    time: Res<Time<Real>>,
+    mut q_recorder: Query<(Ref<PerfUiRecorder>, &mut PerfUiRecorderState, &mut PerfUiCollector)>,
+) {
+    let now = time.elapsed_secs();
+    for (recorder, mut state, mut collector) in &mut q_recorder {
+        if recorder.is_changed() {
+            // (re)start recording with the new settings
+            *state = default();
+        }
+        if state.failed {
+            state.due = false;
+            continue;
+        }
+        state.due = state.timer.update_due(recorder.interval, now);
+        collector.due |= state.due;
+    }
+}
+
+pub(crate) fn output_perf_ui_recorder(
+    time: Res<Time<Real>>,
+    mut q_recorder: Query<(&PerfUiRecorder, &mut PerfUiRecorderState, &PerfUiCollector)>,
+) {
+    let now = time.elapsed_secs_f64();
+    for (recorder, mut state, collector) in &mut q_recorder {
+        if !state.due {
+            continue;
+        }
+        let mut result = write_record(recorder, &mut state, now, &collector.rows);
+        if result.is_ok() && now - state.last_flush >= recorder.flush_interval.as_secs_f64() {
+            result = flush_recorder(&mut state, now);
+        }
+        if let Err(e) = result {
+            error!("Perf UI Recorder: cannot write to {:?}: {}", recorder.path, e);
+            state.failed = true;
+            state.file = None;
+        }
+    }
+}
+
+/// Flush all recorders when the app exits, so no buffered data is lost.
+pub(crate) fn flush_perf_ui_recorders_on_exit(
+    time: Res<Time<Real>>,
+    mut q_recorder: Query<(&PerfUiRecorder, &mut PerfUiRecorderState)>,
+) {
+    let now = time.elapsed_secs_f64();
+    for (recorder, mut state) in &mut q_recorder {
+        if let Err(e) = flush_recorder(&mut state, now) {
+            error!("Perf UI Recorder: cannot write to {:?}: {}", recorder.path, e);
+            state.failed = true;
+            state.file = None;
+        }
+    }
+}
+
+fn flush_recorder(state: &mut PerfUiRecorderState, now: f64) -> std::io::Result<()> {
+    if let Some(file) = &mut state.file {
+        file.flush()?;
+    }
+    state.last_flush = now;
+    Ok(())
+}
+
+fn write_record(
+    recorder: &PerfUiRecorder,
+    state: &mut PerfUiRecorderState,
+    now: f64,
+    rows: &[PerfUiSnapshotEntry],
+) -> std::io::Result<()> {
+    if state.file.is_none() {
+        state.file = Some(BufWriter::new(File::create(&recorder.path)?));
+    }
+    let columns = unique_labels(rows, &["time"]);
+    let file = state.file.as_mut().unwrap();
+    match recorder.format {
+        PerfUiRecordFormat::Csv => {
+            if columns != state.columns {
+                write!(file, "time")?;
+                for column in &columns {
+                    write!(file, ",{}", csv_escape(column))?;
+                }
+                writeln!(file)?;
+            }
+            write!(file, "{}", now)?;
+            for row in rows {
+                write!(file, ",")?;
+                if let Some(v) = &row.value {
+                    match v.numeric {
+                        Some(x) => write!(file, "{}", x)?,
+                        None => write!(file, "{}", csv_escape(&v.raw))?,
+                    }
+                }
+            }
+            writeln!(file)?;
+        }
+        PerfUiRecordFormat::JsonLines => {
+            write!(file, "{{\"time\":{}", now)?;
+            for (column, row) in columns.iter().zip(rows) {
+                write!(file, ",{}:", json_string(column))?;
+                match &row.value {
+                    Some(v) => match v.numeric {
+                        Some(x) if x.is_finite() => write!(file, "{}", x)?,
+                        Some(_) => write!(file, "null")?,
+                        None => write!(file, "{}", json_string(&v.raw))?,
+                    },
+                    None => write!(file, "null")?,
+                }
+            }
+            writeln!(file, "}}")?;
+        }
+    }
+    state.columns = columns;
+    Ok(())
+}
+
+fn csv_escape(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_owned()
+    }
+}
+
+fn json_string(s: &str) -> String {
+    use std::fmt::Write as _;
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
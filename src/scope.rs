@@ -0,0 +1,360 @@
+//! Timing scopes in your own code.
+//!
+//! Use the [`perf_ui_scope!`](crate::perf_ui_scope) macro to measure how long
+//! a block of code takes. The time is accumulated per named scope, every frame
+//! (the same scope can run many times per frame, on any thread).
+//!
+//! ```rust
+//! fn pathfinding(/* ... */) {
+//!     perf_ui_scope!("pathfinding");
+//!     // ... the rest of the block is measured ...
+//! }
+//! ```
+//!
+//! To display the scopes, add [`PerfUiScopes`] to your Perf UI. Every scope
+//! will automatically appear as a row, with the minimum, average, and maximum
+//! time per frame, over the last few seconds. If you only want specific
+//! scopes, use [`PerfUiEntryScope`] entries instead.
+//!
+//! The values are also available from the [`PerfUiScopeTimings`] resource.
+
+use std::collections::VecDeque;
+use std::sync::{Mutex, PoisonError};
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy::ecs::system::lifetimeless::SRes;
+use bevy::ecs::system::SystemParam;
+use bevy::platform::time::Instant;
+
+use crate::prelude::*;
+use crate::entry::PerfUiEntry;
+use crate::utils::{format_pretty_float, next_sort_key};
+
+/// Time spent in each scope since the last frame, not yet collected.
+static PENDING: Mutex<Vec<(&'static str, Duration)>> = Mutex::new(Vec::new());
+
+/// Measure the time until the end of the current block, as a named scope.
+///
+/// See the [module documentation](crate::scope) for more info.
+///
+/// ```rust
+/// perf_ui_scope!("pathfinding");
+/// ```
+#[macro_export]
+macro_rules! perf_ui_scope {
+    ($name:expr) => {
+        let _perf_ui_scope_guard = $crate::scope::PerfUiScopeGuard::new($name);
+    };
+}
+
+/// Add time to a named scope, measured by other means.
+///
+/// Like [`PerfUiScopeGuard`], but if you already know the duration.
+pub fn record_perf_ui_scope(name: &'static str, elapsed: Duration) {
+    let mut pending = PENDING.lock().unwrap_or_else(PoisonError::into_inner);
+    match pending.iter_mut().find(|(n, _)| *n == name) {
+        Some((_, total)) => *total += elapsed,
+        None => pending.push((name, elapsed)),
+    }
+}
+
+/// Measures the time from its creation until it is dropped, as a named scope.
+///
+/// Usually created using the [`perf_ui_scope!`](crate::perf_ui_scope) macro.
+#[must_use = "the time is measured until the guard is dropped"]
+pub struct PerfUiScopeGuard {
+    name: &'static str,
+    start: Instant,
+}
+
+impl PerfUiScopeGuard {
+    /// Start measuring a scope.
+    pub fn new(name: &'static str) -> Self {
+        PerfUiScopeGuard {
+            name,
+            start: Instant::now(),
+        }
+    }
+}
+
+impl Drop for PerfUiScopeGuard {
+    fn drop(&mut self) {
+        record_perf_ui_scope(self.name, self.start.elapsed());
+    }
+}
+
+/// Resource with the recent times of all the scopes measured so far.
+///
+/// Updated every frame, in `First`. The time of each scope in every frame is
+/// the sum of all the times it ran since the previous frame (zero, if it
+/// did not run).
+#[derive(Resource, Debug)]
+pub struct PerfUiScopeTimings {
+    /// How long to keep the times, for computing the min/avg/max.
+    ///
+    /// Default: 5 seconds
+    pub window: Duration,
+    scopes: Vec<PerfUiScopeSamples>,
+}
+
+#[derive(Debug)]
+struct PerfUiScopeSamples {
+    name: &'static str,
+    /// The time per frame (in milliseconds), with the time it was collected.
+    samples: VecDeque<(Duration, f64)>,
+}
+
+/// The min/avg/max time per frame of a scope, in milliseconds.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PerfUiScopeTiming {
+    /// The shortest time in a frame.
+    pub min: f64,
+    /// The average time per frame.
+    pub avg: f64,
+    /// The longest time in a frame.
+    pub max: f64,
+}
+
+impl Default for PerfUiScopeTimings {
+    fn default() -> Self {
+        PerfUiScopeTimings {
+            window: Duration::from_secs(5),
+            scopes: Vec::new(),
+        }
+    }
+}
+
+impl PerfUiScopeTimings {
+    /// The names of all the scopes measured so far, in the order they were first seen.
+    pub fn names(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.scopes.iter().map(|scope| scope.name)
+    }
+
+    /// Get the min/avg/max time per frame of a scope, within the window.
+    pub fn get(&self, name: &str) -> Option<PerfUiScopeTiming> {
+        let scope = self.scopes.iter().find(|scope| scope.name == name)?;
+        if scope.samples.is_empty() {
+            return None;
+        }
+        let values = || scope.samples.iter().map(|(_, v)| *v);
+        Some(PerfUiScopeTiming {
+            min: values().reduce(f64::min)?,
+            avg: values().sum::<f64>() / scope.samples.len() as f64,
+            max: values().reduce(f64::max)?,
+        })
+    }
+}
+
+pub(crate) fn collect_perf_ui_scopes(
+    time: Res<Time<Real>>,
+    mut timings: ResMut<PerfUiScopeTimings>,
+) {
+    let pending = std::mem::take(&mut *PENDING.lock().unwrap_or_else(PoisonError::into_inner));
+    let timings = &mut *timings;
+    for (name, _) in &pending {
+        if !timings.scopes.iter().any(|scope| scope.name == *name) {
+            timings.scopes.push(PerfUiScopeSamples {
+                name,
+                samples: VecDeque::new(),
+            });
+        }
+    }
+    let now = time.elapsed();
+    for scope in &mut timings.scopes {
+        while scope.samples.front().is_some_and(|(t, _)| now.saturating_sub(*t) > timings.window) {
+            scope.samples.pop_front();
+        }
+        let elapsed = pending.iter()
+            .find(|(name, _)| *name == scope.name)
+            .map(|(_, elapsed)| *elapsed)
+            .unwrap_or_default();
+        scope.samples.push_back((now, elapsed.as_secs_f64() * 1000.0));
+    }
+}
+
+/// Perf UI Entry to display the time spent in a scope measured using
+/// [`perf_ui_scope!`](crate::perf_ui_scope).
+///
+/// Displays the minimum, average, and maximum time per frame (in that
+/// order), over the window of [`PerfUiScopeTimings`], in *milliseconds*.
+///
+/// The color and highlight are based on the average.
+#[derive(Component, Debug, Clone, Reflect)]
+#[require(PerfUiRoot)]
+#[reflect(Component, Default, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct PerfUiEntryScope {
+    /// Custom label. If empty (default), the name of the scope will be used.
+    pub label: String,
+    /// The name of the scope (as given to `perf_ui_scope!`).
+    pub name: String,
+    /// Display the unit ("ms") alongside the numbers.
+    ///
+    /// Default: `true`
+    pub display_units: bool,
+    /// Enable color based on value.
+    ///
+    /// To disable (always use default color), set to empty `ColorGradient::default()`.
+    ///
+    /// Default: Green-Yellow-Red gradient (or the default `ColorGradientPreset`, reversed) between the frametimes equivalent to 120-60-30 FPS.
+    pub color_gradient: ColorGradient,
+    /// Highlight the value if above this threshold.
+    ///
+    /// Default: frametime equivalent to 20 FPS
+    pub threshold_highlight: Option<f32>,
+    /// Number of digits to display for the integer (whole number) part.
+    ///
+    /// Default: `2`
+    pub digits: u8,
+    /// Number of digits to display for the fractional (after the decimal point) part.
+    ///
+    /// Default: `2` (so that the three numbers fit in the Perf UI's values column)
+    pub precision: u8,
+    /// Update the displayed value at this interval, instead of the Perf UI's.
+    ///
+    /// Default: `None` (use the `update_interval` of the `PerfUiRoot`)
+    pub update_interval: Option<PerfUiUpdateInterval>,
+    /// Sort Key (control where the entry will appear in the Perf UI).
+    pub sort_key: i32,
+}
+
+impl Default for PerfUiEntryScope {
+    fn default() -> Self {
+        PerfUiEntryScope {
+            label: String::new(),
+            name: String::new(),
+            display_units: true,
+            color_gradient: ColorGradient::new_preset_default_reversed(
+                1000.0 / 120.0,
+                1000.0 / 60.0,
+                1000.0 / 30.0,
+            ).unwrap(),
+            threshold_highlight: Some(1000.0 / 20.0),
+            digits: 2,
+            precision: 2,
+            update_interval: None,
+            sort_key: next_sort_key(),
+        }
+    }
+}
+
+impl PerfUiEntryScope {
+    /// Create a new entry for the given scope, with default settings
+    pub fn new(name: impl Into<String>) -> Self {
+        PerfUiEntryScope {
+            name: name.into(),
+            ..default()
+        }
+    }
+}
+
+impl PerfUiEntry for PerfUiEntryScope {
+    type SystemParam = SRes<PerfUiScopeTimings>;
+    type Value = PerfUiScopeTiming;
+
+    fn label(&self) -> &str {
+        if self.label.is_empty() {
+            &self.name
+        } else {
+            &self.label
+        }
+    }
+    fn update_value(
+        &self,
+        timings: &mut <Self::SystemParam as SystemParam>::Item<'_, '_>,
+    ) -> Option<Self::Value> {
+        timings.get(&self.name)
+    }
+    fn format_value(
+        &self,
+        value: &Self::Value,
+    ) -> String {
+        let mut s = format!(
+            "{}/{}/{}",
+            format_pretty_float(self.digits, self.precision, value.min),
+            format_pretty_float(self.digits, self.precision, value.avg),
+            format_pretty_float(self.digits, self.precision, value.max),
+        );
+        if self.display_units {
+            s.push_str(" ms");
+        }
+        s
+    }
+    fn value_color(
+        &self,
+        value: &Self::Value,
+    ) -> Option<Color> {
+        self.color_gradient.get_color_for_value(value.avg as f32)
+    }
+    fn value_highlight(
+        &self,
+        value: &Self::Value,
+    ) -> bool {
+        self.threshold_highlight
+            .map(|t| (value.avg as f32) > t)
+            .unwrap_or(false)
+    }
+    fn highlight_threshold(&self, _value: &Self::Value) -> Option<f64> {
+        self.threshold_highlight.map(|t| t as f64)
+    }
+    fn value_as_f64(
+        &self,
+        value: &Self::Value,
+    ) -> Option<f64> {
+        Some(value.avg)
+    }
+    fn update_interval(&self) -> Option<PerfUiUpdateInterval> {
+        self.update_interval
+    }
+    fn sort_key(&self) -> i32 {
+        self.sort_key
+    }
+}
+
+/// Component to display all the scopes measured using
+/// [`perf_ui_scope!`](crate::perf_ui_scope) in a Perf UI.
+///
+/// Add this to the entity with the `PerfUiRoot` component. For every scope,
+/// a [`PerfUiEntryScope`] is spawned as a child entity, as soon as the scope
+/// has been measured for the first time.
+#[derive(Component, Debug, Clone, Default, Reflect)]
+#[require(PerfUiRoot)]
+#[reflect(Component, Default, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct PerfUiScopes {
+    /// The settings for the entries. Its `label` and `name` are ignored.
+    ///
+    /// All the entries have its `sort_key`, and appear in the
+    /// order in which the scopes were first measured.
+    pub template: PerfUiEntryScope,
+}
+
+pub(crate) fn spawn_perf_ui_scope_entries(
+    mut commands: Commands,
+    timings: Res<PerfUiScopeTimings>,
+    q_root: Query<(Entity, &PerfUiScopes, Option<&Children>)>,
+    q_entry: Query<&PerfUiEntryScope>,
+) {
+    for (e_root, scopes, children) in &q_root {
+        for name in timings.names() {
+            let exists = children.into_iter()
+                .flatten()
+                .filter_map(|e| q_entry.get(*e).ok())
+                .any(|entry| entry.name == name);
+            if exists {
+                continue;
+            }
+            commands.spawn((
+                PerfUiEntryScope {
+                    label: String::new(),
+                    name: name.to_owned(),
+                    ..scopes.template.clone()
+                },
+                ChildOf(e_root),
+            ));
+        }
+    }
+}
@@ -0,0 +1,407 @@
+//! Statistics of the values of any Perf UI entry, over time.
+//!
+//! Wrap any entry with a numeric value in [`PerfUiEntryStats`], to display
+//! the minimum, maximum, mean, or a percentile of its values over a recent
+//! time window or over the whole session, instead of its current value.
+//!
+//! ```rust
+//! commands.spawn((
+//!     // worst Render GPU Time in the last 5 seconds
+//!     PerfUiEntryStats::new(PerfUiEntryRenderGpuTime::default())
+//!         .with_stat(PerfUiStat::Max)
+//!         .with_window(PerfUiStatsWindow::Seconds(5.0)),
+//!     // peak RAM usage this session
+//!     PerfUiEntryStats::new(PerfUiEntryMemUsage::default())
+//!         .with_stat(PerfUiStat::Max)
+//!         .with_window(PerfUiStatsWindow::Session),
+//! ));
+//! ```
+//!
+//! The resulting value is formatted, colored and highlighted by the wrapped
+//! entry, as if it was the entry's own value.
+//!
+//! To display multiple statistics of the same entry type, put them on
+//! child entities of the Perf UI entity.
+//!
+//! The built-in entries with numeric values are supported out of the box.
+//! To use your own entry types, register them using
+//! `app.add_perf_ui_stats::<E>()`.
+
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy::ecs::system::SystemParam;
+use num_traits::{NumCast, ToPrimitive};
+
+use crate::entry::{PerfUiEntry, PerfUiEntryDisplayRange};
+use crate::ui::root::{PerfUiRoot, PerfUiUpdateInterval, PerfUiHighlightDebounce};
+
+/// Which statistic should [`PerfUiEntryStats`] display?
+#[derive(Debug, Default, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Debug, Default, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PerfUiStat {
+    /// The lowest value.
+    Min,
+    /// The highest value.
+    Max,
+    /// The average (arithmetic mean) of all values.
+    #[default]
+    Mean,
+    /// The given percentile (`0.0`-`100.0`) of the values.
+    ///
+    /// For example, `Percentile(99.0)` is the value that
+    /// 99% of the values are lower than or equal to.
+    Percentile(f32),
+}
+
+/// Which values should [`PerfUiEntryStats`] compute statistics over?
+#[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Debug, Default, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PerfUiStatsWindow {
+    /// The values from the last N seconds (of real time).
+    Seconds(f32),
+    /// All values since the app started (or since `PerfUiEntryStats::reset`).
+    ///
+    /// For `PerfUiStat::Percentile`, all the values have to be kept in
+    /// memory (sorted), so it will use more memory the longer the app runs.
+    /// Each new value is inserted in order, which costs a little more CPU
+    /// time as the number of values grows.
+    Session,
+}
+
+impl Default for PerfUiStatsWindow {
+    fn default() -> Self {
+        PerfUiStatsWindow::Seconds(5.0)
+    }
+}
+
+/// Perf UI Entry to display statistics of another entry's values over time.
+///
+/// This struct wraps the entry type, which will be the source of the
+/// values. The wrapped entry is sampled every frame (even if the Perf UI
+/// is hidden), regardless of its update interval.
+///
+/// Only the settings are serialized/reflected, not the collected values.
+#[derive(Component, Clone, Reflect)]
+#[require(PerfUiRoot)]
+#[reflect(Component)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[cfg_attr(feature = "serde", serde(bound(deserialize = "E: serde::Deserialize<'de> + Default, E::Value: NumCast")))]
+pub struct PerfUiEntryStats<E: PerfUiEntry> {
+    /// Custom label. If empty (default), the wrapped entry's label
+    /// will be used, with the statistic and window appended.
+    pub label: String,
+    /// Which statistic to display.
+    ///
+    /// Default: `Mean`
+    pub stat: PerfUiStat,
+    /// Which values to compute the statistic over.
+    ///
+    /// Default: the last 5 seconds
+    pub window: PerfUiStatsWindow,
+    /// The entry (source of the values).
+    pub entry: E,
+    /// The values collected so far.
+    ///
+    /// Default: empty
+    #[reflect(ignore)]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub samples: PerfUiStatsSamples,
+}
+
+impl<E: PerfUiEntry> PerfUiEntryStats<E>
+where
+    E::Value: NumCast,
+{
+    /// Create a new stats entry with default settings.
+    pub fn new(entry: E) -> Self {
+        PerfUiEntryStats {
+            label: String::new(),
+            stat: default(),
+            window: default(),
+            entry,
+            samples: default(),
+        }
+    }
+
+    /// Set the statistic to display (builder-style API).
+    pub fn with_stat(mut self, stat: PerfUiStat) -> Self {
+        self.stat = stat;
+        self
+    }
+
+    /// Set the window to compute the statistic over (builder-style API).
+    pub fn with_window(mut self, window: PerfUiStatsWindow) -> Self {
+        self.window = window;
+        self
+    }
+
+    /// Set a custom label (builder-style API).
+    pub fn with_label(mut self, label: impl Into<String>) -> Self {
+        self.label = label.into();
+        self
+    }
+
+    /// Discard all the values collected so far.
+    ///
+    /// Useful with `PerfUiStatsWindow::Session`, to start over.
+    pub fn reset(&mut self) {
+        self.samples.clear();
+    }
+
+    fn default_label(&self) -> String {
+        let stat = match self.stat {
+            PerfUiStat::Min => "min".to_owned(),
+            PerfUiStat::Max => "max".to_owned(),
+            PerfUiStat::Mean => "avg".to_owned(),
+            PerfUiStat::Percentile(p) => format!("p{}", p),
+        };
+        match self.window {
+            PerfUiStatsWindow::Seconds(s) => format!("{} ({}, {}s)", self.entry.label(), stat, s),
+            PerfUiStatsWindow::Session => format!("{} ({})", self.entry.label(), stat),
+        }
+    }
+}
+
+impl<E> Default for PerfUiEntryStats<E>
+where
+    E: PerfUiEntry + Default,
+    E::Value: NumCast,
+{
+    fn default() -> Self {
+        Self::new(E::default())
+    }
+}
+
+/// The values collected by entries that display statistics over time,
+/// such as [`PerfUiEntryStats`].
+///
+/// The framework records new values every frame. This is only meant to be
+/// stored in entries, it is not serialized/reflected.
+#[derive(Debug, Default, Clone)]
+pub struct PerfUiStatsSamples {
+    /// The entry's default label, generated from its settings.
+    pub(crate) auto_label: String,
+    /// The label of the wrapped entry that `auto_label` was generated from.
+    ///
+    /// Only for entries that wrap another entry.
+    pub(crate) source_label: String,
+    /// The settings the values were collected for.
+    settings: Option<(PerfUiStat, PerfUiStatsWindow)>,
+    /// The values within the window, with the time they were sampled.
+    ///
+    /// Only for `PerfUiStatsWindow::Seconds`.
+    values: VecDeque<(Duration, f64)>,
+    /// The values within the window, in ascending order.
+    ///
+    /// Only for `PerfUiStat::Percentile`.
+    sorted: Vec<f64>,
+    /// Running totals for `PerfUiStatsWindow::Session`.
+    min: f64,
+    max: f64,
+    sum: f64,
+    count: u64,
+}
+
+impl PerfUiStatsSamples {
+    /// Discard all the values collected so far.
+    pub fn clear(&mut self) {
+        *self = PerfUiStatsSamples {
+            auto_label: std::mem::take(&mut self.auto_label),
+            source_label: std::mem::take(&mut self.source_label),
+            ..default()
+        };
+    }
+
+    /// Record a new value.
+    ///
+    /// Returns `true` if the settings have changed, and
+    /// the values collected so far have been discarded.
+    pub(crate) fn record(&mut self, stat: PerfUiStat, window: PerfUiStatsWindow, now: Duration, value: Option<f64>) -> bool {
+        let restarted = self.settings != Some((stat, window));
+        if restarted {
+            // start over with the new settings
+            self.clear();
+            self.settings = Some((stat, window));
+        }
+        let value = value.filter(|v| !v.is_nan());
+        match window {
+            PerfUiStatsWindow::Seconds(s) => {
+                let window = Duration::try_from_secs_f32(s.max(0.0)).unwrap_or(Duration::MAX);
+                while let Some((t, old)) = self.values.front().copied() {
+                    if now.saturating_sub(t) <= window {
+                        break;
+                    }
+                    self.values.pop_front();
+                    if let PerfUiStat::Percentile(_) = stat {
+                        self.remove_sorted(old);
+                    }
+                }
+                if let Some(value) = value {
+                    self.values.push_back((now, value));
+                    if let PerfUiStat::Percentile(_) = stat {
+                        self.insert_sorted(value);
+                    }
+                }
+            }
+            PerfUiStatsWindow::Session => {
+                let Some(value) = value else {
+                    return restarted;
+                };
+                if self.count == 0 {
+                    self.min = value;
+                    self.max = value;
+                } else {
+                    self.min = self.min.min(value);
+                    self.max = self.max.max(value);
+                }
+                self.sum += value;
+                self.count += 1;
+                if let PerfUiStat::Percentile(_) = stat {
+                    self.insert_sorted(value);
+                }
+            }
+        }
+        restarted
+    }
+
+    fn insert_sorted(&mut self, value: f64) {
+        let i = self.sorted.partition_point(|v| v.total_cmp(&value).is_lt());
+        self.sorted.insert(i, value);
+    }
+
+    fn remove_sorted(&mut self, value: f64) {
+        let i = self.sorted.partition_point(|v| v.total_cmp(&value).is_lt());
+        if self.sorted.get(i).is_some_and(|v| v.total_cmp(&value).is_eq()) {
+            self.sorted.remove(i);
+        }
+    }
+
+    pub(crate) fn compute(&self, stat: PerfUiStat, window: PerfUiStatsWindow) -> Option<f64> {
+        if self.settings != Some((stat, window)) {
+            return None;
+        }
+        if let (PerfUiStatsWindow::Session, PerfUiStat::Min | PerfUiStat::Max | PerfUiStat::Mean) = (window, stat) {
+            if self.count == 0 {
+                return None;
+            }
+            return Some(match stat {
+                PerfUiStat::Min => self.min,
+                PerfUiStat::Max => self.max,
+                _ => self.sum / self.count as f64,
+            });
+        }
+        if let PerfUiStat::Percentile(p) = stat {
+            if self.sorted.is_empty() {
+                return None;
+            }
+            // nearest-rank method
+            let rank = (p.clamp(0.0, 100.0) as f64 / 100.0 * self.sorted.len() as f64).ceil() as usize;
+            return Some(self.sorted[rank.clamp(1, self.sorted.len()) - 1]);
+        }
+        if self.values.is_empty() {
+            return None;
+        }
+        let values = self.values.iter().map(|(_, v)| *v);
+        match stat {
+            PerfUiStat::Min => values.reduce(f64::min),
+            PerfUiStat::Max => values.reduce(f64::max),
+            _ => Some(values.sum::<f64>() / self.values.len() as f64),
+        }
+    }
+}
+
+impl<E: PerfUiEntry> PerfUiEntry for PerfUiEntryStats<E>
+where
+    E::Value: NumCast,
+{
+    type SystemParam = E::SystemParam;
+    type Value = E::Value;
+
+    fn label(&self) -> &str {
+        if !self.label.is_empty() {
+            &self.label
+        } else if !self.samples.auto_label.is_empty() {
+            &self.samples.auto_label
+        } else {
+            self.entry.label()
+        }
+    }
+    fn sort_key(&self) -> i32 {
+        self.entry.sort_key()
+    }
+    fn update_interval(&self) -> Option<PerfUiUpdateInterval> {
+        self.entry.update_interval()
+    }
+    fn update_value(
+        &self,
+        _: &mut <Self::SystemParam as SystemParam>::Item<'_, '_>,
+    ) -> Option<Self::Value> {
+        self.samples.compute(self.stat, self.window)
+            .and_then(NumCast::from)
+    }
+    fn sample(
+        &mut self,
+        param: &mut <Self::SystemParam as SystemParam>::Item<'_, '_>,
+        now: Duration,
+    ) {
+        self.entry.sample(param, now);
+        let value = self.entry.update_value(param)
+            .and_then(|v| v.to_f64());
+        let restarted = self.samples.record(self.stat, self.window, now, value);
+        if restarted || self.samples.source_label != self.entry.label() {
+            self.samples.source_label = self.entry.label().to_owned();
+            self.samples.auto_label = self.default_label();
+        }
+    }
+    fn clear_samples(&mut self) {
+        self.entry.clear_samples();
+        self.samples.clear();
+    }
+    fn format_value(
+        &self,
+        value: &Self::Value,
+    ) -> String {
+        self.entry.format_value(value)
+    }
+    fn value_color(
+        &self,
+        value: &Self::Value,
+    ) -> Option<Color> {
+        self.entry.value_color(value)
+    }
+    fn value_highlight(
+        &self,
+        value: &Self::Value,
+    ) -> bool {
+        self.entry.value_highlight(value)
+    }
+    fn highlight_threshold(&self, value: &Self::Value) -> Option<f64> {
+        self.entry.highlight_threshold(value)
+    }
+    fn highlight_debounce(&self) -> Option<PerfUiHighlightDebounce> {
+        self.entry.highlight_debounce()
+    }
+    fn value_as_f64(
+        &self,
+        value: &Self::Value,
+    ) -> Option<f64> {
+        self.entry.value_as_f64(value)
+    }
+}
+
+impl<E: PerfUiEntryDisplayRange> PerfUiEntryDisplayRange for PerfUiEntryStats<E>
+where
+    E::Value: NumCast,
+{
+    fn max_value_hint(&self) -> Option<Self::Value> {
+        self.entry.max_value_hint()
+    }
+    fn min_value_hint(&self) -> Option<Self::Value> {
+        self.entry.min_value_hint()
+    }
+}
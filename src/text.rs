@@ -0,0 +1,145 @@
+//! Plain-text output for Perf UIs.
+//!
+//! Instead of (or in addition to) displaying a Perf UI using Bevy UI,
+//! the values of all of its entries can be reported as a plain-text table,
+//! at a configurable interval. This works without any UI or rendering,
+//! so it is useful for headless apps (dedicated servers, CI runs, etc.)
+//! using `MinimalPlugins`.
+//!
+//! To use it, add the [`PerfUiTextOutput`] component to your Perf UI entity:
+//!
+//! ```rust
+//! commands.spawn((
+//!     PerfUiTextOutput::default(),
+//!     PerfUiEntryFPS::default(),
+//!     PerfUiEntryFrameTime::default(),
+//!     // ...
+//! ));
+//! ```
+//!
+//! All entry types registered using `app.add_perf_ui_widget` are supported,
+//! as long as the widget provides access to its entry via `PerfUiWidget::entry`.
+
+use std::sync::Arc;
+
+use bevy::prelude::*;
+
+use crate::collect::{PerfUiSnapshotEntry, PerfUiCollector};
+use crate::ui::root::{PerfUiRoot, PerfUiUpdateInterval, PerfUiUpdateTimer};
+
+/// Where should the text output of a Perf UI be sent?
+#[derive(Clone, Default)]
+pub enum PerfUiTextTarget {
+    /// Log the text using `bevy_log`, at the `INFO` level.
+    #[default]
+    Log,
+    /// Call a custom function, with the Perf UI entity and the text.
+    Callback(Arc<dyn Fn(Entity, &str) + Send + Sync>),
+}
+
+impl std::fmt::Debug for PerfUiTextTarget {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PerfUiTextTarget::Log => write!(f, "Log"),
+            PerfUiTextTarget::Callback(_) => write!(f, "Callback"),
+        }
+    }
+}
+
+/// Component to output a Perf UI as plain text.
+///
+/// Add this to the entity with the `PerfUiRoot` component.
+///
+/// At the given interval, a table of all the entries of the Perf UI
+/// is produced and sent to the target. Every row contains the entry's
+/// label and formatted value. Highlighted values are marked with `(!)`.
+#[derive(Component, Debug, Clone)]
+#[require(PerfUiRoot, PerfUiCollector, PerfUiTextOutputState)]
+pub struct PerfUiTextOutput {
+    /// How often to output the text.
+    ///
+    /// Default: every second
+    pub interval: PerfUiUpdateInterval,
+    /// Where to send the text.
+    ///
+    /// Default: log it using `bevy_log`
+    pub target: PerfUiTextTarget,
+}
+
+impl Default for PerfUiTextOutput {
+    fn default() -> Self {
+        PerfUiTextOutput {
+            interval: PerfUiUpdateInterval::Seconds(1.0),
+            target: default(),
+        }
+    }
+}
+
+impl PerfUiTextOutput {
+    /// Create a text output that calls a custom function (builder-style API).
+    pub fn with_callback(mut self, f: impl Fn(Entity, &str) + Send + Sync + 'static) -> Self {
+        self.target = PerfUiTextTarget::Callback(Arc::new(f));
+        self
+    }
+}
+
+#[doc(hidden)]
+#[derive(Component, Default)]
+pub struct PerfUiTextOutputState {
+    timer: PerfUiUpdateTimer,
+    due: bool,
+}
+
+pub(crate) fn tick_perf_ui_text_output(
+    time: Res<Time<Real>>,
+    mut q_output: Query<(&PerfUiTextOutput, &mut PerfUiTextOutputState, &mut PerfUiCollector)>,
+) {
+    let now = time.elapsed_secs();
+    for (output, mut state, mut collector) in &mut q_output {
+        state.due = state.timer.update_due(output.interval, now);
+        collector.due |= state.due;
+    }
+}
+
+pub(crate) fn output_perf_ui_text(
+    q_output: Query<(Entity, &PerfUiRoot, &PerfUiTextOutput, &PerfUiTextOutputState, &PerfUiCollector, Option<&Name>)>,
+) {
+    for (e_root, root, output, state, collector, name) in &q_output {
+        if !state.due {
+            continue;
+        }
+        let text = format_table(e_root, name, root, &collector.rows);
+        match &output.target {
+            PerfUiTextTarget::Log => info!("{}", text),
+            PerfUiTextTarget::Callback(f) => f(e_root, &text),
+        }
+    }
+}
+
+fn format_table(e_root: Entity, name: Option<&Name>, root: &PerfUiRoot, rows: &[PerfUiSnapshotEntry]) -> String {
+    use std::fmt::Write;
+
+    let label_width = rows.iter()
+        .map(|row| row.label.chars().count())
+        .max().unwrap_or(0);
+    let value_width = rows.iter()
+        .map(|row| row.formatted_or(&root.text_err).chars().count())
+        .max().unwrap_or(0);
+
+    let mut text = if let Some(name) = name {
+        format!("Perf UI {} ({}):", name, e_root)
+    } else {
+        format!("Perf UI ({}):", e_root)
+    };
+    for row in rows {
+        let _ = write!(
+            text, "\n  {:<lw$}  {:>vw$}",
+            row.label, row.formatted_or(&root.text_err),
+            lw = label_width, vw = value_width,
+        );
+        if row.value.as_ref().is_some_and(|v| v.highlight) {
+            text.push_str(" (!)");
+        }
+    }
+    text
+}
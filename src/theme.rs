@@ -0,0 +1,419 @@
+//! Themes: styling shared by all Perf UIs.
+//!
+//! Insert the [`PerfUiTheme`] resource to style all Perf UIs at once,
+//! instead of configuring the colors, fonts and spacing of every `PerfUiRoot`:
+//!
+//! ```rust
+//! app.insert_resource(PerfUiTheme::light());
+//! ```
+//!
+//! Presets are available for dark (the default look), light, high contrast,
+//! and colorblind-safe themes. You can also construct your own.
+//!
+//! The theme replaces the styling settings of every `PerfUiRoot`. To keep
+//! your own values for some settings of a Perf UI, add a
+//! [`PerfUiThemeOverrides`] component to it, and mark those settings:
+//!
+//! ```rust
+//! commands.spawn((
+//!     PerfUiRoot {
+//!         label_color: Color::srgb(0.5, 0.75, 1.0),
+//!         ..default()
+//!     },
+//!     PerfUiThemeOverrides {
+//!         label_color: true,
+//!         ..default()
+//!     },
+//!     // ...
+//! ));
+//! ```
+//!
+//! The theme also recolors entries' value gradients: the default gradients
+//! (created with `ColorGradient::new_preset_default`, whichever
+//! `ColorGradientPreset` is the default) use the theme's colors instead.
+//! Gradients you have created or modified yourself are kept.
+//! Set `gradients` in `PerfUiThemeOverrides` to keep all of them as they are.
+//!
+//! Modifying or replacing the resource at runtime restyles all Perf UIs.
+//! If the resource is removed, Perf UIs keep the style of the last theme.
+//!
+//! Gradients are found using reflection, so this works for any entry or
+//! widget type that is registered in the type registry
+//! (`app.register_type::<T>()`) and reflects `Component`.
+
+use std::any::TypeId;
+
+use bevy::prelude::*;
+use bevy::ecs::component::ComponentId;
+use bevy::reflect::ReflectMut;
+
+use crate::ui::root::{PerfUiEntryHost, PerfUiRoot};
+use crate::utils::ColorGradient;
+
+/// Resource to style all Perf UIs.
+///
+/// See the [module-level documentation](self) for more info.
+///
+/// The fields correspond to those of [`PerfUiRoot`], plus the colors
+/// to use for entries' value gradients.
+#[derive(Resource, Debug, Clone, PartialEq, Reflect)]
+#[reflect(Resource, Default, Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct PerfUiTheme {
+    /// The color to use for the background of the Perf UI.
+    pub background_color: Color,
+    /// The color to use for the background of each entry/row.
+    pub inner_background_color: Color,
+    /// The color to use for the background of highlighted entries.
+    pub inner_background_color_highlight: Color,
+    /// The color for the error text.
+    pub err_color: Color,
+    /// The color to use for entries that do not provide a custom color.
+    pub default_value_color: Color,
+    /// The color to use for label text.
+    pub label_color: Color,
+    /// The font to use for labels.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub font_label: Handle<Font>,
+    /// The font to use for values.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub font_value: Handle<Font>,
+    /// The font to use for highlighted values.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub font_highlight: Handle<Font>,
+    /// The font size for labels.
+    pub fontsize_label: f32,
+    /// The font size for values.
+    pub fontsize_value: f32,
+    /// Distance from the edge of the screen in pixels
+    pub margin: f32,
+    /// Empty space around the edge of the Perf UI
+    pub padding: f32,
+    /// Empty space around entries (rows) in pixels
+    pub inner_margin: f32,
+    /// Empty space around the text in every row
+    pub inner_padding: f32,
+    /// The width (in pixels) of the values column
+    pub values_col_width: f32,
+    /// Color of the default gradients for good values.
+    pub gradient_good: Color,
+    /// Color of the default gradients for middling values.
+    pub gradient_mid: Color,
+    /// Color of the default gradients for bad values.
+    pub gradient_bad: Color,
+}
+
+/// Component to keep some settings of a Perf UI from following the [`PerfUiTheme`].
+///
+/// Add it to the entity with the `PerfUiRoot`. Settings marked `true` keep
+/// the values from the `PerfUiRoot`, the others are set by the theme.
+/// Use [`PerfUiThemeOverrides::all`] to opt the Perf UI out of the theme.
+/// For a Perf UI built from a layout asset, add it to the entity with
+/// the `PerfUiLayoutHandle`.
+///
+/// See the [module-level documentation](self) for more info.
+#[derive(Component, Debug, Clone, Copy, Default, PartialEq, Eq, Reflect)]
+#[reflect(Component, Default, Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct PerfUiThemeOverrides {
+    /// Keep the root's `background_color`.
+    pub background_color: bool,
+    /// Keep the root's `inner_background_color`.
+    pub inner_background_color: bool,
+    /// Keep the root's `inner_background_color_highlight`.
+    pub inner_background_color_highlight: bool,
+    /// Keep the root's `err_color`.
+    pub err_color: bool,
+    /// Keep the root's `default_value_color`.
+    pub default_value_color: bool,
+    /// Keep the root's `label_color`.
+    pub label_color: bool,
+    /// Keep the root's `font_label`.
+    pub font_label: bool,
+    /// Keep the root's `font_value`.
+    pub font_value: bool,
+    /// Keep the root's `font_highlight`.
+    pub font_highlight: bool,
+    /// Keep the root's `fontsize_label`.
+    pub fontsize_label: bool,
+    /// Keep the root's `fontsize_value`.
+    pub fontsize_value: bool,
+    /// Keep the root's `margin`.
+    pub margin: bool,
+    /// Keep the root's `padding`.
+    pub padding: bool,
+    /// Keep the root's `inner_margin`.
+    pub inner_margin: bool,
+    /// Keep the root's `inner_padding`.
+    pub inner_padding: bool,
+    /// Keep the root's `values_col_width`.
+    pub values_col_width: bool,
+    /// Keep the colors of the value gradients of the Perf UI's entries.
+    pub gradients: bool,
+}
+
+impl PerfUiThemeOverrides {
+    /// Keep everything: the Perf UI does not follow the theme at all.
+    pub fn all() -> Self {
+        PerfUiThemeOverrides {
+            background_color: true,
+            inner_background_color: true,
+            inner_background_color_highlight: true,
+            err_color: true,
+            default_value_color: true,
+            label_color: true,
+            font_label: true,
+            font_value: true,
+            font_highlight: true,
+            fontsize_label: true,
+            fontsize_value: true,
+            margin: true,
+            padding: true,
+            inner_margin: true,
+            inner_padding: true,
+            values_col_width: true,
+            gradients: true,
+        }
+    }
+}
+
+impl Default for PerfUiTheme {
+    fn default() -> Self {
+        Self::dark()
+    }
+}
+
+impl PerfUiTheme {
+    /// Create a theme using the styling of an existing `PerfUiRoot`.
+    ///
+    /// The gradient colors are the default Red/Yellow/Green.
+    pub fn from_root(root: &PerfUiRoot) -> Self {
+        PerfUiTheme {
+            background_color: root.background_color,
+            inner_background_color: root.inner_background_color,
+            inner_background_color_highlight: root.inner_background_color_highlight,
+            err_color: root.err_color,
+            default_value_color: root.default_value_color,
+            label_color: root.label_color,
+            font_label: root.font_label.clone(),
+            font_value: root.font_value.clone(),
+            font_highlight: root.font_highlight.clone(),
+            fontsize_label: root.fontsize_label,
+            fontsize_value: root.fontsize_value,
+            margin: root.margin,
+            padding: root.padding,
+            inner_margin: root.inner_margin,
+            inner_padding: root.inner_padding,
+            values_col_width: root.values_col_width,
+            gradient_good: Color::srgb(0.0, 1.0, 0.0),
+            gradient_mid: Color::srgb(1.0, 1.0, 0.0),
+            gradient_bad: Color::srgb(1.0, 0.0, 0.0),
+        }
+    }
+
+    /// Preset: dark translucent background, Red/Yellow/Green gradients.
+    ///
+    /// This is the default look of Perf UIs.
+    pub fn dark() -> Self {
+        Self::from_root(&PerfUiRoot::default())
+    }
+
+    /// Preset: light background with dark text, for bright scenes.
+    pub fn light() -> Self {
+        PerfUiTheme {
+            background_color: Color::srgba(0.95, 0.95, 0.95, 0.85),
+            inner_background_color_highlight: Color::srgba(0.9, 0.0, 0.0, 0.15),
+            err_color: Color::srgb(0.6, 0.6, 0.6),
+            default_value_color: Color::srgb(0.3, 0.3, 0.3),
+            label_color: Color::srgb(0.05, 0.05, 0.05),
+            gradient_good: Color::srgb(0.0, 0.55, 0.1),
+            gradient_mid: Color::srgb(0.8, 0.5, 0.0),
+            gradient_bad: Color::srgb(0.8, 0.0, 0.0),
+            ..Self::dark()
+        }
+    }
+
+    /// Preset: opaque black background, bright saturated colors, larger text.
+    pub fn high_contrast() -> Self {
+        PerfUiTheme {
+            background_color: Color::srgb(0.0, 0.0, 0.0),
+            inner_background_color_highlight: Color::srgb(0.5, 0.0, 0.0),
+            err_color: Color::srgb(0.7, 0.7, 0.7),
+            default_value_color: Color::srgb(1.0, 1.0, 1.0),
+            label_color: Color::srgb(1.0, 1.0, 0.0),
+            fontsize_label: 16.0,
+            fontsize_value: 16.0,
+            padding: 4.0,
+            values_col_width: 160.0,
+            gradient_good: Color::srgb(0.2, 1.0, 0.2),
+            gradient_mid: Color::srgb(1.0, 1.0, 0.0),
+            gradient_bad: Color::srgb(1.0, 0.2, 0.2),
+            ..Self::dark()
+        }
+    }
+
+    /// Preset: like `dark`, but with gradients that avoid red/green.
+    ///
+    /// Uses colors from the Okabe-Ito palette (blue, yellow, vermillion),
+    /// which are distinguishable with all common forms of color blindness.
+    pub fn colorblind_safe() -> Self {
+        PerfUiTheme {
+            inner_background_color_highlight: Color::srgba(0.84, 0.37, 0.0, 0.25),
+            gradient_good: Color::srgb(0.34, 0.71, 0.91),
+            gradient_mid: Color::srgb(0.94, 0.89, 0.26),
+            gradient_bad: Color::srgb(0.84, 0.37, 0.0),
+            ..Self::dark()
+        }
+    }
+
+    /// Apply the theme to the fields of `root` that are not marked in `overrides`.
+    ///
+    /// Returns `true` if anything was changed.
+    pub fn apply_to_root(&self, root: &mut PerfUiRoot, overrides: &PerfUiThemeOverrides) -> bool {
+        let mut changed = false;
+        macro_rules! follow {
+            ($($field:ident),*) => {
+                $(
+                    if !overrides.$field && root.$field != self.$field {
+                        root.$field = self.$field.clone();
+                        changed = true;
+                    }
+                )*
+            };
+        }
+        follow!(
+            background_color,
+            inner_background_color,
+            inner_background_color_highlight,
+            err_color,
+            default_value_color,
+            label_color,
+            font_label,
+            font_value,
+            font_highlight,
+            fontsize_label,
+            fontsize_value,
+            margin,
+            padding,
+            inner_margin,
+            inner_padding,
+            values_col_width
+        );
+        changed
+    }
+
+    /// Recolor a gradient, if it was created with the default preset
+    /// (`ColorGradient::new_preset_default` or `new_preset_default_reversed`,
+    /// as used by the built-in entries) and has not been modified since.
+    ///
+    /// Returns `true` if anything was changed.
+    pub fn apply_to_gradient(&self, gradient: &mut ColorGradient) -> bool {
+        gradient.recolor_default_preset(self.gradient_bad, self.gradient_mid, self.gradient_good)
+    }
+}
+
+/// Recursively find and recolor all `ColorGradient`s in a reflected value.
+fn apply_to_reflect(theme: &PerfUiTheme, value: &mut dyn PartialReflect) -> bool {
+    if let Some(gradient) = value.try_downcast_mut::<ColorGradient>() {
+        return theme.apply_to_gradient(gradient);
+    }
+    let mut changed = false;
+    match value.reflect_mut() {
+        ReflectMut::Struct(s) => {
+            for i in 0..s.field_len() {
+                if let Some(field) = s.field_at_mut(i) {
+                    changed |= apply_to_reflect(theme, field);
+                }
+            }
+        }
+        ReflectMut::TupleStruct(s) => {
+            for i in 0..s.field_len() {
+                if let Some(field) = s.field_mut(i) {
+                    changed |= apply_to_reflect(theme, field);
+                }
+            }
+        }
+        ReflectMut::Enum(e) => {
+            for i in 0..e.field_len() {
+                if let Some(field) = e.field_at_mut(i) {
+                    changed |= apply_to_reflect(theme, field);
+                }
+            }
+        }
+        _ => {}
+    }
+    changed
+}
+
+pub(crate) fn rc_apply_perf_ui_theme(
+    theme: Option<Res<PerfUiTheme>>,
+    q_root: Query<(), Or<(Changed<PerfUiRoot>, Changed<PerfUiThemeOverrides>)>>,
+    q_widget: Query<(), Added<crate::ui::PerfUiSortKey>>,
+) -> bool {
+    theme.is_some_and(|theme| theme.is_changed() || !q_root.is_empty() || !q_widget.is_empty())
+}
+
+pub(crate) fn apply_perf_ui_theme(
+    world: &mut World,
+) {
+    let Some(theme) = world.get_resource::<PerfUiTheme>().cloned() else {
+        return;
+    };
+
+    let mut q_entities = world.query_filtered::<
+        (Entity, Option<&PerfUiEntryHost>),
+        Or<(With<PerfUiRoot>, With<PerfUiEntryHost>)>,
+    >();
+    // (entity, its Perf UI root)
+    let entities: Vec<(Entity, Entity)> = q_entities.iter(world)
+        .map(|(e, host)| (e, crate::ui::perf_ui_root_of(e, host)))
+        .collect();
+    let overrides_of = |world: &World, e_root: Entity| {
+        world.get::<PerfUiThemeOverrides>(e_root).copied().unwrap_or_default()
+    };
+
+    for &(e, _) in &entities {
+        let overrides = overrides_of(world, e);
+        let Some(mut root) = world.get_mut::<PerfUiRoot>(e) else {
+            continue;
+        };
+        if theme.apply_to_root(root.bypass_change_detection(), &overrides) {
+            root.set_changed();
+        }
+    }
+
+    // recolor the gradients in all entries and widgets, via reflection
+    let registry = world.resource::<AppTypeRegistry>().clone();
+    let registry = registry.read();
+    for &(e, e_root) in &entities {
+        if overrides_of(world, e_root).gradients {
+            continue;
+        }
+        let component_ids: Vec<ComponentId> = world.entity(e).archetype().components().collect();
+        for id in component_ids {
+            // (immutable components, such as `ChildOf`, cannot be modified via reflection)
+            let Some(type_id) = world.components().get_info(id)
+                .filter(|info| info.mutable())
+                .and_then(|info| info.type_id())
+            else {
+                continue;
+            };
+            if type_id == TypeId::of::<PerfUiRoot>() {
+                continue;
+            }
+            let Some(reflect_component) = registry.get_type_data::<ReflectComponent>(type_id) else {
+                continue;
+            };
+            let mut entity = world.entity_mut(e);
+            let Some(mut component) = reflect_component.reflect_mut(&mut entity) else {
+                continue;
+            };
+            let value = component.bypass_change_detection().as_partial_reflect_mut();
+            if apply_to_reflect(&theme, value) {
+                component.set_changed();
+            }
+        }
+    }
+}
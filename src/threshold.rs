@@ -0,0 +1,135 @@
+//! Events for when entries start or stop being highlighted.
+//!
+//! Add the [`PerfUiThresholdEvents`] component to your Perf UI entity, to be
+//! notified whenever the highlight state of one of its entries changes (the
+//! value crosses the entry's highlight threshold, such as `threshold_highlight`).
+//! You can use this to log warnings, capture bug reports automatically,
+//! or scale the quality of your game dynamically.
+//!
+//! ```rust
+//! commands.spawn((
+//!     PerfUiThresholdEvents,
+//!     PerfUiEntryFPS::default(),
+//!     PerfUiEntryFrameTimeWorst::default(),
+//! ))
+//! .observe(|trigger: Trigger<PerfUiThresholdCrossed>| {
+//!     warn!("{:?}", trigger.event());
+//! });
+//! ```
+//!
+//! Every [`PerfUiThresholdCrossed`] is sent as a regular (buffered) event,
+//! which you can read using an `EventReader`, and also triggered for
+//! observers watching the Perf UI entity.
+
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use crate::collect::{unique_labels, PerfUiSnapshotValue, PerfUiCollector};
+use crate::ui::root::PerfUiRoot;
+
+/// Component to send [`PerfUiThresholdCrossed`] events for a Perf UI.
+///
+/// Add this to the entity with the `PerfUiRoot` component.
+///
+/// The values of all the entries are checked every frame.
+#[derive(Component, Debug, Default, Clone, Copy)]
+#[require(PerfUiRoot, PerfUiCollector, PerfUiThresholdEventsState)]
+pub struct PerfUiThresholdEvents;
+
+/// Event sent when an entry starts or stops being highlighted.
+///
+/// It is also triggered for observers, targeting the Perf UI entity.
+#[derive(Event, Debug, Clone)]
+pub struct PerfUiThresholdCrossed {
+    /// The Perf UI entity (with the `PerfUiRoot` component).
+    pub entity: Entity,
+    /// The label of the entry.
+    ///
+    /// If multiple entries have the same label, a number is appended.
+    pub label: String,
+    /// The value of the entry, which caused the change.
+    ///
+    /// Can be `None` if the value has become unavailable (which ends
+    /// any highlight episode).
+    pub value: Option<PerfUiSnapshotValue>,
+    /// Is the entry highlighted now?
+    ///
+    /// `true` if a highlight episode has started, `false` if it has ended.
+    pub highlighted: bool,
+    /// How long the entry was in the previous state.
+    ///
+    /// If `highlighted` is `false`, this is the duration of the highlight
+    /// episode that has ended. If `highlighted` is `true`, this is how long
+    /// the entry was not highlighted before this episode started.
+    pub duration: Duration,
+}
+
+#[doc(hidden)]
+#[derive(Component, Default)]
+pub struct PerfUiThresholdEventsState {
+    entries: Vec<ThresholdState>,
+}
+
+struct ThresholdState {
+    label: String,
+    highlighted: bool,
+    since: Duration,
+    /// Was this entry present in the latest collected values?
+    seen: bool,
+}
+
+pub(crate) fn tick_perf_ui_threshold_events(
+    mut q_events: Query<&mut PerfUiCollector, With<PerfUiThresholdEvents>>,
+) {
+    for mut collector in &mut q_events {
+        collector.due = true;
+    }
+}
+
+pub(crate) fn output_perf_ui_threshold_events(
+    mut commands: Commands,
+    time: Res<Time<Real>>,
+    mut q_events: Query<(Entity, &mut PerfUiThresholdEventsState, &PerfUiCollector), With<PerfUiThresholdEvents>>,
+    mut evw_crossed: EventWriter<PerfUiThresholdCrossed>,
+) {
+    let now = time.elapsed();
+    for (e_root, mut state, collector) in &mut q_events {
+        for entry in &mut state.entries {
+            entry.seen = false;
+        }
+        let labels = unique_labels(&collector.rows, &[]);
+        for (label, row) in labels.into_iter().zip(&collector.rows) {
+            let highlighted = row.value.as_ref().is_some_and(|v| v.highlight);
+            let entry = match state.entries.iter_mut().position(|s| s.label == label) {
+                Some(i) => &mut state.entries[i],
+                None => {
+                    state.entries.push(ThresholdState {
+                        label: label.clone(),
+                        highlighted: false,
+                        since: now,
+                        seen: false,
+                    });
+                    state.entries.last_mut().unwrap()
+                }
+            };
+            entry.seen = true;
+            if entry.highlighted == highlighted {
+                continue;
+            }
+            let ev = PerfUiThresholdCrossed {
+                entity: e_root,
+                label,
+                value: row.value.clone(),
+                highlighted,
+                duration: now.saturating_sub(entry.since),
+            };
+            entry.highlighted = highlighted;
+            entry.since = now;
+            commands.trigger_targets(ev.clone(), e_root);
+            evw_crossed.write(ev);
+        }
+        // forget about entries that have been removed
+        state.entries.retain(|s| s.seen);
+    }
+}
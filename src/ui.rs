@@ -1,7 +1,7 @@
 //! Framework for the UI hierarchy
 
 use bevy::prelude::*;
-use self::root::PerfUiRoot;
+use self::root::{PerfUiEntryHost, PerfUiRoot};
 
 pub mod root;
 pub mod widget;
@@ -9,6 +9,27 @@ pub mod widget;
 #[derive(Component, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub(crate) struct PerfUiSortKey(i32);
 
+/// Get the Perf UI root entity that an entry belongs to.
+///
+/// Entries can be on the root entity itself, or on a child entity of the
+/// root, marked with `PerfUiEntryHost`.
+pub(crate) fn perf_ui_root_of(
+    e_entry: Entity,
+    host: Option<&PerfUiEntryHost>,
+) -> Entity {
+    host.map(|host| host.root()).unwrap_or(e_entry)
+}
+
+/// Is Bevy UI available to display the Perf UI?
+///
+/// In headless apps (such as with `MinimalPlugins`), there is no UI,
+/// but Perf UIs can still be used with other outputs, such as text.
+pub(crate) fn rc_ui_enabled(
+    ui_stack: Option<Res<bevy::ui::UiStack>>,
+) -> bool {
+    ui_stack.is_some()
+}
+
 pub(crate) fn rc_sort_perf_ui_widgets(
     q: Query<(), (With<PerfUiRoot>, Changed<Children>)>,
 ) -> bool {
@@ -24,6 +45,26 @@ pub(crate) fn sort_perf_ui_widgets(
     }
 }
 
+/// Is a Perf UI visible, given its root entity's components?
+pub(crate) fn is_visible(
+    visibility: Option<&Visibility>,
+    inherited: Option<&InheritedVisibility>,
+    node: Option<&Node>,
+) -> bool {
+    let vis = match visibility {
+        None => true,
+        Some(Visibility::Hidden) => false,
+        Some(Visibility::Visible) => true,
+        Some(Visibility::Inherited) => inherited
+            .map(|x| x.get())
+            .unwrap_or(true),
+    };
+    let display = node
+        .map(|s| s.display != Display::None)
+        .unwrap_or(true);
+    vis && display
+}
+
 pub(crate) fn rc_any_visible(
     q_root: Query<(
         Option<&Visibility>,
@@ -32,18 +73,5 @@ pub(crate) fn rc_any_visible(
     ), With<PerfUiRoot>>,
 ) -> bool {
     q_root.iter()
-        .any(|(visibility, inherited, node)| {
-            let vis = match visibility {
-                None => true,
-                Some(Visibility::Hidden) => false,
-                Some(Visibility::Visible) => true,
-                Some(Visibility::Inherited) => inherited
-                    .map(|x| x.get())
-                    .unwrap_or(true),
-            };
-            let display = node
-                .map(|s| s.display != Display::None)
-                .unwrap_or(true);
-            vis && display
-        })
+        .any(|(visibility, inherited, node)| is_visible(visibility, inherited, node))
 }
@@ -6,7 +6,9 @@
 use bevy::prelude::*;
 
 /// Which corner of the screen to display the Perf UI at?
-#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+#[reflect(Debug, Default, PartialEq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum PerfUiPosition {
     /// Absolute positioning based on distance from top and left edges of viewport.
     TopLeft,
@@ -19,6 +21,89 @@ pub enum PerfUiPosition {
     BottomRight,
 }
 
+/// How often should the displayed values be updated?
+///
+/// Values that change every frame can be hard to read. Updating less often
+/// makes them more readable and also avoids the overhead of formatting
+/// the values and laying out the text every frame.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Debug, Default, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PerfUiUpdateInterval {
+    /// Update every frame.
+    #[default]
+    EveryFrame,
+    /// Update once every N frames.
+    Frames(u32),
+    /// Update at most once per the given amount of (real) time, in seconds.
+    Seconds(f32),
+}
+
+/// Settings to make highlighting less sensitive to noisy values.
+///
+/// By default, an entry is highlighted in every frame when its value is
+/// past the threshold (such as `threshold_highlight`). If the value is noisy,
+/// the highlight can flicker on and off. These settings let you require the
+/// value to stay past the threshold for some time before it is highlighted
+/// (debounce), and to cross back by a margin before the highlight is cleared
+/// (hysteresis).
+///
+/// The conditions for highlighting are combined: the value must be past the
+/// threshold for at least `updates` consecutive updates *and* `seconds`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Debug, Default, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct PerfUiHighlightDebounce {
+    /// How many consecutive updates the value must be past the threshold.
+    ///
+    /// With the default update interval, that is the number of frames.
+    ///
+    /// Default: `0` (highlight immediately)
+    pub updates: u32,
+    /// How long (in seconds) the value must be past the threshold.
+    ///
+    /// Default: `0.0` (highlight immediately)
+    pub seconds: f32,
+    /// How far back the value must cross, to clear the highlight.
+    ///
+    /// As a fraction of the threshold. For example, with a FPS threshold
+    /// of 60 and a margin of `0.1`, the highlight is cleared above 66 FPS.
+    ///
+    /// Only applies to entries that provide their threshold via
+    /// `PerfUiEntry::highlight_threshold`.
+    ///
+    /// Default: `0.0` (clear immediately)
+    pub clear_margin: f32,
+}
+
+/// Keeps track of when something was last updated, to implement [`PerfUiUpdateInterval`].
+#[derive(Debug, Default, Clone)]
+pub(crate) struct PerfUiUpdateTimer {
+    frames_since_update: u32,
+    last_update: Option<f32>,
+}
+
+impl PerfUiUpdateTimer {
+    /// Check if it is time to update now, and if so, reset the timer.
+    ///
+    /// Must be called once per frame. `now` is the elapsed (real) time in seconds.
+    pub(crate) fn update_due(&mut self, interval: PerfUiUpdateInterval, now: f32) -> bool {
+        self.frames_since_update = self.frames_since_update.saturating_add(1);
+        let due = match (interval, self.last_update) {
+            (_, None) => true,
+            (PerfUiUpdateInterval::EveryFrame, _) => true,
+            (PerfUiUpdateInterval::Frames(n), _) => self.frames_since_update >= n,
+            (PerfUiUpdateInterval::Seconds(secs), Some(last)) => now - last >= secs,
+        };
+        if due {
+            self.frames_since_update = 0;
+            self.last_update = Some(now);
+        }
+        due
+    }
+}
+
 /// Component to configure a Perf UI instance.
 ///
 /// To create a Perf UI, spawn an entity with this component
@@ -40,7 +125,15 @@ pub enum PerfUiPosition {
 ///
 /// We will automatically detect that you have added these components
 /// and will do the rest of the setup to spawn the UI. :)
-#[derive(Component, Debug, Clone)]
+///
+/// Entries can also be put on child entities of the Perf UI entity.
+/// This allows you to have multiple instances of the same entry type
+/// in one Perf UI. Such child entities get a [`PerfUiEntryHost`]
+/// instead of a `PerfUiRoot`.
+#[derive(Component, Debug, Clone, Reflect)]
+#[reflect(Component, Default, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct PerfUiRoot {
     /// The color to use for the background of the Perf UI.
     ///
@@ -80,10 +173,13 @@ pub struct PerfUiRoot {
     /// Default: WHITE
     pub label_color: Color,
     /// The font to use for labels.
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub font_label: Handle<Font>,
     /// The font to use for values.
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub font_value: Handle<Font>,
     /// The font to use for highlighted values.
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub font_highlight: Handle<Font>,
     /// The font size for labels.
     ///
@@ -96,6 +192,7 @@ pub struct PerfUiRoot {
     /// The ZIndex of the UI.
     ///
     /// Default: `i32::MAX` (display on top of all other UI)
+    #[cfg_attr(feature = "serde", serde(with = "serde_global_z_index"))]
     pub z_index: GlobalZIndex,
     /// The position of the UI.
     ///
@@ -121,6 +218,18 @@ pub struct PerfUiRoot {
     ///
     /// Default: `128.0`
     pub values_col_width: f32,
+    /// How often to update the displayed values.
+    ///
+    /// Individual entries can override this.
+    ///
+    /// Default: every frame
+    pub update_interval: PerfUiUpdateInterval,
+    /// Debounce/hysteresis settings for highlighting values.
+    ///
+    /// Individual entries can override this.
+    ///
+    /// Default: highlight immediately when a value crosses the threshold
+    pub highlight_debounce: PerfUiHighlightDebounce,
 }
 
 impl Default for PerfUiRoot {
@@ -147,10 +256,27 @@ impl Default for PerfUiRoot {
             inner_margin: 0.0,
             inner_padding: 0.0,
             values_col_width: 128.0,
+            update_interval: default(),
+            highlight_debounce: default(),
         }
     }
 }
 
+/// `GlobalZIndex` as a plain number.
+#[cfg(feature = "serde")]
+mod serde_global_z_index {
+    use bevy::ui::GlobalZIndex;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S: Serializer>(z_index: &GlobalZIndex, serializer: S) -> Result<S::Ok, S::Error> {
+        z_index.0.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<GlobalZIndex, D::Error> {
+        i32::deserialize(deserializer).map(GlobalZIndex)
+    }
+}
+
 impl PerfUiPosition {
     fn top(self, margin: f32) -> Val {
         match self {
@@ -178,6 +304,62 @@ impl PerfUiPosition {
     }
 }
 
+/// Marks a child entity of a Perf UI that holds entries for it.
+///
+/// Each entity can only have one component of each type, so to have multiple
+/// instances of the same entry type in one Perf UI, spawn the extra ones as
+/// child entities of the Perf UI entity. Entries and widgets require a
+/// `PerfUiRoot`, but on child entities of a Perf UI, it is automatically
+/// replaced with this.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PerfUiEntryHost {
+    root: Entity,
+}
+
+impl PerfUiEntryHost {
+    /// The Perf UI entity (with the `PerfUiRoot`) that the entries belong to.
+    pub fn root(&self) -> Entity {
+        self.root
+    }
+}
+
+/// Turn an entity that was given a `PerfUiRoot` into a `PerfUiEntryHost`,
+/// if it is a child of a Perf UI.
+pub(crate) fn on_add_perf_ui_root(
+    trigger: Trigger<OnAdd, PerfUiRoot>,
+    mut commands: Commands,
+    q_child_of: Query<&ChildOf>,
+    q_root: Query<(), With<PerfUiRoot>>,
+) {
+    let e = trigger.target();
+    if let Ok(child_of) = q_child_of.get(e) {
+        if q_root.contains(child_of.parent()) {
+            commands.entity(e)
+                .remove::<PerfUiRoot>()
+                .insert(PerfUiEntryHost { root: child_of.parent() });
+        }
+    }
+}
+
+/// Same as `on_add_perf_ui_root`, for entities that become a child of
+/// a Perf UI later. Also keeps hosts pointing at their parent Perf UI.
+pub(crate) fn on_insert_perf_ui_child_of(
+    trigger: Trigger<OnInsert, ChildOf>,
+    mut commands: Commands,
+    q_entity: Query<&ChildOf, Or<(With<PerfUiRoot>, With<PerfUiEntryHost>)>>,
+    q_root: Query<(), With<PerfUiRoot>>,
+) {
+    let e = trigger.target();
+    let Ok(child_of) = q_entity.get(e) else {
+        return;
+    };
+    if q_root.contains(child_of.parent()) {
+        commands.entity(e)
+            .remove::<PerfUiRoot>()
+            .insert(PerfUiEntryHost { root: child_of.parent() });
+    }
+}
+
 pub(crate) fn rc_setup_perf_ui(
     q: Query<(), Changed<PerfUiRoot>>,
 ) -> bool {
@@ -3,16 +3,19 @@
 use std::marker::PhantomData;
 
 use bevy::prelude::*;
+use bevy::ecs::component::Mutable;
 use bevy::ecs::system::SystemParam;
 use bevy::ecs::system::StaticSystemParam;
 use bevy::ecs::system::lifetimeless::SQuery;
-use crate::ui::root::PerfUiRoot;
-use crate::entry::PerfUiEntry;
+use bevy::platform::collections::HashMap;
+use crate::ui::root::{PerfUiEntryHost, PerfUiRoot, PerfUiUpdateInterval, PerfUiUpdateTimer};
+use crate::collect::PerfUiCollector;
+use crate::entry::{PerfUiEntry, PerfUiHighlightFilter, PerfUiHistoryRecorder};
 
 use super::PerfUiSortKey;
 
 /// Trait for Perf UI Widgets.
-pub trait PerfUiWidget<T: PerfUiEntry>: Component {
+pub trait PerfUiWidget<T: PerfUiEntry>: Component<Mutability = Mutable> {
     /// Any extra system parameters you need to setup the UI.
     type SystemParamSpawn: SystemParam + 'static;
     /// Any system parameters you need to update the UI.
@@ -54,6 +57,12 @@ pub trait PerfUiWidget<T: PerfUiEntry>: Component {
 
     /// Update the UI for the widget.
     ///
+    /// The framework obtains the current value of the entry (from
+    /// `PerfUiEntry::update_value`) and provides it as `value`, so that
+    /// it only has to be computed once per update. `highlight` says
+    /// whether the value should be displayed highlighted (taking into
+    /// account the `PerfUiHighlightDebounce` settings).
+    ///
     /// You can use arbitrary Bevy system parameters to access the data
     /// you need to update the UI. Put them in `type SystemParamUpdate`
     /// and access them via `param`.
@@ -67,17 +76,42 @@ pub trait PerfUiWidget<T: PerfUiEntry>: Component {
         root: &PerfUiRoot,
         e_root: Entity,
         e_widget: Entity,
+        value: Option<&T::Value>,
+        highlight: bool,
         param: &mut <Self::SystemParamUpdate as SystemParam>::Item<'_, '_>,
     );
 
     /// The sort key of the entry that the widget is displaying.
     fn sort_key(&self) -> i32;
+
+    /// Access the entry that the widget is displaying.
+    ///
+    /// The framework uses this to obtain the values to display, and for
+    /// features that work with the entry independently of the UI (such
+    /// as recording history).
+    fn entry(&self) -> &T;
+
+    /// Access the entry that the widget is displaying mutably.
+    ///
+    /// See `entry`.
+    fn entry_mut(&mut self) -> &mut T;
+
+    /// How often the widget should be updated, if different from the Perf UI.
+    ///
+    /// `None` means the `update_interval` of the `PerfUiRoot` should be used.
+    ///
+    /// If unimplemented, the entry's preference is used.
+    fn update_interval(&self) -> Option<PerfUiUpdateInterval> {
+        self.entry().update_interval()
+    }
 }
 
 /// Marker component to keep track of a widget's toplevel entity
 #[derive(Component)]
 pub struct PerfUiWidgetMarker<W> {
     e_root: Entity,
+    e_entry: Entity,
+    timer: PerfUiUpdateTimer,
     _pd: PhantomData<W>,
 }
 
@@ -90,7 +124,8 @@ pub(crate) fn rc_setup_perf_ui_widget<E: PerfUiEntry, W: PerfUiWidget<E>>(
 
 pub(crate) fn setup_perf_ui_widget<E: PerfUiEntry, W: PerfUiWidget<E>>(
     mut commands: Commands,
-    q_root: Query<(Entity, &PerfUiRoot, &W), Or<(Changed<W>, Changed<PerfUiRoot>)>>,
+    q_root: Query<Ref<PerfUiRoot>>,
+    q_entry: Query<(Entity, Ref<W>, Option<&PerfUiEntryHost>)>,
     q_widget: Query<(Entity, &PerfUiWidgetMarker<W>)>,
     mut removed: RemovedComponents<W>,
     widget_param: StaticSystemParam<W::SystemParamSpawn>,
@@ -98,36 +133,46 @@ pub(crate) fn setup_perf_ui_widget<E: PerfUiEntry, W: PerfUiWidget<E>>(
     let mut widget_param = widget_param.into_inner();
 
     // handle any removals:
-    // if the entry component was removed from a perf ui root entity,
+    // if the entry component was removed from an entity,
     // we need to find the entity of the entry's UI and despawn it.
     for e_removed in removed.read() {
-        if let Some(e_entry) = q_widget.iter()
-            .find(|(_, marker)| marker.e_root == e_removed)
+        if let Some(e_widget) = q_widget.iter()
+            .find(|(_, marker)| marker.e_entry == e_removed)
             .map(|(e, _)| e)
         {
-            commands.entity(e_entry).despawn();
+            commands.entity(e_widget).despawn();
         }
     }
     // handle any additions or reconfigurations:
-    // if an entry component was added/changed to a perf ui root entity,
-    // or if the ui root component itself was changed,
-    // find and despawn any existing entries and
-    // spawn a new UI hierarchy for the entry.
-    for (e_root, root, widget) in &q_root {
-        // despawn any old/existing UI hierarchy for relevant entries
+    // if an entry component was added/changed (on a perf ui root entity,
+    // or on a child entity of one), or if the ui root component itself
+    // was changed, find and despawn any existing UI for the entry and
+    // spawn a new UI hierarchy for it.
+    for (e_entry, widget, host) in &q_entry {
+        let e_root = super::perf_ui_root_of(e_entry, host);
+        let Ok(root) = q_root.get(e_root) else {
+            continue;
+        };
+        if !widget.is_changed() && !root.is_changed() {
+            continue;
+        }
+
+        // despawn any old/existing UI hierarchy for this entry
         if let Some(e_widget) = q_widget.iter()
-            .find(|(_, marker)| marker.e_root == e_root)
+            .find(|(_, marker)| marker.e_entry == e_entry)
             .map(|(e, _)| e)
         {
             commands.entity(e_widget).despawn();
         }
 
         let e_widget = widget.spawn(
-            root, e_root, &mut commands, &mut widget_param
+            &root, e_root, &mut commands, &mut widget_param
         );
         commands.entity(e_widget).insert((
             PerfUiWidgetMarker::<W> {
                 e_root,
+                e_entry,
+                timer: default(),
                 _pd: PhantomData,
             },
             PerfUiSortKey(widget.sort_key()),
@@ -136,22 +181,123 @@ pub(crate) fn setup_perf_ui_widget<E: PerfUiEntry, W: PerfUiWidget<E>>(
     }
 }
 
+/// Internal state of `update_perf_ui_widget`.
+#[derive(Default)]
+pub(crate) struct PerfUiWidgetUpdateState {
+    /// The highlight state of every entry.
+    highlight: HashMap<Entity, PerfUiHighlightFilter>,
+    /// The entries whose UI should be updated this frame, with their widget entity.
+    due: Vec<(Entity, Entity)>,
+    /// The Perf UIs that are visible this frame.
+    visible: Vec<Entity>,
+}
+
 /// System that updates the values of Perf UI entries of a given type
 ///
+/// The value of each entry is obtained once, and then used to update
+/// the UI, to record the entry's history (if registered using
+/// `add_perf_ui_history`), and for other outputs (such as `PerfUiSnapshot`).
+///
 /// Exposed as `pub` so you can refer to it for ordering.
 #[allow(private_interfaces)]
+#[allow(clippy::too_many_arguments)]
 pub fn update_perf_ui_widget<E: PerfUiEntry, W: PerfUiWidget<E>>(
-    q_root: Query<(Entity, &PerfUiRoot, &W)>,
-    q_widget: Query<(Entity, &PerfUiWidgetMarker<W>)>,
-    widget_param: StaticSystemParam<W::SystemParamUpdate>,
+    time: Res<Time<Real>>,
+    history: Option<Res<PerfUiHistoryRecorder<E>>>,
+    q_root: Query<&PerfUiRoot>,
+    mut q_collector: Query<&mut PerfUiCollector>,
+    mut q_entry: Query<(Entity, &mut W, Option<&PerfUiEntryHost>)>,
+    mut q_widget: Query<(Entity, &mut PerfUiWidgetMarker<W>)>,
+    mut removed: RemovedComponents<W>,
+    mut state: Local<PerfUiWidgetUpdateState>,
+    entry_param: StaticSystemParam<E::SystemParam>,
+    // (widgets may access the same components that we use to check visibility)
+    mut params: ParamSet<(
+        Query<(
+            Entity,
+            Option<&Visibility>,
+            Option<&InheritedVisibility>,
+            Option<&Node>,
+        ), With<PerfUiRoot>>,
+        StaticSystemParam<W::SystemParamUpdate>,
+    )>,
 ) {
-    let mut widget_param = widget_param.into_inner();
-    for (e_widget, marker) in &q_widget {
-        let Ok((e_root, root, widget)) = q_root.get(marker.e_root) else {
+    let state = &mut *state;
+    let mut entry_param = entry_param.into_inner();
+    let now = time.elapsed();
+
+    for e_removed in removed.read() {
+        state.highlight.remove(&e_removed);
+    }
+
+    // find the widgets whose UI should be updated this frame
+    state.due.clear();
+    for (e_widget, mut marker) in &mut q_widget {
+        let (Ok(root), Ok((_, widget, _))) = (q_root.get(marker.e_root), q_entry.get(marker.e_entry)) else {
             continue; // TODO: should we panic here?
         };
-        widget.update(root, e_root, e_widget, &mut widget_param);
+        let interval = widget.update_interval().unwrap_or(root.update_interval);
+        if marker.bypass_change_detection().timer.update_due(interval, now.as_secs_f32()) {
+            state.due.push((marker.e_entry, e_widget));
+        }
     }
+
+    // history is only recorded while the Perf UI is visible
+    state.visible.clear();
+    if history.is_some() {
+        state.visible.extend(params.p0().iter()
+            .filter(|(_, visibility, inherited, node)| super::is_visible(*visibility, *inherited, *node))
+            .map(|(e_root, ..)| e_root)
+        );
+    }
+
+    let mut widget_param = params.p1();
+    let widget_param = &mut *widget_param;
+    for (e_entry, mut widget, host) in &mut q_entry {
+        let e_root = super::perf_ui_root_of(e_entry, host);
+        let Ok(root) = q_root.get(e_root) else {
+            continue;
+        };
+        let e_widget = state.due.iter()
+            .find(|(e, _)| *e == e_entry)
+            .map(|(_, e_widget)| *e_widget);
+        let record = history.as_ref().filter(|history| {
+            state.visible.contains(&e_root)
+                && (history.wants_sample)(widget.entry(), now)
+        });
+        let mut collector = q_collector.get_mut(e_root).ok()
+            .filter(|collector| collector.due);
+        if e_widget.is_none() && record.is_none() && collector.is_none() {
+            continue;
+        }
+
+        // Recording history must not count as a change to the component,
+        // otherwise we would respawn the widget's UI every frame.
+        let widget = widget.bypass_change_detection();
+        let value = widget.entry().update_value(&mut entry_param);
+        if let (Some(history), Some(value)) = (record, &value) {
+            (history.record)(widget.entry_mut(), now, value);
+        }
+        if e_widget.is_none() && collector.is_none() {
+            continue;
+        }
+
+        let highlight = state.highlight.entry(e_entry)
+            .or_default()
+            .update(widget.entry(), value.as_ref(), root, now.as_secs_f32());
+        if let Some(collector) = &mut collector {
+            collector.push(widget.entry(), widget.sort_key(), value.as_ref(), highlight, root);
+        }
+        if let Some(e_widget) = e_widget {
+            widget.update(root, e_root, e_widget, value.as_ref(), highlight, widget_param);
+        }
+    }
+}
+
+#[doc(hidden)]
+#[derive(Component)]
+pub struct PerfUiSimpleWidgetParts {
+    e_text: Entity,
 }
 
 #[doc(hidden)]
@@ -163,8 +309,10 @@ pub struct SimpleWidgetTextMarker<E: PerfUiEntry> {
 impl<E: PerfUiEntry> PerfUiWidget<E> for E {
     type SystemParamSpawn = ();
     type SystemParamUpdate = (
-        <E as PerfUiEntry>::SystemParam,
-        SQuery<&'static mut BackgroundColor, With<PerfUiWidgetMarker<E>>>,
+        SQuery<(
+            &'static mut BackgroundColor,
+            &'static PerfUiSimpleWidgetParts,
+        ), With<PerfUiWidgetMarker<E>>>,
         SQuery<(&'static mut Text, &'static mut TextColor, &'static mut TextFont), With<SimpleWidgetTextMarker<E>>>,
     );
 
@@ -235,6 +383,9 @@ impl<E: PerfUiEntry> PerfUiWidget<E> for E {
         )).id();
         commands.entity(e_text_wrapper).add_child(e_text);
         commands.entity(e_widget).add_child(e_text_wrapper);
+        commands.entity(e_widget).insert(PerfUiSimpleWidgetParts {
+            e_text,
+        });
         e_widget
     }
 
@@ -243,23 +394,25 @@ impl<E: PerfUiEntry> PerfUiWidget<E> for E {
         root: &crate::prelude::PerfUiRoot,
         _e_root: Entity,
         e_widget: Entity,
+        value: Option<&E::Value>,
+        entry_highlight: bool,
         (
-            entry_param,
             q_widget,
             q_text,
         ): &mut <Self::SystemParamUpdate as SystemParam>::Item<'_, '_>,
     ) {
-        for (mut text, mut color, mut font) in q_text.iter_mut() {
-            let mut entry_highlight = false;
-            if let Some(value) = self.update_value(entry_param) {
-                let new_color = self.value_color(&value)
+        let Ok((mut entry_bgcolor, parts)) = q_widget.get_mut(e_widget) else {
+            return;
+        };
+        if let Ok((mut text, mut color, mut font)) = q_text.get_mut(parts.e_text) {
+            if let Some(value) = value {
+                let new_color = self.value_color(value)
                     .unwrap_or(root.default_value_color);
-                let s = self.format_value(&value);
+                let s = self.format_value(value);
                 *text = Text(s);
                 *color = TextColor(new_color);
-                if self.value_highlight(&value) {
+                if entry_highlight {
                     font.font = root.font_highlight.clone();
-                    entry_highlight = true;
                 } else {
                     font.font = root.font_value.clone();
                 }
@@ -269,17 +422,23 @@ impl<E: PerfUiEntry> PerfUiWidget<E> for E {
                 *color = TextColor(root.err_color);
                 font.font = root.font_value.clone();
             }
-            if let Ok(mut entry_bgcolor) = q_widget.get_mut(e_widget) {
-                if entry_highlight {
-                    entry_bgcolor.0 = root.inner_background_color_highlight;
-                } else {
-                    entry_bgcolor.0 = root.inner_background_color;
-                }
-            }
+        }
+        if entry_highlight {
+            entry_bgcolor.0 = root.inner_background_color_highlight;
+        } else {
+            entry_bgcolor.0 = root.inner_background_color;
         }
     }
 
     fn sort_key(&self) -> i32 {
         PerfUiEntry::sort_key(self)
     }
+
+    fn entry(&self) -> &E {
+        self
+    }
+
+    fn entry_mut(&mut self) -> &mut E {
+        self
+    }
 }
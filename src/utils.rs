@@ -3,7 +3,7 @@
 //! Mostly stuff for implementing new `PerfUiEntry` types and
 //! formatting of values.
 
-use std::sync::atomic::{AtomicI32, Ordering};
+use std::sync::atomic::{AtomicI32, AtomicU8, Ordering};
 use std::time::Duration;
 
 use bevy::prelude::*;
@@ -29,9 +29,96 @@ pub fn next_sort_key() -> i32 {
 ///
 /// The interpolation is done in Bevy's OKLAB color space, so it looks
 /// nicer and more perceputally-uniform.
-#[derive(Debug, Default, Clone)]
+///
+/// In "stepped" mode, there is no interpolation: every value gets the color
+/// of the nearest stop at or below it.
+#[derive(Debug, Default, Clone, Reflect)]
+#[reflect(Debug, Default, Clone)]
+#[cfg_attr(feature = "serde", reflect(Serialize, Deserialize))]
 pub struct ColorGradient {
     stops: Vec<(FloatOrd, Oklaba)>,
+    stepped: bool,
+    /// If the gradient was created with the default preset, how.
+    ///
+    /// This is what allows `PerfUiTheme` to recolor default gradients only.
+    #[reflect(ignore)]
+    preset: Option<DefaultPreset>,
+}
+
+/// How a gradient was created by `ColorGradient::new_preset_default(_reversed)`.
+#[derive(Debug, Clone, PartialEq)]
+struct DefaultPreset {
+    low: f32,
+    mid: f32,
+    high: f32,
+    /// Are lower values better?
+    reversed: bool,
+    /// The stops as they were created, to detect if the gradient was modified since.
+    stops: Vec<(FloatOrd, Oklaba)>,
+}
+
+static DEFAULT_PRESET: AtomicU8 = AtomicU8::new(ColorGradientPreset::RedYellowGreen as u8);
+
+/// Color schemes for [`ColorGradient`] presets.
+///
+/// Each preset goes from a color for "bad" values to a color for "good" values.
+///
+/// The built-in entries use the default preset for their default gradients.
+/// You can change it globally using [`ColorGradientPreset::set_default`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+#[reflect(Debug, Default, PartialEq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(u8)]
+pub enum ColorGradientPreset {
+    /// Red (bad), Yellow, Green (good).
+    #[default]
+    RedYellowGreen,
+    /// Viridis: Yellow (bad), Green, Teal, Blue, Purple (good).
+    ///
+    /// Perceptually-uniform and readable with all common forms of color blindness.
+    Viridis,
+    /// Cividis: Yellow (bad), Gray, Navy Blue (good).
+    ///
+    /// Perceptually-uniform, designed to look nearly identical
+    /// with and without red-green color blindness.
+    Cividis,
+    /// Orange (bad), Light Gray, Blue (good).
+    ///
+    /// Diverging, readable with all common forms of color blindness.
+    BlueOrange,
+}
+
+impl ColorGradientPreset {
+    /// Set the preset to use for the default gradients of entries.
+    ///
+    /// This affects entries created after it is called, so call it during
+    /// app initialization, before spawning any Perf UIs.
+    pub fn set_default(self) {
+        DEFAULT_PRESET.store(self as u8, Ordering::Relaxed);
+    }
+
+    /// Get the preset to use for the default gradients of entries.
+    pub fn get_default() -> Self {
+        match DEFAULT_PRESET.load(Ordering::Relaxed) {
+            1 => ColorGradientPreset::Viridis,
+            2 => ColorGradientPreset::Cividis,
+            3 => ColorGradientPreset::BlueOrange,
+            _ => ColorGradientPreset::RedYellowGreen,
+        }
+    }
+
+    /// Create a gradient from "bad" to "good" between the specified low-mid-high values.
+    ///
+    /// If higher values are worse, reverse it (see `ColorGradient::reversed`).
+    #[allow(clippy::result_unit_err)]
+    pub fn gradient(self, low: f32, mid: f32, high: f32) -> Result<ColorGradient, ()> {
+        match self {
+            ColorGradientPreset::RedYellowGreen => ColorGradient::new_preset_ryg(low, mid, high),
+            ColorGradientPreset::Viridis => ColorGradient::new_preset_viridis(low, mid, high).map(ColorGradient::reversed),
+            ColorGradientPreset::Cividis => ColorGradient::new_preset_cividis(low, mid, high).map(ColorGradient::reversed),
+            ColorGradientPreset::BlueOrange => ColorGradient::new_preset_blue_orange(low, mid, high).map(ColorGradient::reversed),
+        }
+    }
 }
 
 impl ColorGradient {
@@ -45,6 +132,8 @@ impl ColorGradient {
     pub fn new() -> Self {
         ColorGradient {
             stops: vec![],
+            stepped: false,
+            preset: None,
         }
     }
 
@@ -56,10 +145,13 @@ impl ColorGradient {
             stops: vec![
                 (FloatOrd(f32::NEG_INFINITY), color.into()),
             ],
+            stepped: false,
+            preset: None,
         }
     }
 
     /// Preset constructor: Red-Yellow-Green between the specified low-mid-high values.
+    #[allow(clippy::result_unit_err)]
     pub fn new_preset_ryg(low: f32, mid: f32, high: f32) -> Result<Self, ()> {
         if low.is_nan() || mid.is_nan() || high.is_nan() || low > mid || mid > high {
             return Err(());
@@ -70,10 +162,13 @@ impl ColorGradient {
                 (FloatOrd(mid), Color::srgb(1.0, 1.0, 0.0).into()),
                 (FloatOrd(high), Color::srgb(0.0, 1.0, 0.0).into()),
             ],
+            stepped: false,
+            preset: None,
         })
     }
 
     /// Preset constructor: Green-Yellow-Red between the specified low-mid-high values.
+    #[allow(clippy::result_unit_err)]
     pub fn new_preset_gyr(low: f32, mid: f32, high: f32) -> Result<Self, ()> {
         if low.is_nan() || mid.is_nan() || high.is_nan() || low > mid || mid > high {
             return Err(());
@@ -84,9 +179,166 @@ impl ColorGradient {
                 (FloatOrd(mid), Color::srgb(1.0, 1.0, 0.0).into()),
                 (FloatOrd(high), Color::srgb(1.0, 0.0, 0.0).into()),
             ],
+            stepped: false,
+            preset: None,
         })
     }
 
+    /// Preset constructor: Viridis colormap (Purple-Blue-Teal-Green-Yellow)
+    /// between the specified low-mid-high values.
+    #[allow(clippy::result_unit_err)]
+    pub fn new_preset_viridis(low: f32, mid: f32, high: f32) -> Result<Self, ()> {
+        Self::new_preset_5(low, mid, high, [
+            Color::srgb_u8(0x44, 0x01, 0x54),
+            Color::srgb_u8(0x3b, 0x52, 0x8b),
+            Color::srgb_u8(0x21, 0x91, 0x8c),
+            Color::srgb_u8(0x5e, 0xc9, 0x62),
+            Color::srgb_u8(0xfd, 0xe7, 0x25),
+        ])
+    }
+
+    /// Preset constructor: Cividis colormap (Navy Blue-Gray-Yellow)
+    /// between the specified low-mid-high values.
+    #[allow(clippy::result_unit_err)]
+    pub fn new_preset_cividis(low: f32, mid: f32, high: f32) -> Result<Self, ()> {
+        Self::new_preset_5(low, mid, high, [
+            Color::srgb_u8(0x00, 0x22, 0x4e),
+            Color::srgb_u8(0x41, 0x4d, 0x6b),
+            Color::srgb_u8(0x7c, 0x7b, 0x78),
+            Color::srgb_u8(0xbc, 0xaf, 0x6f),
+            Color::srgb_u8(0xfe, 0xe8, 0x38),
+        ])
+    }
+
+    /// Preset constructor: Blue-Light Gray-Orange diverging gradient
+    /// between the specified low-mid-high values.
+    #[allow(clippy::result_unit_err)]
+    pub fn new_preset_blue_orange(low: f32, mid: f32, high: f32) -> Result<Self, ()> {
+        Self::new_preset_5(low, mid, high, [
+            Color::srgb_u8(0x21, 0x66, 0xac),
+            Color::srgb_u8(0x67, 0xa9, 0xcf),
+            Color::srgb_u8(0xe8, 0xe8, 0xe8),
+            Color::srgb_u8(0xf4, 0xa5, 0x82),
+            Color::srgb_u8(0xe6, 0x61, 0x01),
+        ])
+    }
+
+    /// Preset constructor: use the default preset (see [`ColorGradientPreset::set_default`]),
+    /// from "bad" at `low` to "good" at `high`.
+    ///
+    /// This is what the built-in entries use, if higher values are better.
+    #[allow(clippy::result_unit_err)]
+    ///
+    /// If a [`PerfUiTheme`](crate::theme::PerfUiTheme) is used, it recolors the
+    /// gradient using its own colors (unless the gradient is modified).
+    pub fn new_preset_default(low: f32, mid: f32, high: f32) -> Result<Self, ()> {
+        Self::new_preset_default_impl(low, mid, high, false)
+    }
+
+    /// Preset constructor: use the default preset (see [`ColorGradientPreset::set_default`]),
+    /// from "good" at `low` to "bad" at `high`.
+    ///
+    /// This is what the built-in entries use, if lower values are better.
+    #[allow(clippy::result_unit_err)]
+    ///
+    /// If a [`PerfUiTheme`](crate::theme::PerfUiTheme) is used, it recolors the
+    /// gradient using its own colors (unless the gradient is modified).
+    pub fn new_preset_default_reversed(low: f32, mid: f32, high: f32) -> Result<Self, ()> {
+        Self::new_preset_default_impl(low, mid, high, true)
+    }
+
+    fn new_preset_default_impl(low: f32, mid: f32, high: f32, reversed: bool) -> Result<Self, ()> {
+        let mut gradient = ColorGradientPreset::get_default().gradient(low, mid, high)?;
+        if reversed {
+            gradient.reverse();
+        }
+        gradient.preset = Some(DefaultPreset {
+            low, mid, high, reversed,
+            stops: gradient.stops.clone(),
+        });
+        Ok(gradient)
+    }
+
+    /// Recolor a gradient created by `new_preset_default(_reversed)`,
+    /// using the given colors for bad, middling, and good values.
+    ///
+    /// Does nothing if the gradient was created any other way,
+    /// or has been modified since. Returns `true` if anything was changed.
+    pub(crate) fn recolor_default_preset(&mut self, bad: Color, mid: Color, good: Color) -> bool {
+        let Some(preset) = &mut self.preset else {
+            return false;
+        };
+        if preset.stops != self.stops {
+            return false;
+        }
+        let mut colors = [bad, mid, good];
+        if preset.reversed {
+            colors.reverse();
+        }
+        let stops: Vec<(FloatOrd, Oklaba)> = [preset.low, preset.mid, preset.high].into_iter()
+            .map(FloatOrd)
+            .zip(colors.into_iter().map(Oklaba::from))
+            .collect();
+        if stops == self.stops {
+            return false;
+        }
+        preset.stops = stops.clone();
+        self.stops = stops;
+        true
+    }
+
+    /// Five colors: at low, mid, high, and halfway in-between.
+    fn new_preset_5(low: f32, mid: f32, high: f32, colors: [Color; 5]) -> Result<Self, ()> {
+        if low.is_nan() || mid.is_nan() || high.is_nan() || low > mid || mid > high {
+            return Err(());
+        }
+        let values = [low, (low + mid) / 2.0, mid, (mid + high) / 2.0, high];
+        Ok(ColorGradient::new().with_stops(values.into_iter().zip(colors)))
+    }
+
+    /// Is the gradient in "stepped" mode (no interpolation)?
+    pub fn is_stepped(&self) -> bool {
+        self.stepped
+    }
+
+    /// Enable or disable "stepped" mode.
+    ///
+    /// In stepped mode, values get the color of the nearest stop at or below them,
+    /// without interpolation. Useful for clearly-distinguishable discrete levels.
+    ///
+    /// See `with_stepped` for a builder-style version of this method.
+    pub fn set_stepped(&mut self, stepped: bool) {
+        self.stepped = stepped;
+    }
+
+    /// Enable or disable "stepped" mode (builder-style API).
+    ///
+    /// See `set_stepped` for a non-builder-style version of this method.
+    pub fn with_stepped(mut self, stepped: bool) -> Self {
+        self.stepped = stepped;
+        self
+    }
+
+    /// Reverse the order of the colors, keeping the values of the stops.
+    ///
+    /// For example, this turns a Red-Yellow-Green gradient into Green-Yellow-Red.
+    ///
+    /// See `reversed` for a builder-style version of this method.
+    pub fn reverse(&mut self) {
+        let colors: Vec<Oklaba> = self.stops.iter().rev().map(|(_, c)| *c).collect();
+        for (stop, color) in self.stops.iter_mut().zip(colors) {
+            stop.1 = color;
+        }
+    }
+
+    /// Reverse the order of the colors, keeping the values of the stops (builder-style API).
+    ///
+    /// See `reverse` for a non-builder-style version of this method.
+    pub fn reversed(mut self) -> Self {
+        self.reverse();
+        self
+    }
+
     /// Add a stop to the gradient.
     ///
     /// See `with_stop` for a builder-style version of this method.
@@ -151,7 +403,7 @@ impl ColorGradient {
     /// If the value is below the lowest stop, the lowest stop's color is returned.
     ///
     /// If the value is in-between, a color interpolated between the nearest two stops'
-    /// colors is returned.
+    /// colors is returned. In stepped mode, the lower of the two stops' color is returned.
     ///
     /// If the gradient is empty (no stops were added), returns `None`.
     pub fn get_color_for_value(&self, value: f32) -> Option<Color> {
@@ -175,8 +427,13 @@ impl ColorGradient {
                 Some(self.stops[i].1.into())
             }
             Err(i) => {
+                // the stops could be out of order, if they were modified via reflection
+                let i = i.clamp(1, self.stops.len() - 1);
                 let stop_low = self.stops[i - 1];
                 let stop_high = self.stops[i];
+                if self.stepped {
+                    return Some(stop_low.1.into());
+                }
                 let lerp_value = (value.0 - stop_low.0.0) / (stop_high.0.0 - stop_low.0.0);
                 Some(stop_low.1.mix(&stop_high.1, lerp_value).into())
             }
@@ -218,6 +475,149 @@ impl ColorGradient {
     }
 }
 
+/// Serialized as a list of `(value, color)` stops.
+///
+/// In stepped mode, serialized as a struct: `(stops: [...], stepped: true)`.
+///
+/// Non-finite stop values (such as the one created by [`ColorGradient::single`])
+/// are serialized as `None`, so that they survive formats like JSON.
+#[cfg(feature = "serde")]
+impl serde::Serialize for ColorGradient {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        struct Stops<'a>(&'a [(FloatOrd, Oklaba)]);
+        impl serde::Serialize for Stops<'_> {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.collect_seq(
+                    self.0.iter().map(|(f, c)| (StopValue(f.0), Color::Oklaba(*c)))
+                )
+            }
+        }
+
+        if self.stepped {
+            let mut s = serializer.serialize_struct("ColorGradient", 2)?;
+            s.serialize_field("stops", &Stops(&self.stops))?;
+            s.serialize_field("stepped", &self.stepped)?;
+            s.end()
+        } else {
+            Stops(&self.stops).serialize(serializer)
+        }
+    }
+}
+
+/// Deserialized from a list of `(value, color)` stops, in any order,
+/// or from a struct: `(stops: [...], stepped: true)`.
+///
+/// A stop value of `None` (or `null`) means negative infinity.
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for ColorGradient {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct GradientVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for GradientVisitor {
+            type Value = ColorGradient;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                write!(f, "a list of (value, color) stops, or a struct with `stops` and `stepped`")
+            }
+
+            fn visit_seq<A: serde::de::SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                let mut gradient = ColorGradient::new();
+                while let Some((value, color)) = seq.next_element::<(StopValue, Color)>()? {
+                    gradient.add_stop(value.0, color);
+                }
+                Ok(gradient)
+            }
+
+            fn visit_map<A: serde::de::MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                #[derive(serde::Deserialize)]
+                #[serde(field_identifier, rename_all = "snake_case")]
+                enum Field {
+                    Stops,
+                    Stepped,
+                }
+
+                let mut gradient = ColorGradient::new();
+                while let Some(key) = map.next_key::<Field>()? {
+                    match key {
+                        Field::Stops => {
+                            let stops = map.next_value::<Vec<(StopValue, Color)>>()?;
+                            gradient.add_stops(stops.into_iter().map(|(value, color)| (value.0, color)));
+                        }
+                        Field::Stepped => {
+                            gradient.stepped = map.next_value()?;
+                        }
+                    }
+                }
+                Ok(gradient)
+            }
+        }
+
+        deserializer.deserialize_any(GradientVisitor)
+    }
+}
+
+/// The value of a [`ColorGradient`] stop in serialized form.
+///
+/// Finite values are plain numbers. Non-finite values are written as `None`
+/// (many formats, like JSON, cannot represent infinity) and read back as
+/// negative infinity.
+#[cfg(feature = "serde")]
+struct StopValue(f32);
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for StopValue {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if self.0.is_finite() {
+            serializer.serialize_f32(self.0)
+        } else {
+            serializer.serialize_none()
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for StopValue {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct StopValueVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for StopValueVisitor {
+            type Value = StopValue;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                write!(f, "a number, or none for negative infinity")
+            }
+
+            fn visit_f64<E: serde::de::Error>(self, v: f64) -> Result<Self::Value, E> {
+                Ok(StopValue(v as f32))
+            }
+
+            fn visit_i64<E: serde::de::Error>(self, v: i64) -> Result<Self::Value, E> {
+                Ok(StopValue(v as f32))
+            }
+
+            fn visit_u64<E: serde::de::Error>(self, v: u64) -> Result<Self::Value, E> {
+                Ok(StopValue(v as f32))
+            }
+
+            fn visit_none<E: serde::de::Error>(self) -> Result<Self::Value, E> {
+                Ok(StopValue(f32::NEG_INFINITY))
+            }
+
+            fn visit_unit<E: serde::de::Error>(self) -> Result<Self::Value, E> {
+                Ok(StopValue(f32::NEG_INFINITY))
+            }
+
+            fn visit_some<D: serde::Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+                deserializer.deserialize_any(self)
+            }
+        }
+
+        deserializer.deserialize_any(StopValueVisitor)
+    }
+}
+
 /// Format a float in a pretty way.
 ///
 /// - Right aligned
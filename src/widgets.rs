@@ -8,9 +8,56 @@ use crate::prelude::*;
 /// Prelude of predefined widget types.
 pub mod prelude {
     pub use super::bar::PerfUiWidgetBar;
+    pub use super::graph::PerfUiWidgetGraph;
+    pub use super::histogram::PerfUiWidgetHistogram;
+    pub use super::stacked_bar::PerfUiWidgetStackedBar;
+    pub use super::table::PerfUiWidgetTable;
 }
 
 pub mod bar;
+pub mod graph;
+pub mod histogram;
+pub mod stacked_bar;
+pub mod table;
+
+/// Compute the range of values that a widget should visualize.
+///
+/// Based on the widget's color gradient and the entry's min/max value hints.
+pub(crate) fn display_range<V, E>(gradient: &ColorGradient, entry: &E) -> Option<(f64, f64)>
+where
+    V: num_traits::ToPrimitive,
+    E: crate::entry::PerfUiEntryDisplayRange<Value = V>,
+{
+    use num_traits::NumCast;
+    let g_min = gradient.min_stop()
+        .map(|(v, _)| *v as f64);
+    let g_max = gradient.max_stop()
+        .map(|(v, _)| *v as f64);
+    let h_min = entry.min_value_hint()
+        .and_then(|v| <f64 as NumCast>::from(v));
+    let h_max = entry.max_value_hint()
+        .and_then(|v| <f64 as NumCast>::from(v));
+    if g_min == g_max {
+        if let (Some(h_min), Some(h_max)) = (h_min, h_max) {
+            return Some((h_min, h_max));
+        } else {
+            return None;
+        }
+    }
+    let v_min = match (g_min, h_min) {
+        (Some(g_min), Some(h_min)) => g_min.min(h_min),
+        (Some(g_min), None) => g_min,
+        (None, Some(h_min)) => h_min,
+        (None, None) => return None,
+    };
+    let v_max = match (g_max, h_max) {
+        (Some(g_max), Some(h_max)) => g_max.max(h_max),
+        (Some(g_max), None) => g_max,
+        (None, Some(h_max)) => h_max,
+        (None, None) => return None,
+    };
+    Some((v_min, v_max))
+}
 
 #[cfg(feature = "entries")]
 pub(crate) fn predefined_widgets_plugin(app: &mut App) {
@@ -19,9 +66,13 @@ pub(crate) fn predefined_widgets_plugin(app: &mut App) {
     app.add_perf_ui_widget::<bar::PerfUiWidgetBar<PerfUiEntryFrameTime>, _>();
     app.add_perf_ui_widget::<bar::PerfUiWidgetBar<PerfUiEntryFPSWorst>, _>();
     app.add_perf_ui_widget::<bar::PerfUiWidgetBar<PerfUiEntryFrameTimeWorst>, _>();
+    app.add_perf_ui_widget::<bar::PerfUiWidgetBar<PerfUiEntryFrameTimePercentile>, _>();
+    app.add_perf_ui_widget::<bar::PerfUiWidgetBar<PerfUiEntryFrameTimeJitter>, _>();
+    app.add_perf_ui_widget::<bar::PerfUiWidgetBar<PerfUiEntryHitches>, _>();
     app.add_perf_ui_widget::<bar::PerfUiWidgetBar<PerfUiEntryFPSPctLow>, _>();
     app.add_perf_ui_widget::<bar::PerfUiWidgetBar<PerfUiEntryFPSAverage>, _>();
     app.add_perf_ui_widget::<bar::PerfUiWidgetBar<PerfUiEntryEntityCount>, _>();
+    app.add_perf_ui_widget::<bar::PerfUiWidgetBar<PerfUiEntryDiagnostic>, _>();
     #[cfg(feature = "sysinfo")]
     app.add_perf_ui_widget::<bar::PerfUiWidgetBar<PerfUiEntryCpuUsage>, _>();
     #[cfg(feature = "sysinfo")]
@@ -33,4 +84,159 @@ pub(crate) fn predefined_widgets_plugin(app: &mut App) {
     app.add_perf_ui_widget::<bar::PerfUiWidgetBar<PerfUiEntryFixedOverstep>, _>();
     app.add_perf_ui_widget::<bar::PerfUiWidgetBar<PerfUiEntryRenderCpuTime>, _>();
     app.add_perf_ui_widget::<bar::PerfUiWidgetBar<PerfUiEntryRenderGpuTime>, _>();
+    app.add_perf_ui_widget::<bar::PerfUiWidgetBar<PerfUiEntryScheduleTime>, _>();
+    app.add_perf_ui_widget::<bar::PerfUiWidgetBar<PerfUiEntryArchetypeCount>, _>();
+    app.add_perf_ui_widget::<bar::PerfUiWidgetBar<PerfUiEntryTableCount>, _>();
+    app.add_perf_ui_widget::<bar::PerfUiWidgetBar<PerfUiEntryComponentCount>, _>();
+    app.add_perf_ui_widget::<bar::PerfUiWidgetBar<PerfUiEntryResourceCount>, _>();
+    app.add_perf_ui_widget::<bar::PerfUiWidgetBar<PerfUiEntryComponentInstanceCount>, _>();
+    app.add_perf_ui_widget::<table::PerfUiWidgetTable<PerfUiEntryRenderPasses>, _>();
+    app.add_perf_ui_widget::<table::PerfUiWidgetTable<PerfUiEntryScheduleBreakdown>, _>();
+    app.add_perf_ui_widget::<stacked_bar::PerfUiWidgetStackedBar<PerfUiEntryRenderPasses>, _>();
+    app.add_perf_ui_widget::<stacked_bar::PerfUiWidgetStackedBar<PerfUiEntryScheduleBreakdown>, _>();
+
+    app.register_type::<bar::BarTextPosition>();
+    app.register_type::<bar::BarFillDirection>();
+    app.register_type::<graph::GraphStyle>();
+    app.register_type::<bar::PerfUiWidgetBar<PerfUiEntryFPS>>();
+    app.register_type::<bar::PerfUiWidgetBar<PerfUiEntryFrameTime>>();
+    app.register_type::<bar::PerfUiWidgetBar<PerfUiEntryFPSWorst>>();
+    app.register_type::<bar::PerfUiWidgetBar<PerfUiEntryFrameTimeWorst>>();
+    app.register_type::<bar::PerfUiWidgetBar<PerfUiEntryFrameTimePercentile>>();
+    app.register_type::<bar::PerfUiWidgetBar<PerfUiEntryFrameTimeJitter>>();
+    app.register_type::<bar::PerfUiWidgetBar<PerfUiEntryHitches>>();
+    app.register_type::<bar::PerfUiWidgetBar<PerfUiEntryFPSPctLow>>();
+    app.register_type::<bar::PerfUiWidgetBar<PerfUiEntryFPSAverage>>();
+    app.register_type::<bar::PerfUiWidgetBar<PerfUiEntryEntityCount>>();
+    app.register_type::<bar::PerfUiWidgetBar<PerfUiEntryDiagnostic>>();
+    #[cfg(feature = "sysinfo")]
+    {
+        app.register_type::<bar::PerfUiWidgetBar<PerfUiEntryCpuUsage>>();
+        app.register_type::<bar::PerfUiWidgetBar<PerfUiEntryMemUsage>>();
+        app.register_type::<bar::PerfUiWidgetBar<PerfUiEntrySystemCpuUsage>>();
+        app.register_type::<bar::PerfUiWidgetBar<PerfUiEntrySystemMemUsage>>();
+    }
+    app.register_type::<bar::PerfUiWidgetBar<PerfUiEntryFixedOverstep>>();
+    app.register_type::<bar::PerfUiWidgetBar<PerfUiEntryRenderCpuTime>>();
+    app.register_type::<bar::PerfUiWidgetBar<PerfUiEntryRenderGpuTime>>();
+    app.register_type::<bar::PerfUiWidgetBar<PerfUiEntryScheduleTime>>();
+    app.register_type::<bar::PerfUiWidgetBar<PerfUiEntryArchetypeCount>>();
+    app.register_type::<bar::PerfUiWidgetBar<PerfUiEntryTableCount>>();
+    app.register_type::<bar::PerfUiWidgetBar<PerfUiEntryComponentCount>>();
+    app.register_type::<bar::PerfUiWidgetBar<PerfUiEntryResourceCount>>();
+    app.register_type::<bar::PerfUiWidgetBar<PerfUiEntryComponentInstanceCount>>();
+    app.register_type::<table::PerfUiWidgetTable<PerfUiEntryRenderPasses>>();
+    app.register_type::<table::PerfUiWidgetTable<PerfUiEntryScheduleBreakdown>>();
+    app.register_type::<stacked_bar::PerfUiWidgetStackedBar<PerfUiEntryRenderPasses>>();
+    app.register_type::<stacked_bar::PerfUiWidgetStackedBar<PerfUiEntryScheduleBreakdown>>();
+
+    #[cfg(feature = "asset")]
+    {
+        // (widgets for `PerfUiEntryDiagnostic` are not supported, as it has no default path)
+        add_bar_layout_entry::<PerfUiEntryFPS>(app, "FPS");
+        add_bar_layout_entry::<PerfUiEntryFrameTime>(app, "FrameTime");
+        add_bar_layout_entry::<PerfUiEntryFPSWorst>(app, "FPSWorst");
+        add_bar_layout_entry::<PerfUiEntryFrameTimeWorst>(app, "FrameTimeWorst");
+        add_bar_layout_entry::<PerfUiEntryFrameTimePercentile>(app, "FrameTimePercentile");
+        add_bar_layout_entry::<PerfUiEntryFrameTimeJitter>(app, "FrameTimeJitter");
+        add_bar_layout_entry::<PerfUiEntryHitches>(app, "Hitches");
+        add_bar_layout_entry::<PerfUiEntryFPSPctLow>(app, "FPSPctLow");
+        add_bar_layout_entry::<PerfUiEntryFPSAverage>(app, "FPSAverage");
+        add_bar_layout_entry::<PerfUiEntryEntityCount>(app, "EntityCount");
+        #[cfg(feature = "sysinfo")]
+        {
+            add_bar_layout_entry::<PerfUiEntryCpuUsage>(app, "CpuUsage");
+            add_bar_layout_entry::<PerfUiEntryMemUsage>(app, "MemUsage");
+            add_bar_layout_entry::<PerfUiEntrySystemCpuUsage>(app, "SystemCpuUsage");
+            add_bar_layout_entry::<PerfUiEntrySystemMemUsage>(app, "SystemMemUsage");
+        }
+        add_bar_layout_entry::<PerfUiEntryFixedOverstep>(app, "FixedOverstep");
+        add_bar_layout_entry::<PerfUiEntryRenderCpuTime>(app, "RenderCpuTime");
+        add_bar_layout_entry::<PerfUiEntryRenderGpuTime>(app, "RenderGpuTime");
+        add_bar_layout_entry::<PerfUiEntryScheduleTime>(app, "ScheduleTime");
+        add_bar_layout_entry::<PerfUiEntryArchetypeCount>(app, "ArchetypeCount");
+        add_bar_layout_entry::<PerfUiEntryTableCount>(app, "TableCount");
+        add_bar_layout_entry::<PerfUiEntryComponentCount>(app, "ComponentCount");
+        add_bar_layout_entry::<PerfUiEntryResourceCount>(app, "ResourceCount");
+        add_bar_layout_entry::<PerfUiEntryComponentInstanceCount>(app, "ComponentInstanceCount");
+        app.add_perf_ui_layout_entry::<table::PerfUiWidgetTable<PerfUiEntryRenderPasses>>("Table<RenderPasses>");
+        app.add_perf_ui_layout_entry::<table::PerfUiWidgetTable<PerfUiEntryScheduleBreakdown>>("Table<ScheduleBreakdown>");
+        app.add_perf_ui_layout_entry::<stacked_bar::PerfUiWidgetStackedBar<PerfUiEntryRenderPasses>>("StackedBar<RenderPasses>");
+        app.add_perf_ui_layout_entry::<stacked_bar::PerfUiWidgetStackedBar<PerfUiEntryScheduleBreakdown>>("StackedBar<ScheduleBreakdown>");
+
+        add_history_layout_entries::<PerfUiEntryFPS>(app, "FPS");
+        add_history_layout_entries::<PerfUiEntryFrameTime>(app, "FrameTime");
+        add_history_layout_entries::<PerfUiEntryEntityCount>(app, "EntityCount");
+        #[cfg(feature = "sysinfo")]
+        {
+            add_history_layout_entries::<PerfUiEntryCpuUsage>(app, "CpuUsage");
+            add_history_layout_entries::<PerfUiEntryMemUsage>(app, "MemUsage");
+            add_history_layout_entries::<PerfUiEntrySystemCpuUsage>(app, "SystemCpuUsage");
+            add_history_layout_entries::<PerfUiEntrySystemMemUsage>(app, "SystemMemUsage");
+        }
+        add_history_layout_entries::<PerfUiEntryFixedOverstep>(app, "FixedOverstep");
+        add_history_layout_entries::<PerfUiEntryRenderCpuTime>(app, "RenderCpuTime");
+        add_history_layout_entries::<PerfUiEntryRenderGpuTime>(app, "RenderGpuTime");
+        add_history_layout_entries::<PerfUiEntryScheduleTime>(app, "ScheduleTime");
+        add_history_layout_entries::<PerfUiEntryArchetypeCount>(app, "ArchetypeCount");
+        add_history_layout_entries::<PerfUiEntryTableCount>(app, "TableCount");
+        add_history_layout_entries::<PerfUiEntryComponentCount>(app, "ComponentCount");
+        add_history_layout_entries::<PerfUiEntryResourceCount>(app, "ResourceCount");
+        add_history_layout_entries::<PerfUiEntryComponentInstanceCount>(app, "ComponentInstanceCount");
+    }
+
+    add_history_widgets::<PerfUiEntryFPS>(app);
+    add_history_widgets::<PerfUiEntryFrameTime>(app);
+    add_history_widgets::<PerfUiEntryEntityCount>(app);
+    add_history_widgets::<PerfUiEntryDiagnostic>(app);
+    #[cfg(feature = "sysinfo")]
+    add_history_widgets::<PerfUiEntryCpuUsage>(app);
+    #[cfg(feature = "sysinfo")]
+    add_history_widgets::<PerfUiEntryMemUsage>(app);
+    #[cfg(feature = "sysinfo")]
+    add_history_widgets::<PerfUiEntrySystemCpuUsage>(app);
+    #[cfg(feature = "sysinfo")]
+    add_history_widgets::<PerfUiEntrySystemMemUsage>(app);
+    add_history_widgets::<PerfUiEntryFixedOverstep>(app);
+    add_history_widgets::<PerfUiEntryRenderCpuTime>(app);
+    add_history_widgets::<PerfUiEntryRenderGpuTime>(app);
+    add_history_widgets::<PerfUiEntryScheduleTime>(app);
+    add_history_widgets::<PerfUiEntryArchetypeCount>(app);
+    add_history_widgets::<PerfUiEntryTableCount>(app);
+    add_history_widgets::<PerfUiEntryComponentCount>(app);
+    add_history_widgets::<PerfUiEntryResourceCount>(app);
+    add_history_widgets::<PerfUiEntryComponentInstanceCount>(app);
+}
+
+#[cfg(feature = "entries")]
+fn add_history_widgets<E>(app: &mut App)
+where
+    E: crate::entry::PerfUiEntryDisplayRange + crate::entry::PerfUiEntryHistory,
+    E::Value: num_traits::Num + num_traits::NumCast + Copy,
+    E: bevy::reflect::FromReflect + bevy::reflect::Reflectable,
+{
+    app.register_type::<graph::PerfUiWidgetGraph<E>>();
+    app.register_type::<histogram::PerfUiWidgetHistogram<E>>();
+    app.add_perf_ui_widget::<graph::PerfUiWidgetGraph<E>, E>();
+    app.add_perf_ui_widget::<histogram::PerfUiWidgetHistogram<E>, E>();
+    app.add_perf_ui_history::<E>();
+}
+
+#[cfg(all(feature = "entries", feature = "asset"))]
+fn add_bar_layout_entry<E>(app: &mut App, name: &str)
+where
+    E: crate::entry::PerfUiEntryDisplayRange + Clone + Default + serde::de::DeserializeOwned,
+    E::Value: num_traits::Num + num_traits::ToPrimitive + Copy,
+{
+    app.add_perf_ui_layout_entry::<bar::PerfUiWidgetBar<E>>(format!("Bar<{}>", name));
+}
+
+#[cfg(all(feature = "entries", feature = "asset"))]
+fn add_history_layout_entries<E>(app: &mut App, name: &str)
+where
+    E: crate::entry::PerfUiEntryDisplayRange + crate::entry::PerfUiEntryHistory + Clone + Default + serde::de::DeserializeOwned,
+    E::Value: num_traits::Num + num_traits::NumCast + Copy,
+{
+    app.add_perf_ui_layout_entry::<graph::PerfUiWidgetGraph<E>>(format!("Graph<{}>", name));
+    app.add_perf_ui_layout_entry::<histogram::PerfUiWidgetHistogram<E>>(format!("Histogram<{}>", name));
 }
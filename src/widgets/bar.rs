@@ -13,11 +13,14 @@ use bevy::prelude::*;
 use bevy::ecs::system::lifetimeless::SQuery;
 
 use crate::entry::{PerfUiEntry, PerfUiEntryDisplayRange};
+use crate::ui::root::PerfUiRoot;
 use crate::ui::widget::{PerfUiWidget, PerfUiWidgetMarker};
 use crate::utils::ColorGradient;
 
 /// Where should the text value be displayed inside the bar?
-#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Debug, Default, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum BarTextPosition {
     /// Do not display the value as text. Bar only.
     NoText,
@@ -35,7 +38,9 @@ pub enum BarTextPosition {
 }
 
 /// Which way should the bar fill up?
-#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Debug, Default, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum BarFillDirection {
     /// From left to right.
     #[default]
@@ -52,7 +57,12 @@ pub enum BarFillDirection {
 /// of the data value to be displayed by the bar.
 ///
 /// It allows you to customize the properties of the bar.
-#[derive(Component)]
+#[derive(Component, Clone, Reflect)]
+#[require(PerfUiRoot)]
+#[reflect(Component)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[cfg_attr(feature = "serde", serde(bound(deserialize = "E: serde::Deserialize<'de> + Default, E::Value: num_traits::Num + num_traits::ToPrimitive + Copy")))]
 pub struct PerfUiWidgetBar<E: PerfUiEntryDisplayRange> {
     /// Should the bar also display the value as text? Where?
     pub text_position: BarTextPosition,
@@ -76,6 +86,133 @@ pub struct PerfUiWidgetBar<E: PerfUiEntryDisplayRange> {
     pub entry: E,
 }
 
+/// The layout of a bar, shared by the Bar Widget and other bar-like widgets.
+pub(crate) struct BarLayout {
+    pub(crate) text_position: BarTextPosition,
+    pub(crate) background: Color,
+    pub(crate) border_px: f32,
+    pub(crate) border_color: Color,
+    pub(crate) height_px: Option<f32>,
+    pub(crate) length_px: Option<f32>,
+}
+
+/// The entities of a bar spawned by [`BarLayout::spawn`].
+pub(crate) struct BarLayoutParts {
+    /// Goes into the values column of the widget.
+    pub(crate) e_wrapper: Entity,
+    /// The bar itself (background and border).
+    pub(crate) e_outer: Entity,
+    /// The area inside the border. Put the filled portion of the bar here.
+    pub(crate) e_fill_area: Entity,
+}
+
+impl BarLayout {
+    pub(crate) fn spawn(
+        &self,
+        root: &crate::prelude::PerfUiRoot,
+        commands: &mut Commands,
+    ) -> BarLayoutParts {
+        let e_outer = commands.spawn((
+            BackgroundColor(self.background),
+            BorderColor(self.border_color),
+            Node {
+                border: UiRect::all(Val::Px(self.border_px)),
+                height: if let Some(h) = self.height_px {
+                    Val::Px(h)
+                } else {
+                    Val::Auto
+                },
+                width: if let Some(w) = self.length_px {
+                    Val::Px(w)
+                } else {
+                    Val::Auto
+                },
+                flex_grow: if self.length_px.is_some() {
+                    0.0
+                } else {
+                    1.0
+                },
+                justify_content: match self.text_position {
+                    BarTextPosition::Start => JustifyContent::FlexStart,
+                    BarTextPosition::End => JustifyContent::FlexEnd,
+                    _ => JustifyContent::Center,
+                },
+                align_items: AlignItems::Center,
+                ..default()
+            },
+        )).id();
+        let e_fill_area = commands.spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(0.0),
+                bottom: Val::Px(self.border_px * 2.0),
+                left: Val::Px(0.0),
+                right: Val::Px(self.border_px * 2.0),
+                flex_direction: FlexDirection::Row,
+                overflow: Overflow::clip(),
+                ..default()
+            },
+        )).id();
+        commands.entity(e_outer).add_child(e_fill_area);
+        let e_wrapper = commands.spawn((
+            Node {
+                padding: UiRect::all(Val::Px(4.0)),
+                width: Val::Px(root.values_col_width),
+                flex_grow: 0.0,
+                justify_content: JustifyContent::SpaceBetween,
+                flex_direction: match self.text_position {
+                    BarTextPosition::OutsideStart => FlexDirection::RowReverse,
+                    BarTextPosition::OutsideEnd => FlexDirection::Row,
+                    _ => default(),
+                },
+                align_items: if self.height_px.is_some() {
+                    AlignItems::Center
+                } else {
+                    AlignItems::Stretch
+                },
+                ..default()
+            },
+        )).id();
+        commands.entity(e_wrapper).add_child(e_outer);
+        BarLayoutParts {
+            e_wrapper,
+            e_outer,
+            e_fill_area,
+        }
+    }
+
+    /// Put a text entity where `text_position` says.
+    pub(crate) fn add_text(
+        &self,
+        commands: &mut Commands,
+        bar: &BarLayoutParts,
+        e_text: Entity,
+    ) {
+        commands.entity(e_text).insert(Node {
+            margin: match self.text_position {
+                BarTextPosition::OutsideEnd => UiRect {
+                    left: Val::Px(4.0),
+                    ..UiRect::all(Val::Auto)
+                },
+                BarTextPosition::OutsideStart => UiRect {
+                    right: Val::Px(4.0),
+                    ..UiRect::all(Val::Auto)
+                },
+                _ => UiRect::all(Val::Auto),
+            },
+            ..default()
+        });
+        match self.text_position {
+            BarTextPosition::OutsideStart | BarTextPosition::OutsideEnd => {
+                commands.entity(bar.e_wrapper).add_child(e_text);
+            }
+            _ => {
+                commands.entity(bar.e_outer).add_child(e_text);
+            }
+        }
+    }
+}
+
 #[doc(hidden)]
 #[derive(Component)]
 pub struct PerfUiWidgetBarParts {
@@ -117,35 +254,28 @@ where
     }
 
     fn get_range(&self) -> Option<(f64, f64)> {
-        use num_traits::NumCast;
-        let g_min = self.bar_color.min_stop()
-            .map(|(v, _)| *v as f64);
-        let g_max = self.bar_color.max_stop()
-            .map(|(v, _)| *v as f64);
-        let h_min = self.entry.min_value_hint()
-            .and_then(|v| <f64 as NumCast>::from(v));
-        let h_max = self.entry.max_value_hint()
-            .and_then(|v| <f64 as NumCast>::from(v));
-        if g_min == g_max {
-            if let (Some(h_min), Some(h_max)) = (h_min, h_max) {
-                return Some((h_min, h_max));
-            } else {
-                return None;
-            }
+        super::display_range(&self.bar_color, &self.entry)
+    }
+
+    fn layout(&self) -> BarLayout {
+        BarLayout {
+            text_position: self.text_position,
+            background: self.bar_background,
+            border_px: self.bar_border_px,
+            border_color: self.bar_border_color,
+            height_px: self.bar_height_px,
+            length_px: self.bar_length_px,
         }
-        let v_min = match (g_min, h_min) {
-            (Some(g_min), Some(h_min)) => g_min.min(h_min),
-            (Some(g_min), None) => g_min,
-            (None, Some(h_min)) => h_min,
-            (None, None) => return None,
-        };
-        let v_max = match (g_max, h_max) {
-            (Some(g_max), Some(h_max)) => g_max.max(h_max),
-            (Some(g_max), None) => g_max,
-            (None, Some(h_max)) => h_max,
-            (None, None) => return None,
-        };
-        Some((v_min, v_max))
+    }
+}
+
+impl<E> Default for PerfUiWidgetBar<E>
+where
+    E: PerfUiEntryDisplayRange + Default,
+    E::Value: num_traits::Num + num_traits::ToPrimitive + Copy,
+{
+    fn default() -> Self {
+        Self::new(E::default())
     }
 }
 
@@ -158,7 +288,6 @@ where
 {
     type SystemParamSpawn = ();
     type SystemParamUpdate = (
-        E::SystemParam,
         SQuery<(
             &'static mut BackgroundColor,
             &'static PerfUiWidgetBarParts,
@@ -183,45 +312,7 @@ where
         commands: &mut Commands,
         _: &mut <Self::SystemParamSpawn as SystemParam>::Item<'_, '_>,
     ) -> Entity {
-        let e_bar_outer = commands.spawn((
-            BackgroundColor(self.bar_background),
-            BorderColor(self.bar_border_color),
-            Node {
-                border: UiRect::all(Val::Px(self.bar_border_px)),
-                height: if let Some(h) = self.bar_height_px {
-                    Val::Px(h)
-                } else {
-                    Val::Auto
-                },
-                width: if let Some(w) = self.bar_length_px {
-                    Val::Px(w)
-                } else {
-                    Val::Auto
-                },
-                flex_grow: if self.bar_length_px.is_some() {
-                    0.0
-                } else {
-                    1.0
-                },
-                justify_content: match self.text_position {
-                    BarTextPosition::Start => JustifyContent::FlexStart,
-                    BarTextPosition::End => JustifyContent::FlexEnd,
-                    _ => JustifyContent::Center,
-                },
-                align_items: AlignItems::Center,
-                ..default()
-            },
-        )).id();
-        let e_bar_inner_wrapper = commands.spawn((
-            Node {
-                position_type: PositionType::Absolute,
-                top: Val::Px(0.0),
-                bottom: Val::Px(self.bar_border_px * 2.0),
-                left: Val::Px(0.0),
-                right: Val::Px(self.bar_border_px * 2.0),
-                ..default()
-            },
-        )).id();
+        let bar = self.layout().spawn(root, commands);
         let e_bar_inner = commands.spawn((
             BarWidgetInnerBarMarker::<E> {
                 _pd: PhantomData,
@@ -236,51 +327,16 @@ where
                 ..default()
             },
         )).id();
-        commands.entity(e_bar_inner_wrapper).add_child(e_bar_inner);
-        commands.entity(e_bar_outer).add_child(e_bar_inner_wrapper);
+        commands.entity(bar.e_fill_area).add_child(e_bar_inner);
         let mut parts = PerfUiWidgetBarParts {
             e_bar_inner,
             e_text: None,
         };
-        let e_bar_wrapper = commands.spawn((
-            Node {
-                padding: UiRect::all(Val::Px(4.0)),
-                width: Val::Px(root.values_col_width),
-                flex_grow: 0.0,
-                justify_content: JustifyContent::SpaceBetween,
-                flex_direction: match self.text_position {
-                    BarTextPosition::OutsideStart => FlexDirection::RowReverse,
-                    BarTextPosition::OutsideEnd => FlexDirection::Row,
-                    _ => default(),
-                },
-                align_items: if self.bar_height_px.is_some() {
-                    AlignItems::Center
-                } else {
-                    AlignItems::Stretch
-                },
-                ..default()
-            },
-        )).id();
-        commands.entity(e_bar_wrapper).add_child(e_bar_outer);
         if self.text_position != BarTextPosition::NoText {
             let e_text = commands.spawn((
                 BarWidgetTextMarker::<E> {
                     _pd: PhantomData,
                 },
-                Node {
-                    margin: match self.text_position {
-                        BarTextPosition::OutsideEnd => UiRect {
-                            left: Val::Px(4.0),
-                            ..UiRect::all(Val::Auto)
-                        },
-                        BarTextPosition::OutsideStart => UiRect {
-                            right: Val::Px(4.0),
-                            ..UiRect::all(Val::Auto)
-                        },
-                        _ => UiRect::all(Val::Auto),
-                    },
-                    ..default()
-                },
                 Text(root.text_err.clone()),
                 TextFont {
                     font: root.font_value.clone(),
@@ -289,14 +345,7 @@ where
                 },
                 TextColor(self.text_color_override .unwrap_or(root.err_color))
             )).id();
-            match self.text_position {
-                BarTextPosition::OutsideStart | BarTextPosition::OutsideEnd => {
-                    commands.entity(e_bar_wrapper).add_child(e_text);
-                }
-                _ => {
-                    commands.entity(e_bar_outer).add_child(e_text);
-                }
-            }
+            self.layout().add_text(commands, &bar, e_text);
             parts.e_text = Some(e_text);
         }
         let e_widget = commands.spawn((
@@ -330,7 +379,7 @@ where
             commands.entity(e_label_wrapper).add_child(e_label);
             commands.entity(e_widget).add_child(e_label_wrapper);
         }
-        commands.entity(e_widget).add_child(e_bar_wrapper);
+        commands.entity(e_widget).add_child(bar.e_wrapper);
         e_widget
     }
 
@@ -339,18 +388,15 @@ where
         root: &crate::prelude::PerfUiRoot,
         _e_root: Entity,
         e_widget: Entity,
+        value: Option<&E::Value>,
+        entry_highlight: bool,
         (
-            entry_param,
             q_widget,
             q_bar_inner,
             q_text,
         ): &mut <Self::SystemParamUpdate as SystemParam>::Item<'_, '_>,
     ) {
         if let Ok((mut bgcolor, parts)) = q_widget.get_mut(e_widget) {
-            let value = self.entry.update_value(entry_param);
-            let entry_highlight = value
-                .map(|v| self.entry.value_highlight(&v))
-                .unwrap_or(false);
 
             if entry_highlight {
                 bgcolor.0 = root.inner_background_color_highlight;
@@ -360,7 +406,7 @@ where
 
             if let Ok((mut bar_color, mut bar_style)) = q_bar_inner.get_mut(parts.e_bar_inner) {
                 use num_traits::NumCast;
-                let value = value.and_then(|v| <f64 as NumCast>::from(v));
+                let value = value.and_then(|v| <f64 as NumCast>::from(*v));
 
                 if let Some(value) = value {
                     bar_color.0 = self.bar_color.get_color_for_value(value as f32)
@@ -394,7 +440,7 @@ where
 
             if let Some((mut text, mut color, mut font)) = parts.e_text.and_then(|e| q_text.get_mut(e).ok()) {
                 if let Some(value) = value {
-                    let s = self.entry.format_value(&value);
+                    let s = self.entry.format_value(value);
                     *text = Text(s.trim().to_owned());
                     if entry_highlight {
                         font.font = root.font_highlight.clone();
@@ -402,7 +448,7 @@ where
                         font.font = root.font_value.clone();
                     }
                     if self.text_color_override.is_none() {
-                        let new_color = self.entry.value_color(&value)
+                        let new_color = self.entry.value_color(value)
                             .unwrap_or(root.default_value_color);
                         *color = TextColor(new_color);
                     }
@@ -420,5 +466,13 @@ where
     fn sort_key(&self) -> i32 {
         self.entry.sort_key()
     }
+
+    fn entry(&self) -> &E {
+        &self.entry
+    }
+
+    fn entry_mut(&mut self) -> &mut E {
+        &mut self.entry
+    }
 }
 
@@ -0,0 +1,446 @@
+//! Graph Widget
+//!
+//! Displays the recent history of a Perf UI entry as a rolling graph,
+//! instead of a bare value.
+//!
+//! To use it, simply wrap your entry type in the [`PerfUiWidgetGraph`]
+//! struct, and insert that as a component to your Perf UI entity,
+//! instead of inserting the entry directly as a component.
+//!
+//! The entry must implement [`PerfUiEntryHistory`], and its history
+//! must be recorded. For your own entry types, register the widget with
+//! `app.add_perf_ui_widget::<PerfUiWidgetGraph<E>, E>()`, and the entry's history
+//! with `app.add_perf_ui_history::<E>()`.
+
+use std::marker::PhantomData;
+
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use bevy::ecs::system::lifetimeless::SQuery;
+
+use crate::entry::{PerfUiEntry, PerfUiEntryDisplayRange, PerfUiEntryHistory};
+use crate::ui::root::PerfUiRoot;
+use crate::ui::widget::{PerfUiWidget, PerfUiWidgetMarker};
+use crate::utils::ColorGradient;
+use crate::widgets::bar::BarTextPosition;
+
+/// How should the values be drawn?
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Debug, Default, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GraphStyle {
+    /// A column for each value, filled from the bottom.
+    #[default]
+    Columns,
+    /// A line segment at the height of each value.
+    Line,
+}
+
+/// Display a Perf UI entry as a Graph Widget.
+///
+/// This struct wraps the entry type, which will be the source
+/// of the data to be displayed by the graph.
+///
+/// The newest value is displayed at the end (right side) of the graph.
+///
+/// It allows you to customize the properties of the graph.
+#[derive(Component, Clone, Reflect)]
+#[require(PerfUiRoot)]
+#[reflect(Component)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[cfg_attr(feature = "serde", serde(bound(deserialize = "E: serde::Deserialize<'de> + Default, E::Value: num_traits::Num + num_traits::ToPrimitive + Copy")))]
+pub struct PerfUiWidgetGraph<E: PerfUiEntryDisplayRange + PerfUiEntryHistory> {
+    /// Should the graph also display the current value as text? Where?
+    ///
+    /// "Inside" positions overlay the text on top of the graph.
+    pub text_position: BarTextPosition,
+    /// Set the color of the text that displays the value.
+    pub text_color_override: Option<Color>,
+    /// How should the values be drawn?
+    pub style: GraphStyle,
+    /// How many of the most recent values to display.
+    ///
+    /// Each value gets an equal share of the graph's width.
+    /// Should not be larger than the entry's history length.
+    pub samples: usize,
+    /// The thickness of the line, if `style` is `GraphStyle::Line`.
+    pub line_thickness_px: f32,
+    /// What should be the color of each value?
+    ///
+    /// If the entry does not provide a range using [`PerfUiEntryDisplayRange`],
+    /// the gradient's stops will also determine the vertical scale of the graph.
+    pub graph_color: ColorGradient,
+    /// What should be the color behind the graph?
+    pub graph_background: Color,
+    /// The thickness of the graph's border.
+    pub graph_border_px: f32,
+    /// The color of the graph's border.
+    pub graph_border_color: Color,
+    /// Force the graph to have a specific height in pixels.
+    pub graph_height_px: Option<f32>,
+    /// Force the graph to have a specific length in pixels.
+    pub graph_length_px: Option<f32>,
+    /// The entry (data source for the graph widget).
+    pub entry: E,
+}
+
+#[doc(hidden)]
+#[derive(Component)]
+pub struct PerfUiWidgetGraphParts {
+    e_samples: Vec<Entity>,
+    e_text: Option<Entity>,
+}
+
+#[doc(hidden)]
+#[derive(Component)]
+pub struct GraphWidgetSampleMarker<E: PerfUiEntry> {
+    _pd: PhantomData<E>,
+}
+
+#[doc(hidden)]
+#[derive(Component)]
+pub struct GraphWidgetTextMarker<E: PerfUiEntry> {
+    _pd: PhantomData<E>,
+}
+
+impl<V, E> PerfUiWidgetGraph<E>
+where
+    V: num_traits::Num + num_traits::ToPrimitive + Copy,
+    E: PerfUiEntry<Value = V> + PerfUiEntryDisplayRange + PerfUiEntryHistory,
+{
+    /// Create a new Graph widget with default settings
+    pub fn new(entry: E) -> Self {
+        Self {
+            text_position: BarTextPosition::Start,
+            text_color_override: None,
+            style: default(),
+            samples: 60,
+            line_thickness_px: 2.0,
+            graph_color: ColorGradient::single(Color::srgb(0.5, 0.5, 0.5)),
+            graph_background: Color::srgba(0.0, 0.0, 0.0, 0.5),
+            graph_border_color: Color::srgb(0.0, 0.0, 0.0),
+            graph_border_px: 1.0,
+            graph_height_px: None,
+            graph_length_px: None,
+            entry,
+        }
+    }
+
+    fn get_range(&self) -> Option<(f64, f64)> {
+        super::display_range(&self.graph_color, &self.entry)
+    }
+}
+
+impl<E> Default for PerfUiWidgetGraph<E>
+where
+    E: PerfUiEntryDisplayRange + PerfUiEntryHistory + Default,
+    E::Value: num_traits::Num + num_traits::ToPrimitive + Copy,
+{
+    fn default() -> Self {
+        Self::new(E::default())
+    }
+}
+
+type GraphWidgetMarker<E> = PerfUiWidgetMarker<PerfUiWidgetGraph<E>>;
+
+impl<V, E> PerfUiWidget<E> for PerfUiWidgetGraph<E>
+where
+    V: num_traits::Num + num_traits::ToPrimitive + Copy,
+    E: PerfUiEntry<Value = V> + PerfUiEntryDisplayRange + PerfUiEntryHistory,
+{
+    type SystemParamSpawn = ();
+    type SystemParamUpdate = (
+        SQuery<(
+            &'static mut BackgroundColor,
+            &'static PerfUiWidgetGraphParts,
+        ), (
+            With<GraphWidgetMarker<E>>,
+            Without<GraphWidgetSampleMarker<E>>,
+        )>,
+        SQuery<(
+            &'static mut BackgroundColor,
+            &'static mut Node,
+        ), (
+            With<GraphWidgetSampleMarker<E>>,
+            Without<GraphWidgetMarker<E>>,
+        )>,
+        SQuery<(&'static mut Text, &'static mut TextColor, &'static mut TextFont), With<GraphWidgetTextMarker<E>>>,
+    );
+
+    fn spawn(
+        &self,
+        root: &crate::prelude::PerfUiRoot,
+        _e_root: Entity,
+        commands: &mut Commands,
+        _: &mut <Self::SystemParamSpawn as SystemParam>::Item<'_, '_>,
+    ) -> Entity {
+        let e_graph_outer = commands.spawn((
+            BackgroundColor(self.graph_background),
+            BorderColor(self.graph_border_color),
+            Node {
+                border: UiRect::all(Val::Px(self.graph_border_px)),
+                height: if let Some(h) = self.graph_height_px {
+                    Val::Px(h)
+                } else {
+                    Val::Auto
+                },
+                width: if let Some(w) = self.graph_length_px {
+                    Val::Px(w)
+                } else {
+                    Val::Auto
+                },
+                flex_grow: if self.graph_length_px.is_some() {
+                    0.0
+                } else {
+                    1.0
+                },
+                justify_content: match self.text_position {
+                    BarTextPosition::Start => JustifyContent::FlexStart,
+                    BarTextPosition::End => JustifyContent::FlexEnd,
+                    _ => JustifyContent::Center,
+                },
+                align_items: AlignItems::Center,
+                ..default()
+            },
+        )).id();
+        let e_graph_inner_wrapper = commands.spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(0.0),
+                bottom: Val::Px(self.graph_border_px * 2.0),
+                left: Val::Px(0.0),
+                right: Val::Px(self.graph_border_px * 2.0),
+                ..default()
+            },
+        )).id();
+        let samples = self.samples.max(1);
+        let sample_width = 100.0 / samples as f32;
+        let e_samples: Vec<Entity> = (0..samples).map(|i| {
+            commands.spawn((
+                GraphWidgetSampleMarker::<E> {
+                    _pd: PhantomData,
+                },
+                BackgroundColor(Color::NONE),
+                Node {
+                    position_type: PositionType::Absolute,
+                    bottom: Val::Percent(0.0),
+                    height: Val::Percent(0.0),
+                    left: Val::Percent(i as f32 * sample_width),
+                    width: Val::Percent(sample_width),
+                    ..default()
+                },
+            )).id()
+        }).collect();
+        commands.entity(e_graph_inner_wrapper).add_children(&e_samples);
+        commands.entity(e_graph_outer).add_child(e_graph_inner_wrapper);
+        let mut parts = PerfUiWidgetGraphParts {
+            e_samples,
+            e_text: None,
+        };
+        let e_graph_wrapper = commands.spawn((
+            Node {
+                padding: UiRect::all(Val::Px(4.0)),
+                width: Val::Px(root.values_col_width),
+                flex_grow: 0.0,
+                justify_content: JustifyContent::SpaceBetween,
+                flex_direction: match self.text_position {
+                    BarTextPosition::OutsideStart => FlexDirection::RowReverse,
+                    BarTextPosition::OutsideEnd => FlexDirection::Row,
+                    _ => default(),
+                },
+                align_items: if self.graph_height_px.is_some() {
+                    AlignItems::Center
+                } else {
+                    AlignItems::Stretch
+                },
+                ..default()
+            },
+        )).id();
+        commands.entity(e_graph_wrapper).add_child(e_graph_outer);
+        if self.text_position != BarTextPosition::NoText {
+            let e_text = commands.spawn((
+                GraphWidgetTextMarker::<E> {
+                    _pd: PhantomData,
+                },
+                Node {
+                    margin: match self.text_position {
+                        BarTextPosition::OutsideEnd => UiRect {
+                            left: Val::Px(4.0),
+                            ..UiRect::all(Val::Auto)
+                        },
+                        BarTextPosition::OutsideStart => UiRect {
+                            right: Val::Px(4.0),
+                            ..UiRect::all(Val::Auto)
+                        },
+                        BarTextPosition::Start => UiRect {
+                            left: Val::Px(2.0),
+                            ..UiRect::all(Val::Auto)
+                        },
+                        BarTextPosition::End => UiRect {
+                            right: Val::Px(2.0),
+                            ..UiRect::all(Val::Auto)
+                        },
+                        _ => UiRect::all(Val::Auto),
+                    },
+                    ..default()
+                },
+                Text(root.text_err.clone()),
+                TextFont {
+                    font: root.font_value.clone(),
+                    font_size: root.fontsize_value,
+                    ..default()
+                },
+                TextColor(self.text_color_override.unwrap_or(root.err_color))
+            )).id();
+            match self.text_position {
+                BarTextPosition::OutsideStart | BarTextPosition::OutsideEnd => {
+                    commands.entity(e_graph_wrapper).add_child(e_text);
+                }
+                _ => {
+                    commands.entity(e_graph_outer).add_child(e_text);
+                }
+            }
+            parts.e_text = Some(e_text);
+        }
+        let e_widget = commands.spawn((
+            parts,
+            BackgroundColor(root.inner_background_color),
+            Node {
+                flex_direction: FlexDirection::Row,
+                justify_content: JustifyContent::SpaceBetween,
+                align_items: AlignItems::Center,
+                margin: UiRect::all(Val::Px(root.inner_margin)),
+                padding: UiRect::all(Val::Px(root.inner_padding)),
+                ..default()
+            },
+        )).id();
+        if root.display_labels {
+            let e_label_wrapper = commands.spawn((
+                Node {
+                    padding: UiRect::all(Val::Px(4.0)),
+                    ..default()
+                },
+            )).id();
+            let e_label = commands.spawn((
+                Text(format!("{}: ", self.entry.label())),
+                TextFont {
+                    font: root.font_label.clone(),
+                    font_size: root.fontsize_label,
+                    ..default()
+                },
+                TextColor(root.label_color)
+            )).id();
+            commands.entity(e_label_wrapper).add_child(e_label);
+            commands.entity(e_widget).add_child(e_label_wrapper);
+        }
+        commands.entity(e_widget).add_child(e_graph_wrapper);
+        e_widget
+    }
+
+    fn update(
+        &self,
+        root: &crate::prelude::PerfUiRoot,
+        _e_root: Entity,
+        e_widget: Entity,
+        value: Option<&E::Value>,
+        entry_highlight: bool,
+        (
+            q_widget,
+            q_sample,
+            q_text,
+        ): &mut <Self::SystemParamUpdate as SystemParam>::Item<'_, '_>,
+    ) {
+        use num_traits::NumCast;
+
+        let Ok((mut bgcolor, parts)) = q_widget.get_mut(e_widget) else {
+            return;
+        };
+
+
+        if entry_highlight {
+            bgcolor.0 = root.inner_background_color_highlight;
+        } else {
+            bgcolor.0 = root.inner_background_color;
+        }
+
+        // the most recent values, aligned so that the newest is at the end
+        let history = self.entry.history();
+        let n_samples = parts.e_samples.len();
+        let n_skip = history.len().saturating_sub(n_samples);
+        let n_empty = n_samples.saturating_sub(history.len());
+        let values: Vec<Option<f64>> = std::iter::repeat_n(None, n_empty)
+            .chain(history.iter().skip(n_skip).map(|v| <f64 as NumCast>::from(*v)))
+            .collect();
+
+        // if we don't know the range, scale the graph to fit the values
+        let (v_min, v_max) = self.get_range().unwrap_or_else(|| {
+            let v_max = values.iter().flatten().copied().fold(0.0, f64::max);
+            let v_min = values.iter().flatten().copied().fold(0.0, f64::min);
+            (v_min, v_max)
+        });
+
+        for (e_sample, value) in parts.e_samples.iter().zip(values) {
+            let Ok((mut sample_color, mut sample_style)) = q_sample.get_mut(*e_sample) else {
+                continue;
+            };
+            let Some(value) = value else {
+                sample_color.0 = Color::NONE;
+                continue;
+            };
+            sample_color.0 = self.graph_color.get_color_for_value(value as f32)
+                .unwrap_or(Color::NONE);
+            let pct = if v_max > v_min {
+                ((value - v_min) / (v_max - v_min)).clamp(0.0, 1.0) * 100.0
+            } else {
+                0.0
+            };
+            match self.style {
+                GraphStyle::Columns => {
+                    sample_style.bottom = Val::Percent(0.0);
+                    sample_style.height = Val::Percent(pct as f32);
+                }
+                GraphStyle::Line => {
+                    sample_style.bottom = Val::Percent(pct as f32);
+                    sample_style.height = Val::Px(self.line_thickness_px);
+                    sample_style.margin.bottom = Val::Px(-self.line_thickness_px / 2.0);
+                }
+            }
+        }
+
+        if let Some((mut text, mut color, mut font)) = parts.e_text.and_then(|e| q_text.get_mut(e).ok()) {
+            if let Some(value) = value {
+                let s = self.entry.format_value(value);
+                *text = Text(s.trim().to_owned());
+                if entry_highlight {
+                    font.font = root.font_highlight.clone();
+                } else {
+                    font.font = root.font_value.clone();
+                }
+                if self.text_color_override.is_none() {
+                    let new_color = self.entry.value_color(value)
+                        .unwrap_or(root.default_value_color);
+                    *color = TextColor(new_color);
+                }
+            } else {
+                *text = Text(root.text_err.trim().to_owned());
+                font.font = root.font_value.clone();
+                if self.text_color_override.is_none() {
+                    *color = TextColor(root.err_color);
+                }
+            }
+        }
+    }
+
+    fn sort_key(&self) -> i32 {
+        self.entry.sort_key()
+    }
+
+    fn entry(&self) -> &E {
+        &self.entry
+    }
+
+    fn entry_mut(&mut self) -> &mut E {
+        &mut self.entry
+    }
+}
@@ -0,0 +1,353 @@
+//! Histogram Widget
+//!
+//! Displays the distribution of the recent values of a Perf UI entry,
+//! as a histogram, instead of a bare value.
+//!
+//! To use it, simply wrap your entry type in the [`PerfUiWidgetHistogram`]
+//! struct, and insert that as a component to your Perf UI entity,
+//! instead of inserting the entry directly as a component.
+//!
+//! The entry must implement [`PerfUiEntryHistory`], and its history
+//! must be recorded. For your own entry types, register the widget with
+//! `app.add_perf_ui_widget::<PerfUiWidgetHistogram<E>, E>()`, and the entry's history
+//! with `app.add_perf_ui_history::<E>()`.
+
+use std::marker::PhantomData;
+
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use bevy::ecs::system::lifetimeless::SQuery;
+
+use crate::entry::{PerfUiEntry, PerfUiEntryDisplayRange, PerfUiEntryHistory};
+use crate::ui::root::PerfUiRoot;
+use crate::ui::widget::{PerfUiWidget, PerfUiWidgetMarker};
+use crate::utils::ColorGradient;
+
+/// Display a Perf UI entry as a Histogram Widget.
+///
+/// This struct wraps the entry type, whose history will be the source
+/// of the data to be displayed by the histogram.
+///
+/// The values are sorted into bins of equal width, starting from
+/// the entry's minimum value hint. The bins are displayed as vertical
+/// bars, with heights proportional to how many values fell into each.
+/// Values outside of the range are counted in the first/last bin.
+///
+/// It allows you to customize the properties of the histogram.
+#[derive(Component, Clone, Reflect)]
+#[require(PerfUiRoot)]
+#[reflect(Component)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[cfg_attr(feature = "serde", serde(bound(deserialize = "E: serde::Deserialize<'de> + Default, E::Value: num_traits::Num + num_traits::NumCast + Copy")))]
+pub struct PerfUiWidgetHistogram<E: PerfUiEntryDisplayRange + PerfUiEntryHistory> {
+    /// The width of each bin, in the units of the entry's value.
+    ///
+    /// For example, `1.0` gives 1 ms bins for frame time entries.
+    pub bin_width: f32,
+    /// The maximum number of bins to display.
+    ///
+    /// If the entry's range would need more bins of `bin_width`,
+    /// the range is cut off at the end.
+    pub max_bins: usize,
+    /// What should be the color of each bin?
+    ///
+    /// If `None`, the color the entry would use for the value at
+    /// the center of the bin is used. Usually, that means the entry's
+    /// own `ColorGradient`.
+    pub bin_color: Option<ColorGradient>,
+    /// What should be the color behind the histogram?
+    pub histogram_background: Color,
+    /// The thickness of the histogram's border.
+    pub histogram_border_px: f32,
+    /// The color of the histogram's border.
+    pub histogram_border_color: Color,
+    /// Force the histogram to have a specific height in pixels.
+    pub histogram_height_px: Option<f32>,
+    /// Force the histogram to have a specific length in pixels.
+    pub histogram_length_px: Option<f32>,
+    /// The entry (data source for the histogram widget).
+    pub entry: E,
+}
+
+#[doc(hidden)]
+#[derive(Component)]
+pub struct PerfUiWidgetHistogramParts {
+    e_bins: Vec<Entity>,
+}
+
+#[doc(hidden)]
+#[derive(Component)]
+pub struct HistogramWidgetBinMarker<E: PerfUiEntry> {
+    _pd: PhantomData<E>,
+}
+
+impl<V, E> PerfUiWidgetHistogram<E>
+where
+    V: num_traits::Num + num_traits::NumCast + Copy,
+    E: PerfUiEntry<Value = V> + PerfUiEntryDisplayRange + PerfUiEntryHistory,
+{
+    /// Create a new Histogram widget with default settings
+    pub fn new(entry: E) -> Self {
+        Self {
+            bin_width: 1.0,
+            max_bins: 64,
+            bin_color: None,
+            histogram_background: Color::srgba(0.0, 0.0, 0.0, 0.5),
+            histogram_border_color: Color::srgb(0.0, 0.0, 0.0),
+            histogram_border_px: 1.0,
+            histogram_height_px: Some(32.0),
+            histogram_length_px: None,
+            entry,
+        }
+    }
+
+    /// Compute the start of the range and the number of bins.
+    fn get_bins(&self) -> (f64, usize) {
+        let bin_width = self.bin_width as f64;
+        let range = super::display_range(
+            self.bin_color.as_ref().unwrap_or(&ColorGradient::new()),
+            &self.entry,
+        );
+        match range {
+            Some((v_min, v_max)) if v_max > v_min && bin_width > 0.0 => {
+                let n_bins = ((v_max - v_min) / bin_width).ceil() as usize;
+                (v_min, n_bins.clamp(1, self.max_bins.max(1)))
+            }
+            // we can't know where to put the bins without a range,
+            // so just start at zero and show as many as allowed
+            _ => (0.0, self.max_bins.max(1)),
+        }
+    }
+
+    fn get_bin_color(&self, value: f64) -> Option<Color> {
+        use num_traits::NumCast;
+        if let Some(gradient) = &self.bin_color {
+            gradient.get_color_for_value(value as f32)
+        } else {
+            <V as NumCast>::from(value)
+                .and_then(|v| self.entry.value_color(&v))
+        }
+    }
+}
+
+impl<E> Default for PerfUiWidgetHistogram<E>
+where
+    E: PerfUiEntryDisplayRange + PerfUiEntryHistory + Default,
+    E::Value: num_traits::Num + num_traits::NumCast + Copy,
+{
+    fn default() -> Self {
+        Self::new(E::default())
+    }
+}
+
+type HistogramWidgetMarker<E> = PerfUiWidgetMarker<PerfUiWidgetHistogram<E>>;
+
+impl<V, E> PerfUiWidget<E> for PerfUiWidgetHistogram<E>
+where
+    V: num_traits::Num + num_traits::NumCast + Copy,
+    E: PerfUiEntry<Value = V> + PerfUiEntryDisplayRange + PerfUiEntryHistory,
+{
+    type SystemParamSpawn = ();
+    type SystemParamUpdate = (
+        SQuery<(
+            &'static mut BackgroundColor,
+            &'static PerfUiWidgetHistogramParts,
+        ), (
+            With<HistogramWidgetMarker<E>>,
+            Without<HistogramWidgetBinMarker<E>>,
+        )>,
+        SQuery<(
+            &'static mut BackgroundColor,
+            &'static mut Node,
+        ), (
+            With<HistogramWidgetBinMarker<E>>,
+            Without<HistogramWidgetMarker<E>>,
+        )>,
+    );
+
+    fn spawn(
+        &self,
+        root: &crate::prelude::PerfUiRoot,
+        _e_root: Entity,
+        commands: &mut Commands,
+        _: &mut <Self::SystemParamSpawn as SystemParam>::Item<'_, '_>,
+    ) -> Entity {
+        let e_histogram_outer = commands.spawn((
+            BackgroundColor(self.histogram_background),
+            BorderColor(self.histogram_border_color),
+            Node {
+                border: UiRect::all(Val::Px(self.histogram_border_px)),
+                height: if let Some(h) = self.histogram_height_px {
+                    Val::Px(h)
+                } else {
+                    Val::Auto
+                },
+                width: if let Some(w) = self.histogram_length_px {
+                    Val::Px(w)
+                } else {
+                    Val::Auto
+                },
+                flex_grow: if self.histogram_length_px.is_some() {
+                    0.0
+                } else {
+                    1.0
+                },
+                ..default()
+            },
+        )).id();
+        let e_histogram_inner_wrapper = commands.spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(0.0),
+                bottom: Val::Px(self.histogram_border_px * 2.0),
+                left: Val::Px(0.0),
+                right: Val::Px(self.histogram_border_px * 2.0),
+                ..default()
+            },
+        )).id();
+        let (_, n_bins) = self.get_bins();
+        let bin_width = 100.0 / n_bins as f32;
+        let e_bins: Vec<Entity> = (0..n_bins).map(|i| {
+            commands.spawn((
+                HistogramWidgetBinMarker::<E> {
+                    _pd: PhantomData,
+                },
+                BackgroundColor(Color::NONE),
+                Node {
+                    position_type: PositionType::Absolute,
+                    bottom: Val::Percent(0.0),
+                    height: Val::Percent(0.0),
+                    left: Val::Percent(i as f32 * bin_width),
+                    width: Val::Percent(bin_width),
+                    ..default()
+                },
+            )).id()
+        }).collect();
+        commands.entity(e_histogram_inner_wrapper).add_children(&e_bins);
+        commands.entity(e_histogram_outer).add_child(e_histogram_inner_wrapper);
+        let e_histogram_wrapper = commands.spawn((
+            Node {
+                padding: UiRect::all(Val::Px(4.0)),
+                width: Val::Px(root.values_col_width),
+                flex_grow: 0.0,
+                align_items: if self.histogram_height_px.is_some() {
+                    AlignItems::Center
+                } else {
+                    AlignItems::Stretch
+                },
+                ..default()
+            },
+        )).id();
+        commands.entity(e_histogram_wrapper).add_child(e_histogram_outer);
+        let e_widget = commands.spawn((
+            PerfUiWidgetHistogramParts {
+                e_bins,
+            },
+            BackgroundColor(root.inner_background_color),
+            Node {
+                flex_direction: FlexDirection::Row,
+                justify_content: JustifyContent::SpaceBetween,
+                align_items: AlignItems::Center,
+                margin: UiRect::all(Val::Px(root.inner_margin)),
+                padding: UiRect::all(Val::Px(root.inner_padding)),
+                ..default()
+            },
+        )).id();
+        if root.display_labels {
+            let e_label_wrapper = commands.spawn((
+                Node {
+                    padding: UiRect::all(Val::Px(4.0)),
+                    ..default()
+                },
+            )).id();
+            let e_label = commands.spawn((
+                Text(format!("{}: ", self.entry.label())),
+                TextFont {
+                    font: root.font_label.clone(),
+                    font_size: root.fontsize_label,
+                    ..default()
+                },
+                TextColor(root.label_color)
+            )).id();
+            commands.entity(e_label_wrapper).add_child(e_label);
+            commands.entity(e_widget).add_child(e_label_wrapper);
+        }
+        commands.entity(e_widget).add_child(e_histogram_wrapper);
+        e_widget
+    }
+
+    fn update(
+        &self,
+        root: &crate::prelude::PerfUiRoot,
+        _e_root: Entity,
+        e_widget: Entity,
+        _value: Option<&E::Value>,
+        entry_highlight: bool,
+        (
+            q_widget,
+            q_bin,
+        ): &mut <Self::SystemParamUpdate as SystemParam>::Item<'_, '_>,
+    ) {
+        use num_traits::NumCast;
+
+        let Ok((mut bgcolor, parts)) = q_widget.get_mut(e_widget) else {
+            return;
+        };
+
+        if entry_highlight {
+            bgcolor.0 = root.inner_background_color_highlight;
+        } else {
+            bgcolor.0 = root.inner_background_color;
+        }
+
+        let (v_min, _) = self.get_bins();
+        let bin_width = self.bin_width as f64;
+        let n_bins = parts.e_bins.len();
+        let mut counts = vec![0u32; n_bins];
+        for value in self.entry.history().iter() {
+            let Some(value) = <f64 as NumCast>::from(*value) else {
+                continue;
+            };
+            if value.is_nan() || n_bins == 0 {
+                continue;
+            }
+            let i = if bin_width > 0.0 {
+                ((value - v_min) / bin_width).floor().max(0.0) as usize
+            } else {
+                0
+            };
+            counts[i.min(n_bins - 1)] += 1;
+        }
+        let max_count = counts.iter().copied().max().unwrap_or(0);
+
+        for (i, (e_bin, count)) in parts.e_bins.iter().zip(counts).enumerate() {
+            let Ok((mut bin_color, mut bin_style)) = q_bin.get_mut(*e_bin) else {
+                continue;
+            };
+            if count == 0 {
+                bin_color.0 = Color::NONE;
+                bin_style.height = Val::Percent(0.0);
+                continue;
+            }
+            let bin_center = v_min + (i as f64 + 0.5) * bin_width;
+            bin_color.0 = self.get_bin_color(bin_center)
+                .unwrap_or(root.default_value_color);
+            bin_style.height = Val::Percent(count as f32 / max_count as f32 * 100.0);
+            // make sure rare values are always visible
+            bin_style.min_height = Val::Px(1.0);
+        }
+    }
+
+    fn sort_key(&self) -> i32 {
+        self.entry.sort_key()
+    }
+
+    fn entry(&self) -> &E {
+        &self.entry
+    }
+
+    fn entry_mut(&mut self) -> &mut E {
+        &mut self.entry
+    }
+}
@@ -0,0 +1,523 @@
+//! Stacked Bar Widget
+//!
+//! Displays a Perf UI entry whose value is made of several parts (such
+//! as the time spent on each render pass) as one bar, split into segments,
+//! with a legend.
+//!
+//! To use it, simply wrap your entry type in the [`PerfUiWidgetStackedBar`]
+//! struct, and insert that as a component to your Perf UI entity,
+//! instead of inserting the entry directly as a component.
+//!
+//! The entry must implement [`PerfUiEntryBreakdown`] and [`PerfUiEntryDisplayRange`].
+
+use std::marker::PhantomData;
+
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use bevy::ecs::system::lifetimeless::SQuery;
+
+use crate::entry::{PerfUiBreakdownPart, PerfUiEntry, PerfUiEntryBreakdown, PerfUiEntryDisplayRange};
+use crate::ui::root::PerfUiRoot;
+use crate::ui::widget::{PerfUiWidget, PerfUiWidgetMarker};
+use crate::utils::ColorGradient;
+
+use super::bar::{BarLayout, BarTextPosition};
+
+/// Display a Perf UI entry as a Stacked Bar Widget.
+///
+/// This struct wraps the entry type, which will be the source
+/// of the data to be displayed by the bar.
+///
+/// Each part of the entry's value is shown as a segment of the bar, with its
+/// own color. If the entry provides a range for its total, using
+/// [`PerfUiEntryDisplayRange`] (such as a frame time budget), the bar
+/// represents that range, and the unfilled portion is the headroom.
+/// Otherwise, the segments always fill the whole bar, showing how the
+/// total is split.
+///
+/// It allows you to customize the properties of the bar.
+#[derive(Component, Clone, Reflect)]
+#[require(PerfUiRoot)]
+#[reflect(Component)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[cfg_attr(feature = "serde", serde(bound(deserialize = "E: serde::Deserialize<'de> + Default")))]
+pub struct PerfUiWidgetStackedBar<E: PerfUiEntryBreakdown + PerfUiEntryDisplayRange> {
+    /// Display the value as a whole (such as the total) as text, next to the bar?
+    ///
+    /// Default: `true`
+    pub display_total: bool,
+    /// Display a legend below the bar, with the color, label, and value of each part?
+    ///
+    /// Default: `true`
+    pub display_legend: bool,
+    /// The colors to use for the segments.
+    ///
+    /// Every part gets the next color the first time it is displayed, and
+    /// keeps it, even if the order of the parts changes. If there are more
+    /// parts than colors, the colors are reused.
+    ///
+    /// Default: the Okabe-Ito palette (distinguishable with color blindness).
+    pub segment_colors: Vec<Color>,
+    /// Set the color of the text that displays the value as a whole.
+    pub text_color_override: Option<Color>,
+    /// What should be the color of the unfilled portion of the bar?
+    pub bar_background: Color,
+    /// The thickness of the bar's border.
+    pub bar_border_px: f32,
+    /// The color of the bar's border.
+    pub bar_border_color: Color,
+    /// Force the bar to have a specific height in pixels.
+    ///
+    /// Default: `None` (same as the font size of the values)
+    pub bar_height_px: Option<f32>,
+    /// Force the bar to have a specific length in pixels.
+    pub bar_length_px: Option<f32>,
+    /// The entry (data source for the stacked bar widget).
+    pub entry: E,
+}
+
+#[doc(hidden)]
+#[derive(Component)]
+pub struct PerfUiWidgetStackedBarParts {
+    e_segments: Entity,
+    e_total: Option<Entity>,
+    e_legend: Option<Entity>,
+    /// The segments we have spawned so far.
+    segments: Vec<Entity>,
+    /// The legend rows we have spawned so far: (row, swatch, label text, value text).
+    legend: Vec<(Entity, Entity, Entity, Entity)>,
+    /// The labels of the parts we have seen so far, in the order of their colors.
+    colors: Vec<String>,
+}
+
+#[doc(hidden)]
+#[derive(Component)]
+pub struct StackedBarWidgetSegmentMarker<E: PerfUiEntry> {
+    _pd: PhantomData<E>,
+}
+
+#[doc(hidden)]
+#[derive(Component)]
+pub struct StackedBarWidgetLegendMarker<E: PerfUiEntry> {
+    _pd: PhantomData<E>,
+}
+
+#[doc(hidden)]
+#[derive(Component)]
+pub struct StackedBarWidgetTextMarker<E: PerfUiEntry> {
+    _pd: PhantomData<E>,
+}
+
+impl<E: PerfUiEntryBreakdown + PerfUiEntryDisplayRange> PerfUiWidgetStackedBar<E> {
+    /// Create a new Stacked Bar widget with default settings
+    pub fn new(entry: E) -> Self {
+        Self {
+            display_total: true,
+            display_legend: true,
+            segment_colors: vec![
+                Color::srgb_u8(230, 159, 0),
+                Color::srgb_u8(86, 180, 233),
+                Color::srgb_u8(0, 158, 115),
+                Color::srgb_u8(240, 228, 66),
+                Color::srgb_u8(0, 114, 178),
+                Color::srgb_u8(213, 94, 0),
+                Color::srgb_u8(204, 121, 167),
+            ],
+            text_color_override: None,
+            bar_background: Color::srgba(0.0, 0.0, 0.0, 0.5),
+            bar_border_color: Color::srgb(0.0, 0.0, 0.0),
+            bar_border_px: 1.0,
+            bar_height_px: None,
+            bar_length_px: None,
+            entry,
+        }
+    }
+
+    fn layout(&self, root: &crate::prelude::PerfUiRoot) -> BarLayout {
+        BarLayout {
+            text_position: BarTextPosition::OutsideEnd,
+            background: self.bar_background,
+            border_px: self.bar_border_px,
+            border_color: self.bar_border_color,
+            height_px: Some(self.bar_height_px.unwrap_or(root.fontsize_value)),
+            length_px: self.bar_length_px,
+        }
+    }
+
+    /// The range of values the bar represents, if the entry provides one.
+    fn get_range(&self) -> Option<(f64, f64)> {
+        // the segments have their own colors, so there is no gradient
+        super::display_range(&ColorGradient::default(), &self.entry)
+    }
+
+    fn segment_color(&self, colors: &mut Vec<String>, part: &PerfUiBreakdownPart) -> Color {
+        let i = match colors.iter().position(|label| *label == part.label) {
+            Some(i) => i,
+            None => {
+                colors.push(part.label.clone());
+                colors.len() - 1
+            }
+        };
+        if self.segment_colors.is_empty() {
+            Color::srgb(0.5, 0.5, 0.5)
+        } else {
+            self.segment_colors[i % self.segment_colors.len()]
+        }
+    }
+
+    fn spawn_text(
+        &self,
+        root: &crate::prelude::PerfUiRoot,
+        commands: &mut Commands,
+        text: String,
+        is_value: bool,
+    ) -> Entity {
+        commands.spawn((
+            StackedBarWidgetTextMarker::<E> {
+                _pd: PhantomData,
+            },
+            Text(text),
+            TextFont {
+                font: if is_value {
+                    root.font_value.clone()
+                } else {
+                    root.font_label.clone()
+                },
+                font_size: if is_value {
+                    root.fontsize_value
+                } else {
+                    root.fontsize_label
+                },
+                ..default()
+            },
+            TextColor(if is_value {
+                root.err_color
+            } else {
+                root.label_color
+            }),
+            TextLayout {
+                linebreak: LineBreak::NoWrap,
+                justify: if is_value {
+                    JustifyText::Right
+                } else {
+                    JustifyText::Left
+                },
+            },
+        )).id()
+    }
+}
+
+impl<E> Default for PerfUiWidgetStackedBar<E>
+where
+    E: PerfUiEntryBreakdown + PerfUiEntryDisplayRange + Default,
+{
+    fn default() -> Self {
+        Self::new(E::default())
+    }
+}
+
+type StackedBarWidgetMarker<E> = PerfUiWidgetMarker<PerfUiWidgetStackedBar<E>>;
+
+impl<E: PerfUiEntryBreakdown + PerfUiEntryDisplayRange> PerfUiWidget<E> for PerfUiWidgetStackedBar<E> {
+    type SystemParamSpawn = ();
+    type SystemParamUpdate = (
+        Commands<'static, 'static>,
+        SQuery<(
+            &'static mut BackgroundColor,
+            &'static mut PerfUiWidgetStackedBarParts,
+        ), (
+            With<StackedBarWidgetMarker<E>>,
+            Without<StackedBarWidgetSegmentMarker<E>>,
+        )>,
+        SQuery<(
+            &'static mut BackgroundColor,
+            &'static mut Node,
+        ), (
+            With<StackedBarWidgetSegmentMarker<E>>,
+            Without<StackedBarWidgetMarker<E>>,
+        )>,
+        SQuery<&'static mut Node, (
+            With<StackedBarWidgetLegendMarker<E>>,
+            Without<StackedBarWidgetSegmentMarker<E>>,
+        )>,
+        SQuery<(&'static mut Text, &'static mut TextColor, &'static mut TextFont), With<StackedBarWidgetTextMarker<E>>>,
+    );
+
+    fn spawn(
+        &self,
+        root: &crate::prelude::PerfUiRoot,
+        _e_root: Entity,
+        commands: &mut Commands,
+        _: &mut <Self::SystemParamSpawn as SystemParam>::Item<'_, '_>,
+    ) -> Entity {
+        let bar = self.layout(root).spawn(root, commands);
+        let e_segments = bar.e_fill_area;
+        let e_total = self.display_total.then(|| {
+            let e_total = self.spawn_text(root, commands, root.text_err.clone(), true);
+            commands.entity(e_total).insert(
+                TextColor(self.text_color_override.unwrap_or(root.err_color)),
+            );
+            self.layout(root).add_text(commands, &bar, e_total);
+            e_total
+        });
+        let e_top = commands.spawn((
+            Node {
+                flex_direction: FlexDirection::Row,
+                justify_content: JustifyContent::SpaceBetween,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+        )).id();
+        if root.display_labels {
+            let e_label_wrapper = commands.spawn((
+                Node {
+                    padding: UiRect::all(Val::Px(4.0)),
+                    ..default()
+                },
+            )).id();
+            let e_label = self.spawn_text(root, commands, format!("{}: ", self.entry.label()), false);
+            commands.entity(e_label_wrapper).add_child(e_label);
+            commands.entity(e_top).add_child(e_label_wrapper);
+        }
+        commands.entity(e_top).add_child(bar.e_wrapper);
+        let e_widget = commands.spawn((
+            BackgroundColor(root.inner_background_color),
+            Node {
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Stretch,
+                margin: UiRect::all(Val::Px(root.inner_margin)),
+                padding: UiRect::all(Val::Px(root.inner_padding)),
+                ..default()
+            },
+        )).id();
+        commands.entity(e_widget).add_child(e_top);
+        let e_legend = self.display_legend.then(|| {
+            let e_legend = commands.spawn((
+                Node {
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Stretch,
+                    ..default()
+                },
+            )).id();
+            commands.entity(e_widget).add_child(e_legend);
+            e_legend
+        });
+        commands.entity(e_widget).insert(PerfUiWidgetStackedBarParts {
+            e_segments,
+            e_total,
+            e_legend,
+            segments: Vec::new(),
+            legend: Vec::new(),
+            colors: Vec::new(),
+        });
+        e_widget
+    }
+
+    fn update(
+        &self,
+        root: &crate::prelude::PerfUiRoot,
+        _e_root: Entity,
+        e_widget: Entity,
+        value: Option<&E::Value>,
+        entry_highlight: bool,
+        (
+            commands,
+            q_widget,
+            q_segment,
+            q_legend,
+            q_text,
+        ): &mut <Self::SystemParamUpdate as SystemParam>::Item<'_, '_>,
+    ) {
+        let Ok((mut bgcolor, mut parts)) = q_widget.get_mut(e_widget) else {
+            return;
+        };
+        let parts = &mut *parts;
+
+        if entry_highlight {
+            bgcolor.0 = root.inner_background_color_highlight;
+        } else {
+            bgcolor.0 = root.inner_background_color;
+        }
+
+        if let Some((mut text, mut color, mut font)) = parts.e_total.and_then(|e| q_text.get_mut(e).ok()) {
+            if let Some(value) = value {
+                *text = Text(self.entry.format_value(value).trim().to_owned());
+                if entry_highlight {
+                    font.font = root.font_highlight.clone();
+                } else {
+                    font.font = root.font_value.clone();
+                }
+                if self.text_color_override.is_none() {
+                    *color = TextColor(self.entry.value_color(value)
+                        .unwrap_or(root.default_value_color));
+                }
+            } else {
+                *text = Text(root.text_err.trim().to_owned());
+                font.font = root.font_value.clone();
+                if self.text_color_override.is_none() {
+                    *color = TextColor(root.err_color);
+                }
+            }
+        }
+
+        let empty = Vec::new();
+        let value_parts = value.map(|v| &v.parts).unwrap_or(&empty);
+
+        // the range of the bar: from the entry, unless the total does not fit
+        let total = value.map(|v| v.total()).unwrap_or(0.0);
+        let range = match self.get_range() {
+            Some((v_min, v_max)) => (v_max - v_min).max(total),
+            None => total,
+        };
+        let pct = |part: &PerfUiBreakdownPart| {
+            if range > 0.0 {
+                (part.value / range).clamp(0.0, 1.0) as f32 * 100.0
+            } else {
+                0.0
+            }
+        };
+
+        // spawn any segments and legend rows we are missing; they are set up
+        // right away, because they can only be updated on the next update
+        while parts.segments.len() < value_parts.len() {
+            let part = &value_parts[parts.segments.len()];
+            let color = self.segment_color(&mut parts.colors, part);
+            let e_segment = commands.spawn((
+                StackedBarWidgetSegmentMarker::<E> {
+                    _pd: PhantomData,
+                },
+                BackgroundColor(color),
+                Node {
+                    width: Val::Percent(pct(part)),
+                    height: Val::Percent(100.0),
+                    flex_shrink: 0.0,
+                    ..default()
+                },
+            )).id();
+            commands.entity(parts.e_segments).add_child(e_segment);
+            parts.segments.push(e_segment);
+        }
+        if let Some(e_legend) = parts.e_legend {
+            while parts.legend.len() < value_parts.len() {
+                let part = &value_parts[parts.legend.len()];
+                let color = self.segment_color(&mut parts.colors, part);
+                let e_swatch = commands.spawn((
+                    StackedBarWidgetSegmentMarker::<E> {
+                        _pd: PhantomData,
+                    },
+                    BackgroundColor(color),
+                    Node {
+                        width: Val::Px(root.fontsize_label * 0.75),
+                        height: Val::Px(root.fontsize_label * 0.75),
+                        margin: UiRect::right(Val::Px(4.0)),
+                        ..default()
+                    },
+                )).id();
+                let e_label = self.spawn_text(root, commands, part.label.clone(), false);
+                let e_value = self.spawn_text(root, commands, self.entry.format_part(part), true);
+                let font = if self.entry.part_highlight(part) {
+                    root.font_highlight.clone()
+                } else {
+                    root.font_value.clone()
+                };
+                commands.entity(e_value).insert((
+                    TextColor(self.entry.part_color(part).unwrap_or(root.default_value_color)),
+                    TextFont {
+                        font,
+                        font_size: root.fontsize_value,
+                        ..default()
+                    },
+                ));
+                let e_key = commands.spawn((
+                    Node {
+                        flex_direction: FlexDirection::Row,
+                        align_items: AlignItems::Center,
+                        padding: UiRect::all(Val::Px(4.0)),
+                        ..default()
+                    },
+                )).id();
+                commands.entity(e_key).add_children(&[e_swatch, e_label]);
+                let e_value_wrapper = commands.spawn((
+                    Node {
+                        padding: UiRect::all(Val::Px(4.0)),
+                        justify_content: JustifyContent::FlexEnd,
+                        ..default()
+                    },
+                )).id();
+                commands.entity(e_value_wrapper).add_child(e_value);
+                let e_row = commands.spawn((
+                    StackedBarWidgetLegendMarker::<E> {
+                        _pd: PhantomData,
+                    },
+                    Node {
+                        flex_direction: FlexDirection::Row,
+                        justify_content: JustifyContent::SpaceBetween,
+                        align_items: AlignItems::Center,
+                        ..default()
+                    },
+                )).id();
+                commands.entity(e_row).add_children(&[e_key, e_value_wrapper]);
+                commands.entity(e_legend).add_child(e_row);
+                parts.legend.push((e_row, e_swatch, e_label, e_value));
+            }
+        }
+
+        // update the segments we have, and hide the ones we don't need
+        for (i, &e_segment) in parts.segments.iter().enumerate() {
+            let Ok((mut seg_color, mut seg_style)) = q_segment.get_mut(e_segment) else {
+                continue;
+            };
+            let Some(part) = value_parts.get(i) else {
+                seg_style.display = Display::None;
+                continue;
+            };
+            seg_style.display = Display::Flex;
+            seg_style.width = Val::Percent(pct(part));
+            seg_color.0 = self.segment_color(&mut parts.colors, part);
+        }
+
+        // update the legend rows we have, and hide the ones we don't need
+        for (i, &(e_row, e_swatch, e_label, e_value)) in parts.legend.iter().enumerate() {
+            let Some(part) = value_parts.get(i) else {
+                if let Ok(mut node) = q_legend.get_mut(e_row) {
+                    node.display = Display::None;
+                }
+                continue;
+            };
+            if let Ok(mut node) = q_legend.get_mut(e_row) {
+                node.display = Display::Flex;
+            }
+            if let Ok((mut swatch_color, _)) = q_segment.get_mut(e_swatch) {
+                swatch_color.0 = self.segment_color(&mut parts.colors, part);
+            }
+            if let Ok((mut text, _, _)) = q_text.get_mut(e_label) {
+                if text.0 != part.label {
+                    *text = Text(part.label.clone());
+                }
+            }
+            if let Ok((mut text, mut color, mut font)) = q_text.get_mut(e_value) {
+                *text = Text(self.entry.format_part(part));
+                *color = TextColor(self.entry.part_color(part)
+                    .unwrap_or(root.default_value_color));
+                if self.entry.part_highlight(part) {
+                    font.font = root.font_highlight.clone();
+                } else {
+                    font.font = root.font_value.clone();
+                }
+            }
+        }
+    }
+
+    fn sort_key(&self) -> i32 {
+        self.entry.sort_key()
+    }
+
+    fn entry(&self) -> &E {
+        &self.entry
+    }
+
+    fn entry_mut(&mut self) -> &mut E {
+        &mut self.entry
+    }
+}
@@ -0,0 +1,330 @@
+//! Table Widget
+//!
+//! Displays a Perf UI entry whose value is made of several parts (such
+//! as the time spent on each render pass) as a table, with one row per part.
+//!
+//! To use it, simply wrap your entry type in the [`PerfUiWidgetTable`]
+//! struct, and insert that as a component to your Perf UI entity,
+//! instead of inserting the entry directly as a component.
+//!
+//! The entry must implement [`PerfUiEntryBreakdown`].
+
+use std::marker::PhantomData;
+
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use bevy::ecs::system::lifetimeless::SQuery;
+
+use crate::entry::{PerfUiEntry, PerfUiEntryBreakdown};
+use crate::ui::root::PerfUiRoot;
+use crate::ui::widget::{PerfUiWidget, PerfUiWidgetMarker};
+
+/// Display a Perf UI entry as a Table Widget.
+///
+/// This struct wraps the entry type, which will be the source
+/// of the data to be displayed by the table.
+///
+/// The first row shows the entry's label and its value as a whole.
+/// It is followed by one row for each part of the value.
+#[derive(Component, Clone, Reflect)]
+#[require(PerfUiRoot)]
+#[reflect(Component)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[cfg_attr(feature = "serde", serde(bound(deserialize = "E: serde::Deserialize<'de> + Default")))]
+pub struct PerfUiWidgetTable<E: PerfUiEntryBreakdown> {
+    /// Display the value as a whole (such as the total) in the first row?
+    ///
+    /// Default: `true`
+    pub display_total: bool,
+    /// How much to indent the labels of the parts, in pixels.
+    ///
+    /// Default: `12.0`
+    pub indent_px: f32,
+    /// The entry (data source for the table widget).
+    pub entry: E,
+}
+
+#[doc(hidden)]
+#[derive(Component)]
+pub struct PerfUiWidgetTableParts {
+    e_total: Option<Entity>,
+    /// The rows we have spawned so far: (row, label text, value text).
+    rows: Vec<(Entity, Entity, Entity)>,
+}
+
+#[doc(hidden)]
+#[derive(Component)]
+pub struct TableWidgetRowMarker<E: PerfUiEntry> {
+    _pd: PhantomData<E>,
+}
+
+#[doc(hidden)]
+#[derive(Component)]
+pub struct TableWidgetTextMarker<E: PerfUiEntry> {
+    _pd: PhantomData<E>,
+}
+
+impl<E: PerfUiEntryBreakdown> PerfUiWidgetTable<E> {
+    /// Create a new Table widget with default settings
+    pub fn new(entry: E) -> Self {
+        Self {
+            display_total: true,
+            indent_px: 12.0,
+            entry,
+        }
+    }
+
+    fn spawn_text(
+        &self,
+        root: &crate::prelude::PerfUiRoot,
+        commands: &mut Commands,
+        text: String,
+        justify: JustifyText,
+    ) -> Entity {
+        let is_value = justify == JustifyText::Right;
+        commands.spawn((
+            TableWidgetTextMarker::<E> {
+                _pd: PhantomData,
+            },
+            Text(text),
+            TextFont {
+                font: if is_value {
+                    root.font_value.clone()
+                } else {
+                    root.font_label.clone()
+                },
+                font_size: if is_value {
+                    root.fontsize_value
+                } else {
+                    root.fontsize_label
+                },
+                ..default()
+            },
+            TextColor(if is_value {
+                root.err_color
+            } else {
+                root.label_color
+            }),
+            TextLayout {
+                linebreak: LineBreak::NoWrap,
+                justify,
+            },
+        )).id()
+    }
+
+    fn spawn_row(
+        &self,
+        root: &crate::prelude::PerfUiRoot,
+        commands: &mut Commands,
+        e_label: Option<Entity>,
+        e_value: Entity,
+        indent: f32,
+    ) -> Entity {
+        let e_row = commands.spawn((
+            Node {
+                flex_direction: FlexDirection::Row,
+                justify_content: JustifyContent::SpaceBetween,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+        )).id();
+        if let Some(e_label) = e_label {
+            let e_label_wrapper = commands.spawn((
+                Node {
+                    padding: UiRect {
+                        left: Val::Px(4.0 + indent),
+                        ..UiRect::all(Val::Px(4.0))
+                    },
+                    ..default()
+                },
+            )).id();
+            commands.entity(e_label_wrapper).add_child(e_label);
+            commands.entity(e_row).add_child(e_label_wrapper);
+        }
+        let e_value_wrapper = commands.spawn((
+            Node {
+                padding: UiRect::all(Val::Px(4.0)),
+                width: Val::Px(root.values_col_width),
+                justify_content: JustifyContent::FlexEnd,
+                ..default()
+            },
+        )).id();
+        commands.entity(e_value_wrapper).add_child(e_value);
+        commands.entity(e_row).add_child(e_value_wrapper);
+        e_row
+    }
+}
+
+impl<E> Default for PerfUiWidgetTable<E>
+where
+    E: PerfUiEntryBreakdown + Default,
+{
+    fn default() -> Self {
+        Self::new(E::default())
+    }
+}
+
+type TableWidgetMarker<E> = PerfUiWidgetMarker<PerfUiWidgetTable<E>>;
+
+impl<E: PerfUiEntryBreakdown> PerfUiWidget<E> for PerfUiWidgetTable<E> {
+    type SystemParamSpawn = ();
+    type SystemParamUpdate = (
+        Commands<'static, 'static>,
+        SQuery<(
+            &'static mut BackgroundColor,
+            &'static mut PerfUiWidgetTableParts,
+        ), With<TableWidgetMarker<E>>>,
+        SQuery<&'static mut Node, With<TableWidgetRowMarker<E>>>,
+        SQuery<(&'static mut Text, &'static mut TextColor, &'static mut TextFont), With<TableWidgetTextMarker<E>>>,
+    );
+
+    fn spawn(
+        &self,
+        root: &crate::prelude::PerfUiRoot,
+        _e_root: Entity,
+        commands: &mut Commands,
+        _: &mut <Self::SystemParamSpawn as SystemParam>::Item<'_, '_>,
+    ) -> Entity {
+        let e_label = root.display_labels.then(|| {
+            self.spawn_text(root, commands, format!("{}: ", self.entry.label()), JustifyText::Left)
+        });
+        let e_total = self.display_total.then(|| {
+            self.spawn_text(root, commands, root.text_err.clone(), JustifyText::Right)
+        });
+        let e_widget = commands.spawn((
+            PerfUiWidgetTableParts {
+                e_total,
+                rows: Vec::new(),
+            },
+            BackgroundColor(root.inner_background_color),
+            Node {
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Stretch,
+                margin: UiRect::all(Val::Px(root.inner_margin)),
+                padding: UiRect::all(Val::Px(root.inner_padding)),
+                ..default()
+            },
+        )).id();
+        if e_label.is_some() || e_total.is_some() {
+            let e_value = e_total.unwrap_or_else(|| {
+                self.spawn_text(root, commands, String::new(), JustifyText::Right)
+            });
+            let e_header = self.spawn_row(root, commands, e_label, e_value, 0.0);
+            commands.entity(e_widget).add_child(e_header);
+        }
+        e_widget
+    }
+
+    fn update(
+        &self,
+        root: &crate::prelude::PerfUiRoot,
+        _e_root: Entity,
+        e_widget: Entity,
+        value: Option<&E::Value>,
+        entry_highlight: bool,
+        (
+            commands,
+            q_widget,
+            q_row,
+            q_text,
+        ): &mut <Self::SystemParamUpdate as SystemParam>::Item<'_, '_>,
+    ) {
+        let Ok((mut bgcolor, mut parts)) = q_widget.get_mut(e_widget) else {
+            return;
+        };
+
+        if entry_highlight {
+            bgcolor.0 = root.inner_background_color_highlight;
+        } else {
+            bgcolor.0 = root.inner_background_color;
+        }
+
+        if let Some((mut text, mut color, mut font)) = parts.e_total.and_then(|e| q_text.get_mut(e).ok()) {
+            if let Some(value) = value {
+                *text = Text(self.entry.format_value(value));
+                *color = TextColor(self.entry.value_color(value)
+                    .unwrap_or(root.default_value_color));
+                if entry_highlight {
+                    font.font = root.font_highlight.clone();
+                } else {
+                    font.font = root.font_value.clone();
+                }
+            } else {
+                *text = Text(root.text_err.clone());
+                *color = TextColor(root.err_color);
+                font.font = root.font_value.clone();
+            }
+        }
+
+        let empty = Vec::new();
+        let value_parts = value.map(|v| &v.parts).unwrap_or(&empty);
+
+        // spawn any rows we are missing; they get their text right away,
+        // because they can only be updated on the next update
+        while parts.rows.len() < value_parts.len() {
+            let part = &value_parts[parts.rows.len()];
+            let e_label = self.spawn_text(root, commands, part.label.clone(), JustifyText::Left);
+            let e_value = self.spawn_text(root, commands, self.entry.format_part(part), JustifyText::Right);
+            let font = if self.entry.part_highlight(part) {
+                root.font_highlight.clone()
+            } else {
+                root.font_value.clone()
+            };
+            commands.entity(e_value).insert((
+                TextColor(self.entry.part_color(part).unwrap_or(root.default_value_color)),
+                TextFont {
+                    font,
+                    font_size: root.fontsize_value,
+                    ..default()
+                },
+            ));
+            let e_row = self.spawn_row(root, commands, Some(e_label), e_value, self.indent_px);
+            commands.entity(e_row).insert(TableWidgetRowMarker::<E> {
+                _pd: PhantomData,
+            });
+            commands.entity(e_widget).add_child(e_row);
+            parts.rows.push((e_row, e_label, e_value));
+        }
+
+        // update the rows we have, and hide the ones we don't need
+        for (i, &(e_row, e_label, e_value)) in parts.rows.iter().enumerate() {
+            let Some(part) = value_parts.get(i) else {
+                if let Ok(mut node) = q_row.get_mut(e_row) {
+                    node.display = Display::None;
+                }
+                continue;
+            };
+            if let Ok(mut node) = q_row.get_mut(e_row) {
+                node.display = Display::Flex;
+            }
+            if let Ok((mut text, _, _)) = q_text.get_mut(e_label) {
+                if text.0 != part.label {
+                    *text = Text(part.label.clone());
+                }
+            }
+            if let Ok((mut text, mut color, mut font)) = q_text.get_mut(e_value) {
+                *text = Text(self.entry.format_part(part));
+                *color = TextColor(self.entry.part_color(part)
+                    .unwrap_or(root.default_value_color));
+                if self.entry.part_highlight(part) {
+                    font.font = root.font_highlight.clone();
+                } else {
+                    font.font = root.font_value.clone();
+                }
+            }
+        }
+    }
+
+    fn sort_key(&self) -> i32 {
+        self.entry.sort_key()
+    }
+
+    fn entry(&self) -> &E {
+        &self.entry
+    }
+
+    fn entry_mut(&mut self) -> &mut E {
+        &mut self.entry
+    }
+}